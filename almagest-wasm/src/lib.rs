@@ -331,7 +331,7 @@ mod integration_tests {
 
         assert_relative_eq!(wasm_efficiency, native_efficiency, epsilon = 1e-12);
         assert_relative_eq!(wasm_efficiency, 0.387, epsilon = 0.01);
-        assert!(wasm_efficiency >= 0.0 && wasm_efficiency <= 1.0);
+        assert!((0.0..=1.0).contains(&wasm_efficiency));
     }
 
     // WASM-specific tests (only run in WASM environment)
@@ -0,0 +1,870 @@
+//! # Atmospheric Density
+//!
+//! Density of the upper atmosphere as a function of altitude, for drag on
+//! orbiting facilities and drag and heating on tether tips that dip into the
+//! atmosphere. Three models of increasing fidelity are provided:
+//!
+//! - [`ExponentialAtmosphere`] - piecewise exponential fit to a static
+//!   reference atmosphere (Vallado, Table 8-4, for Earth)
+//! - [`HarrisPriester`] - tabulated night-time minimum and day-time maximum
+//!   densities joined by the diurnal bulge, for moderate solar activity
+//!   (Montenbruck & Gill, Table 3.8); wrap it in [`DiurnalHarrisPriester`]
+//!   with a Sun ephemeris to resolve the bulge along a trajectory
+//! - [`TabulatedAtmosphere`] - global-mean profiles in the spirit of
+//!   NRLMSISE-00, one per level of [`SolarActivity`], interpolated
+//!   log-linearly
+//!
+//! Each body that has an atmosphere carries its reference data in
+//! [`CelestialBody::atmosphere`], and [`Atmosphere::for_body`] picks one of
+//! the models for it. Every model implements [`AtmosphereModel`], which is
+//! what [`forces::Drag`](crate::forces::Drag) consumes.
+//!
+//! The tables are representative, rounded values meant for mission design
+//! trades and decay estimates; they are not a substitute for a full
+//! empirical model driven by measured solar and geomagnetic indices.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::atmosphere::{Atmosphere, AtmosphereKind, AtmosphereModel, SolarActivity};
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::utils::Meters;
+//!
+//! let quiet = Atmosphere::for_body(&EARTH, AtmosphereKind::Tabulated(SolarActivity::Low))
+//!     .expect("Earth has an atmosphere");
+//! let active = Atmosphere::for_body(&EARTH, AtmosphereKind::Tabulated(SolarActivity::High))
+//!     .expect("Earth has an atmosphere");
+//!
+//! let altitude = Meters(400_000.0);
+//! assert!(active.density(altitude).0 > 10.0 * quiet.density(altitude).0);
+//! ```
+
+use libm::{exp, log, pow, sincos, sqrt, tgamma};
+
+use crate::celestials::CelestialBody;
+use crate::utils::{
+    KilogramsPerMetersCubed, Kilometers, Meters, MetersPerSecond, PI, Pascals, Real, Seconds,
+    Vector3, WattsPerMetersSquared,
+};
+
+/// Angle by which the Harris-Priester density bulge trails the Sun: 30°
+const BULGE_LAG: Real = core::f64::consts::PI / 6.0;
+
+/// A source of atmospheric density.
+///
+/// Any closure `Fn(Meters) -> KilogramsPerMetersCubed` is also a model.
+pub trait AtmosphereModel {
+    /// Density at `altitude` above the body's equatorial radius
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed;
+
+    /// Density at time `t` at `position`, `altitude` above the body's
+    /// equatorial radius, for models that vary with more than altitude. Defaults to
+    /// [`density`](Self::density).
+    fn density_at(
+        &self,
+        _t: Seconds,
+        _position: &Vector3,
+        altitude: Meters,
+    ) -> KilogramsPerMetersCubed {
+        self.density(altitude)
+    }
+}
+
+impl<F: Fn(Meters) -> KilogramsPerMetersCubed> AtmosphereModel for F {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        self(altitude)
+    }
+}
+
+/// Level of solar extreme-ultraviolet output, which heats and expands the
+/// thermosphere. Roughly F10.7 ≈ 70, 140 and 230.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolarActivity {
+    Low,
+    Moderate,
+    High,
+}
+
+/// One layer of a piecewise exponential atmosphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExponentialLayer {
+    pub base_altitude: Kilometers,
+    pub base_density: KilogramsPerMetersCubed,
+    pub scale_height: Kilometers,
+}
+
+/// Harris-Priester density extremes at one altitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DensityBounds {
+    pub altitude: Kilometers,
+    /// Night-time density, at the antapex of the diurnal bulge
+    pub minimum: KilogramsPerMetersCubed,
+    /// Day-time density, at the apex of the diurnal bulge
+    pub maximum: KilogramsPerMetersCubed,
+}
+
+/// Mean density at one altitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DensitySample {
+    pub altitude: Kilometers,
+    pub density: KilogramsPerMetersCubed,
+}
+
+/// Reference atmosphere data for a body.
+#[derive(Debug)]
+pub struct BodyAtmosphere<'a> {
+    /// Layers for [`ExponentialAtmosphere`], in increasing altitude
+    pub exponential: &'a [ExponentialLayer],
+    /// Table for [`HarrisPriester`], in increasing altitude; empty where the
+    /// model is not defined
+    pub harris_priester: &'a [DensityBounds],
+    /// Profiles for [`TabulatedAtmosphere`] at low, moderate and high solar
+    /// activity, in increasing altitude
+    pub profiles: [&'a [DensitySample]; 3],
+    /// Sutton-Graves constant for stagnation-point heating, in kg^½/m,
+    /// which depends on the gas composition
+    pub sutton_graves: Real,
+}
+
+/// Density decaying exponentially within each layer,
+/// `ρ = ρ₀ exp(-(h - h₀)/H)`.
+///
+/// Below the first layer its profile is extended downward; above the top
+/// layer, the top layer's profile continues.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExponentialAtmosphere<'a> {
+    layers: &'a [ExponentialLayer],
+}
+
+impl<'a> ExponentialAtmosphere<'a> {
+    pub fn new(layers: &'a [ExponentialLayer]) -> Result<Self, &'static str> {
+        if layers.is_empty() {
+            return Err("Atmosphere needs at least one layer");
+        }
+        if layers
+            .iter()
+            .any(|layer| layer.base_density.0 <= 0.0 || layer.scale_height.value() <= 0.0)
+        {
+            return Err("Layer densities and scale heights must be positive");
+        }
+        if !increasing(layers.iter().map(|layer| layer.base_altitude)) {
+            return Err("Layers must be in increasing order of altitude");
+        }
+        Ok(Self { layers })
+    }
+}
+
+impl AtmosphereModel for ExponentialAtmosphere<'_> {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        let h = Kilometers::from(altitude).value();
+        let layer = self
+            .layers
+            .iter()
+            .rev()
+            .find(|layer| layer.base_altitude.value() <= h)
+            .unwrap_or(&self.layers[0]);
+        KilogramsPerMetersCubed(
+            layer.base_density.0
+                * exp(-(h - layer.base_altitude.value()) / layer.scale_height.value()),
+        )
+    }
+}
+
+/// The Harris-Priester model: exponential interpolation of tabulated
+/// minimum and maximum densities, blended by the angle ψ from the apex of
+/// the diurnal bulge,
+///
+/// `ρ = ρ_min + (ρ_max - ρ_min) cosⁿ(ψ/2)`
+///
+/// The bulge lags the Sun by 30° in right ascension. The exponent `n` is
+/// about 2 for low-inclination orbits and 6 for polar ones. Above the table
+/// the density is zero; below it, the lowest interval is extended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarrisPriester<'a> {
+    table: &'a [DensityBounds],
+    pub exponent: Real,
+}
+
+impl<'a> HarrisPriester<'a> {
+    pub fn new(table: &'a [DensityBounds]) -> Result<Self, &'static str> {
+        if table.len() < 2 {
+            return Err("Harris-Priester table needs at least two altitudes");
+        }
+        if table
+            .iter()
+            .any(|row| row.minimum.0 <= 0.0 || row.maximum.0 < row.minimum.0)
+        {
+            return Err("Densities must be positive with maximum at least minimum");
+        }
+        if !increasing(table.iter().map(|row| row.altitude)) {
+            return Err("Table must be in increasing order of altitude");
+        }
+        Ok(Self {
+            table,
+            exponent: 2.0,
+        })
+    }
+
+    /// Night-time and day-time densities at `altitude`
+    pub fn bounds(&self, altitude: Meters) -> (KilogramsPerMetersCubed, KilogramsPerMetersCubed) {
+        let h = Kilometers::from(altitude).value();
+        let top = self.table[self.table.len() - 1].altitude.value();
+        if h > top {
+            return (KilogramsPerMetersCubed(0.0), KilogramsPerMetersCubed(0.0));
+        }
+        let i = interval(self.table.iter().map(|row| row.altitude.value()), h);
+        let (lower, upper) = (&self.table[i], &self.table[i + 1]);
+        let between = |low: Real, high: Real| {
+            log_interpolate(h, lower.altitude.value(), low, upper.altitude.value(), high)
+        };
+        (
+            KilogramsPerMetersCubed(between(lower.minimum.0, upper.minimum.0)),
+            KilogramsPerMetersCubed(between(lower.maximum.0, upper.maximum.0)),
+        )
+    }
+
+    /// Density at `altitude` where the angle from the bulge apex has cosine
+    /// `cos_psi`
+    pub fn diurnal_density(&self, altitude: Meters, cos_psi: Real) -> KilogramsPerMetersCubed {
+        let (min, max) = self.bounds(altitude);
+        let weight = pow(0.5 * (1.0 + cos_psi.clamp(-1.0, 1.0)), 0.5 * self.exponent);
+        KilogramsPerMetersCubed(min.0 + (max.0 - min.0) * weight)
+    }
+
+    /// Cosine of the angle between `position` and the apex of the diurnal
+    /// bulge, given the Sun's direction in the same equatorial frame
+    pub fn bulge_cosine(position: &Vector3, sun: &Vector3) -> Real {
+        let (sin, cos) = sincos(BULGE_LAG);
+        let apex = Vector3::new(cos * sun.x - sin * sun.y, sin * sun.x + cos * sun.y, sun.z);
+        position.unit().dot(&apex.unit())
+    }
+}
+
+/// Without a Sun direction, the density averaged around a circular orbit
+/// through the bulge apex, which is what orbit-averaged decay needs.
+///
+/// The mean of `cosⁿ(ψ/2)` around such an orbit is
+/// `Γ((n+1)/2) / (√π Γ(n/2+1))`. For the default exponent of 2 it is ½ for
+/// every orbit plane, so the density is the midpoint of the night and day
+/// values. Use [`DiurnalHarrisPriester`] to follow the bulge along a
+/// trajectory instead.
+impl AtmosphereModel for HarrisPriester<'_> {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        let (min, max) = self.bounds(altitude);
+        let n = self.exponent;
+        let weight = tgamma(0.5 * (n + 1.0)) / (sqrt(PI) * tgamma(0.5 * n + 1.0));
+        KilogramsPerMetersCubed(min.0 + (max.0 - min.0) * weight)
+    }
+}
+
+/// The Harris-Priester model with a Sun ephemeris, so that
+/// [`density_at`](AtmosphereModel::density_at) places each position
+/// relative to the diurnal bulge. This is the form
+/// [`forces::Drag`](crate::forces::Drag) needs to see the day-night
+/// difference; [`density`](AtmosphereModel::density) alone is still the
+/// orbit average.
+#[derive(Clone, Copy, Debug)]
+pub struct DiurnalHarrisPriester<'a, E> {
+    pub model: HarrisPriester<'a>,
+    /// Position of the Sun relative to the body at time `t`, in the same
+    /// equatorial frame as the positions passed to the model
+    pub sun: E,
+}
+
+impl<'a, E: Fn(Seconds) -> Vector3> DiurnalHarrisPriester<'a, E> {
+    pub fn new(model: HarrisPriester<'a>, sun: E) -> Self {
+        Self { model, sun }
+    }
+}
+
+impl<E: Fn(Seconds) -> Vector3> AtmosphereModel for DiurnalHarrisPriester<'_, E> {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        self.model.density(altitude)
+    }
+
+    fn density_at(
+        &self,
+        t: Seconds,
+        position: &Vector3,
+        altitude: Meters,
+    ) -> KilogramsPerMetersCubed {
+        let cos_psi = HarrisPriester::bulge_cosine(position, &(self.sun)(t));
+        self.model.diurnal_density(altitude, cos_psi)
+    }
+}
+
+/// A mean density profile interpolated log-linearly between samples, and
+/// extrapolated from the nearest interval outside them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabulatedAtmosphere<'a> {
+    samples: &'a [DensitySample],
+}
+
+impl<'a> TabulatedAtmosphere<'a> {
+    pub fn new(samples: &'a [DensitySample]) -> Result<Self, &'static str> {
+        if samples.len() < 2 {
+            return Err("Density profile needs at least two altitudes");
+        }
+        if samples.iter().any(|sample| sample.density.0 <= 0.0) {
+            return Err("Densities must be positive");
+        }
+        if !increasing(samples.iter().map(|sample| sample.altitude)) {
+            return Err("Profile must be in increasing order of altitude");
+        }
+        Ok(Self { samples })
+    }
+}
+
+impl AtmosphereModel for TabulatedAtmosphere<'_> {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        let h = Kilometers::from(altitude).value();
+        let i = interval(self.samples.iter().map(|sample| sample.altitude.value()), h);
+        let (lower, upper) = (&self.samples[i], &self.samples[i + 1]);
+        KilogramsPerMetersCubed(log_interpolate(
+            h,
+            lower.altitude.value(),
+            lower.density.0,
+            upper.altitude.value(),
+            upper.density.0,
+        ))
+    }
+}
+
+/// Which density model to build for a body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AtmosphereKind {
+    Exponential,
+    HarrisPriester,
+    Tabulated(SolarActivity),
+}
+
+/// Any of the density models, chosen at run time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Atmosphere<'a> {
+    Exponential(ExponentialAtmosphere<'a>),
+    HarrisPriester(HarrisPriester<'a>),
+    Tabulated(TabulatedAtmosphere<'a>),
+}
+
+impl<'a> Atmosphere<'a> {
+    /// The `kind` of model for `body`, from its reference data.
+    ///
+    /// Fails for bodies without an atmosphere, and for Harris-Priester on
+    /// bodies other than Earth, where it is not defined.
+    pub fn for_body(body: &CelestialBody<'a>, kind: AtmosphereKind) -> Result<Self, &'static str> {
+        let data = body.atmosphere.ok_or("Body has no atmosphere")?;
+        match kind {
+            AtmosphereKind::Exponential => {
+                ExponentialAtmosphere::new(data.exponential).map(Self::Exponential)
+            }
+            AtmosphereKind::HarrisPriester => {
+                if data.harris_priester.is_empty() {
+                    return Err("No Harris-Priester table for this body");
+                }
+                HarrisPriester::new(data.harris_priester).map(Self::HarrisPriester)
+            }
+            AtmosphereKind::Tabulated(activity) => {
+                let profile = match activity {
+                    SolarActivity::Low => data.profiles[0],
+                    SolarActivity::Moderate => data.profiles[1],
+                    SolarActivity::High => data.profiles[2],
+                };
+                TabulatedAtmosphere::new(profile).map(Self::Tabulated)
+            }
+        }
+    }
+}
+
+impl AtmosphereModel for Atmosphere<'_> {
+    fn density(&self, altitude: Meters) -> KilogramsPerMetersCubed {
+        match self {
+            Self::Exponential(model) => model.density(altitude),
+            Self::HarrisPriester(model) => model.density(altitude),
+            Self::Tabulated(model) => model.density(altitude),
+        }
+    }
+}
+
+/// Dynamic pressure `q = ½ρv²` on a surface moving at `speed` relative to
+/// the air
+pub fn dynamic_pressure(density: KilogramsPerMetersCubed, speed: MetersPerSecond) -> Pascals {
+    Pascals(0.5 * density.0 * speed.value() * speed.value())
+}
+
+/// Convective heat flux at the stagnation point of a blunt body, by the
+/// Sutton-Graves relation `q = k √(ρ/rₙ) v³`.
+///
+/// `nose_radius` is the radius of curvature of the leading surface, such as
+/// the tip of a tether end mass. The constant `k` depends on the gas
+/// composition and comes from the body's atmosphere data.
+pub fn stagnation_heat_flux(
+    body: &CelestialBody,
+    density: KilogramsPerMetersCubed,
+    speed: MetersPerSecond,
+    nose_radius: Meters,
+) -> Result<WattsPerMetersSquared, &'static str> {
+    let data = body.atmosphere.ok_or("Body has no atmosphere")?;
+    if nose_radius.value() <= 0.0 || !nose_radius.value().is_finite() {
+        return Err("Nose radius must be positive");
+    }
+    if density.0 < 0.0 || !density.0.is_finite() {
+        return Err("Density must be non-negative");
+    }
+    let v = speed.value();
+    Ok(WattsPerMetersSquared(
+        data.sutton_graves * sqrt(density.0 / nose_radius.value()) * v * v * v,
+    ))
+}
+
+/// Index of the table interval used for altitude `h`, clamped to the first
+/// and last intervals
+fn interval(altitudes: impl Iterator<Item = Real>, h: Real) -> usize {
+    let mut count = 0;
+    let mut below = 0;
+    for (i, altitude) in altitudes.enumerate() {
+        if altitude <= h {
+            below = i;
+        }
+        count = i + 1;
+    }
+    below.min(count - 2)
+}
+
+/// Exponential interpolation between `(h0, rho0)` and `(h1, rho1)`
+fn log_interpolate(h: Real, h0: Real, rho0: Real, h1: Real, rho1: Real) -> Real {
+    let t = (h - h0) / (h1 - h0);
+    exp(log(rho0) + t * (log(rho1) - log(rho0)))
+}
+
+fn increasing(mut altitudes: impl Iterator<Item = Kilometers>) -> bool {
+    let Some(mut previous) = altitudes.next() else {
+        return true;
+    };
+    for altitude in altitudes {
+        if altitude.value() <= previous.value() {
+            return false;
+        }
+        previous = altitude;
+    }
+    true
+}
+
+const fn layer(base_km: Real, density: Real, scale_km: Real) -> ExponentialLayer {
+    ExponentialLayer {
+        base_altitude: Kilometers(base_km),
+        base_density: KilogramsPerMetersCubed(density),
+        scale_height: Kilometers(scale_km),
+    }
+}
+
+/// Harris-Priester rows are conventionally tabulated in g/km³
+const fn bounds(km: Real, minimum: Real, maximum: Real) -> DensityBounds {
+    DensityBounds {
+        altitude: Kilometers(km),
+        minimum: KilogramsPerMetersCubed(minimum * 1e-12),
+        maximum: KilogramsPerMetersCubed(maximum * 1e-12),
+    }
+}
+
+const fn sample(km: Real, density: Real) -> DensitySample {
+    DensitySample {
+        altitude: Kilometers(km),
+        density: KilogramsPerMetersCubed(density),
+    }
+}
+
+/// Reference data for Earth's atmosphere.
+///
+/// # References
+/// - Vallado, *Fundamentals of Astrodynamics and Applications*, Table 8-4
+/// - Montenbruck & Gill, *Satellite Orbits*, Table 3.8
+/// - NRLMSISE-00 global-mean profiles, rounded
+pub const EARTH_ATMOSPHERE: BodyAtmosphere<'static> = BodyAtmosphere {
+    exponential: &[
+        layer(0.0, 1.225, 7.249),
+        layer(25.0, 3.899e-2, 6.349),
+        layer(30.0, 1.774e-2, 6.682),
+        layer(40.0, 3.972e-3, 7.554),
+        layer(50.0, 1.057e-3, 8.382),
+        layer(60.0, 3.206e-4, 7.714),
+        layer(70.0, 8.770e-5, 6.549),
+        layer(80.0, 1.905e-5, 5.799),
+        layer(90.0, 3.396e-6, 5.382),
+        layer(100.0, 5.297e-7, 5.877),
+        layer(110.0, 9.661e-8, 7.263),
+        layer(120.0, 2.438e-8, 9.473),
+        layer(130.0, 8.484e-9, 12.636),
+        layer(140.0, 3.845e-9, 16.149),
+        layer(150.0, 2.070e-9, 22.523),
+        layer(180.0, 5.464e-10, 29.740),
+        layer(200.0, 2.789e-10, 37.105),
+        layer(250.0, 7.248e-11, 45.546),
+        layer(300.0, 2.418e-11, 53.628),
+        layer(350.0, 9.518e-12, 53.298),
+        layer(400.0, 3.725e-12, 58.515),
+        layer(450.0, 1.585e-12, 60.828),
+        layer(500.0, 6.967e-13, 63.822),
+        layer(600.0, 1.454e-13, 71.835),
+        layer(700.0, 3.614e-14, 88.667),
+        layer(800.0, 1.170e-14, 124.64),
+        layer(900.0, 5.245e-15, 181.05),
+        layer(1000.0, 3.019e-15, 268.00),
+    ],
+    harris_priester: &[
+        bounds(100.0, 497_400.0, 497_400.0),
+        bounds(120.0, 24_900.0, 24_900.0),
+        bounds(130.0, 8_377.0, 8_710.0),
+        bounds(140.0, 3_899.0, 4_059.0),
+        bounds(150.0, 2_122.0, 2_215.0),
+        bounds(160.0, 1_263.0, 1_344.0),
+        bounds(170.0, 800.8, 875.8),
+        bounds(180.0, 528.3, 601.0),
+        bounds(190.0, 361.7, 429.7),
+        bounds(200.0, 255.7, 316.2),
+        bounds(210.0, 183.9, 239.6),
+        bounds(220.0, 134.1, 185.3),
+        bounds(230.0, 99.49, 145.5),
+        bounds(240.0, 74.88, 115.7),
+        bounds(250.0, 57.09, 93.08),
+        bounds(260.0, 44.03, 75.55),
+        bounds(270.0, 34.30, 61.82),
+        bounds(280.0, 26.97, 50.95),
+        bounds(290.0, 21.39, 42.26),
+        bounds(300.0, 17.08, 35.26),
+        bounds(320.0, 10.99, 25.11),
+        bounds(340.0, 7.214, 18.19),
+        bounds(360.0, 4.824, 13.37),
+        bounds(380.0, 3.274, 9.955),
+        bounds(400.0, 2.249, 7.492),
+        bounds(420.0, 1.558, 5.684),
+        bounds(440.0, 1.091, 4.355),
+        bounds(460.0, 0.7701, 3.362),
+        bounds(480.0, 0.5474, 2.612),
+        bounds(500.0, 0.3916, 2.042),
+        bounds(520.0, 0.2819, 1.605),
+        bounds(540.0, 0.2042, 1.267),
+        bounds(560.0, 0.1488, 1.005),
+        bounds(580.0, 0.1092, 0.7997),
+        bounds(600.0, 0.08070, 0.6390),
+        bounds(620.0, 0.06012, 0.5123),
+        bounds(640.0, 0.04519, 0.4121),
+        bounds(660.0, 0.03430, 0.3325),
+        bounds(680.0, 0.02632, 0.2691),
+        bounds(700.0, 0.02043, 0.2185),
+        bounds(720.0, 0.01607, 0.1779),
+        bounds(740.0, 0.01281, 0.1452),
+        bounds(760.0, 0.01036, 0.1190),
+        bounds(780.0, 0.008496, 0.09776),
+        bounds(800.0, 0.007069, 0.08059),
+        bounds(840.0, 0.004680, 0.05741),
+        bounds(880.0, 0.003200, 0.04210),
+        bounds(920.0, 0.002210, 0.03130),
+        bounds(960.0, 0.001560, 0.02360),
+        bounds(1000.0, 0.001150, 0.01810),
+    ],
+    profiles: [
+        &[
+            sample(0.0, 1.225),
+            sample(50.0, 1.03e-3),
+            sample(100.0, 5.0e-7),
+            sample(120.0, 2.2e-8),
+            sample(150.0, 1.7e-9),
+            sample(200.0, 1.8e-10),
+            sample(250.0, 3.7e-11),
+            sample(300.0, 9.0e-12),
+            sample(350.0, 2.4e-12),
+            sample(400.0, 7.2e-13),
+            sample(450.0, 2.3e-13),
+            sample(500.0, 8.4e-14),
+            sample(600.0, 1.5e-14),
+            sample(700.0, 4.6e-15),
+            sample(800.0, 2.0e-15),
+            sample(900.0, 1.1e-15),
+            sample(1000.0, 6.9e-16),
+        ],
+        &[
+            sample(0.0, 1.225),
+            sample(50.0, 1.03e-3),
+            sample(100.0, 5.3e-7),
+            sample(120.0, 2.4e-8),
+            sample(150.0, 2.0e-9),
+            sample(200.0, 2.5e-10),
+            sample(250.0, 6.5e-11),
+            sample(300.0, 2.2e-11),
+            sample(350.0, 8.0e-12),
+            sample(400.0, 3.2e-12),
+            sample(450.0, 1.3e-12),
+            sample(500.0, 5.8e-13),
+            sample(600.0, 1.3e-13),
+            sample(700.0, 3.5e-14),
+            sample(800.0, 1.1e-14),
+            sample(900.0, 4.3e-15),
+            sample(1000.0, 2.0e-15),
+        ],
+        &[
+            sample(0.0, 1.225),
+            sample(50.0, 1.03e-3),
+            sample(100.0, 5.6e-7),
+            sample(120.0, 2.6e-8),
+            sample(150.0, 2.4e-9),
+            sample(200.0, 3.6e-10),
+            sample(250.0, 1.2e-10),
+            sample(300.0, 4.8e-11),
+            sample(350.0, 2.2e-11),
+            sample(400.0, 1.1e-11),
+            sample(450.0, 5.6e-12),
+            sample(500.0, 3.0e-12),
+            sample(600.0, 9.2e-13),
+            sample(700.0, 3.0e-13),
+            sample(800.0, 1.1e-13),
+            sample(900.0, 4.2e-14),
+            sample(1000.0, 1.7e-14),
+        ],
+    ],
+    sutton_graves: 1.7415e-4,
+};
+
+/// Mean density profile of the Martian atmosphere, shared by every level
+/// of solar activity
+const MARS_PROFILE: &[DensitySample] = &[
+    sample(0.0, 2.0e-2),
+    sample(10.0, 8.5e-3),
+    sample(20.0, 3.4e-3),
+    sample(30.0, 1.3e-3),
+    sample(40.0, 4.7e-4),
+    sample(50.0, 1.6e-4),
+    sample(60.0, 5.2e-5),
+    sample(80.0, 4.5e-6),
+    sample(100.0, 3.0e-7),
+    sample(120.0, 1.5e-8),
+    sample(150.0, 5.0e-10),
+    sample(200.0, 1.0e-11),
+    sample(250.0, 3.0e-13),
+    sample(300.0, 1.0e-14),
+];
+
+/// Reference data for the atmosphere of Mars.
+///
+/// The exponential model uses the surface density and scale height from
+/// the NASA Mars Fact Sheet. Harris-Priester is not defined for Mars.
+///
+/// # References
+/// - NASA Mars Fact Sheet
+/// - Mars Global Surveyor and MAVEN aerobraking and periapsis densities, rounded
+pub const MARS_ATMOSPHERE: BodyAtmosphere<'static> = BodyAtmosphere {
+    exponential: &[layer(0.0, 2.0e-2, 11.1)],
+    harris_priester: &[],
+    profiles: [MARS_PROFILE, MARS_PROFILE, MARS_PROFILE],
+    sutton_graves: 1.9027e-4,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS, MOON};
+    use crate::test_helpers::earth_atmosphere;
+    use crate::utils::TAU;
+    use approx::assert_relative_eq;
+
+    fn km(h: Real) -> Meters {
+        Meters(h * 1000.0)
+    }
+
+    #[test]
+    fn test_exponential_earth() {
+        let model = earth_atmosphere(AtmosphereKind::Exponential);
+        assert_relative_eq!(model.density(km(0.0)).0, 1.225);
+        assert_relative_eq!(model.density(km(400.0)).0, 3.725e-12);
+        // Vallado Example 8-4: 747.2 km
+        assert_relative_eq!(
+            model.density(km(747.2)).0,
+            2.1219854e-14,
+            max_relative = 1e-4
+        );
+        // Layers join almost continuously
+        let below = 3.396e-6 * exp(-10.0 / 5.382);
+        assert_relative_eq!(model.density(km(100.0)).0, below, max_relative = 0.01);
+    }
+
+    #[test]
+    fn test_exponential_mars() {
+        let model = Atmosphere::for_body(&MARS, AtmosphereKind::Exponential).unwrap();
+        assert_relative_eq!(model.density(km(11.1)).0, 2.0e-2 / core::f64::consts::E);
+    }
+
+    #[test]
+    fn test_harris_priester() {
+        let table = EARTH_ATMOSPHERE.harris_priester;
+        let model = HarrisPriester::new(table).unwrap();
+        let (min, max) = model.bounds(km(400.0));
+        assert_relative_eq!(min.0, 2.249e-12, max_relative = 1e-12);
+        assert_relative_eq!(max.0, 7.492e-12, max_relative = 1e-12);
+
+        // Day side at the apex, night side opposite it
+        assert_relative_eq!(model.diurnal_density(km(400.0), 1.0).0, max.0);
+        assert_relative_eq!(model.diurnal_density(km(400.0), -1.0).0, min.0);
+        let midway = model.diurnal_density(km(410.0), 0.0).0;
+        let (low, high) = model.bounds(km(410.0));
+        assert!(midway > low.0 && midway < high.0);
+
+        // Nothing above the table
+        assert_eq!(model.density(km(1_200.0)).0, 0.0);
+    }
+
+    #[test]
+    fn test_harris_priester_orbit_average() {
+        let mut model = HarrisPriester::new(EARTH_ATMOSPHERE.harris_priester).unwrap();
+        let (min, max) = model.bounds(km(500.0));
+        assert_relative_eq!(
+            model.density(km(500.0)).0,
+            0.5 * (min.0 + max.0),
+            max_relative = 1e-12
+        );
+
+        // Around a great circle through the apex, for a polar exponent
+        model.exponent = 6.0;
+        let samples = 3_600;
+        let mean = (0..samples)
+            .map(|i| {
+                let psi = TAU * i as Real / samples as Real;
+                model.diurnal_density(km(500.0), libm::cos(psi)).0
+            })
+            .sum::<Real>()
+            / samples as Real;
+        assert_relative_eq!(model.density(km(500.0)).0, mean, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_diurnal_harris_priester() {
+        let model = HarrisPriester::new(EARTH_ATMOSPHERE.harris_priester).unwrap();
+        let sun = |_t: Seconds| Vector3::new(1.5e11, 0.0, 0.0);
+        let diurnal = DiurnalHarrisPriester::new(model, sun);
+        let (min, max) = model.bounds(km(400.0));
+
+        let (sin, cos) = sincos(BULGE_LAG);
+        let apex = Vector3::new(6.778e6 * cos, 6.778e6 * sin, 0.0);
+        let t = Seconds(0.0);
+        assert_relative_eq!(
+            diurnal.density_at(t, &apex, km(400.0)).0,
+            max.0,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            diurnal.density_at(t, &-apex, km(400.0)).0,
+            min.0,
+            max_relative = 1e-12
+        );
+        // Without a position, the orbit average
+        assert_eq!(diurnal.density(km(400.0)), model.density(km(400.0)));
+    }
+
+    #[test]
+    fn test_bulge_trails_the_sun() {
+        let sun = Vector3::new(1.0, 0.0, 0.0);
+        // 30° east of the Sun is the apex
+        let (sin, cos) = sincos(BULGE_LAG);
+        let apex = Vector3::new(7e6 * cos, 7e6 * sin, 0.0);
+        assert_relative_eq!(
+            HarrisPriester::bulge_cosine(&apex, &sun),
+            1.0,
+            epsilon = 1e-15
+        );
+        assert!(HarrisPriester::bulge_cosine(&Vector3::new(-7e6, 0.0, 0.0), &sun) < -0.8);
+    }
+
+    #[test]
+    fn test_tabulated_solar_activity() {
+        let density = |activity| {
+            earth_atmosphere(AtmosphereKind::Tabulated(activity))
+                .density(km(400.0))
+                .0
+        };
+        let (low, moderate, high) = (
+            density(SolarActivity::Low),
+            density(SolarActivity::Moderate),
+            density(SolarActivity::High),
+        );
+        assert!(low < moderate && moderate < high);
+        assert_relative_eq!(moderate, 3.2e-12);
+
+        // Log-linear between samples
+        let model = earth_atmosphere(AtmosphereKind::Tabulated(SolarActivity::Moderate));
+        let geometric_mean = sqrt(3.2e-12 * 1.3e-12);
+        assert_relative_eq!(
+            model.density(km(425.0)).0,
+            geometric_mean,
+            max_relative = 1e-12
+        );
+
+        // All three models agree to within a factor of a few at 400 km
+        let exponential = earth_atmosphere(AtmosphereKind::Exponential)
+            .density(km(400.0))
+            .0;
+        let hp = earth_atmosphere(AtmosphereKind::HarrisPriester)
+            .density(km(400.0))
+            .0;
+        assert!(hp / moderate < 3.0 && moderate / hp < 3.0);
+        assert!(exponential / moderate < 3.0 && moderate / exponential < 3.0);
+    }
+
+    #[test]
+    fn test_selection_per_body() {
+        assert!(Atmosphere::for_body(&MOON, AtmosphereKind::Exponential).is_err());
+        assert!(Atmosphere::for_body(&MARS, AtmosphereKind::HarrisPriester).is_err());
+        let mars = Atmosphere::for_body(&MARS, AtmosphereKind::Tabulated(SolarActivity::High));
+        assert!(mars.unwrap().density(km(100.0)).0 > 1e-7);
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        assert!(ExponentialAtmosphere::new(&[]).is_err());
+        let descending = [sample(100.0, 1e-7), sample(50.0, 1e-3)];
+        assert!(TabulatedAtmosphere::new(&descending).is_err());
+        let inverted = [bounds(100.0, 2.0, 1.0), bounds(200.0, 1.0, 2.0)];
+        assert!(HarrisPriester::new(&inverted).is_err());
+    }
+
+    #[test]
+    fn test_closure_is_a_model() {
+        let constant = |_h: Meters| KilogramsPerMetersCubed(1e-12);
+        assert_eq!(constant.density(km(300.0)).0, 1e-12);
+    }
+
+    #[test]
+    fn test_drives_drag() {
+        use crate::forces::{Drag, ForceModel};
+        use crate::kepler::StateVector;
+        use crate::utils::{Kilograms, MetersSquared, Seconds};
+
+        let model = earth_atmosphere(AtmosphereKind::HarrisPriester);
+        let drag = Drag::new(&EARTH, 2.2, MetersSquared(10.0), Kilograms(1_000.0), model);
+        let r = 6_378_137.0 + 300_000.0;
+        let state = StateVector {
+            position: Vector3::new(r, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 7_730.0, 0.0),
+        };
+        let a = drag.acceleration(Seconds(0.0), &state);
+        let v_rel = drag.relative_velocity(&state).norm();
+        let expected = 0.5 * model.density(km(300.0)).0 * 2.2 * 10.0 / 1_000.0 * v_rel * v_rel;
+        assert_relative_eq!(a.norm(), expected, max_relative = 1e-9);
+        assert!(a.y < 0.0);
+    }
+
+    #[test]
+    fn test_tether_tip_loads() {
+        // A rotovator tip passing through 150 km at 3 km/s relative to the air
+        let model = earth_atmosphere(AtmosphereKind::Tabulated(SolarActivity::Moderate));
+        let rho = model.density(km(150.0));
+        let v = MetersPerSecond(3_000.0);
+        assert_relative_eq!(dynamic_pressure(rho, v).value(), 0.5 * 2.0e-9 * 9e6);
+
+        let q = stagnation_heat_flux(&EARTH, rho, v, Meters(0.5)).unwrap();
+        assert_relative_eq!(
+            q.value(),
+            1.7415e-4 * sqrt(2.0e-9 / 0.5) * 2.7e10,
+            max_relative = 1e-12
+        );
+        assert!(stagnation_heat_flux(&MOON, rho, v, Meters(0.5)).is_err());
+        assert!(stagnation_heat_flux(&EARTH, rho, v, Meters(0.0)).is_err());
+    }
+}
@@ -11,19 +11,25 @@
 //! - **Mass**: Used for gravitational parameter calculations (μ = GM)
 //! - **Radius**: Used for surface gravity, escape velocity, and orbital altitude references
 //! - **Name**: Human-readable identification
+//! - **Shape and spin**: Equatorial radius, rotation rate and zonal harmonics,
+//!   used by the perturbing force models in [`forces`](crate::forces)
+//! - **Orbit**: Mean distance from the primary and sphere-of-influence radius,
+//!   used by [`patched_conics`](crate::patched_conics)
 //!
 //! ## Usage
 //!
 //! ```rust
 //! use almagest::celestials::celestial_bodies::{EARTH, MARS};
+//! use almagest::utils::Meters;
 //!
 //! // Access predefined celestial bodies
 //! println!("Earth mass: {:.3e} kg", EARTH.mass.0);
 //! println!("Mars radius: {} km", MARS.radius.0);
 //!
-//! // Use in orbital calculations
-//! // TODO
-//! // let earth_surface_gravity = calculate_surface_gravity(&EARTH);
+//! // Use in orbital calculations: surface gravity g = μ / r²
+//! let r = Meters::from(EARTH.radius).value();
+//! let g = EARTH.mu.value() / (r * r);
+//! assert!((g - 9.82).abs() < 0.01);
 //! ```
 //!
 //! ## Data Sources
@@ -35,7 +41,8 @@
 //!
 //! Values are given in SI units with sufficient precision for most astrodynamics applications.
 
-use crate::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared};
+use crate::atmosphere::BodyAtmosphere;
+use crate::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared, RadiansPerSecond, Real};
 
 /// Represents a celestial body with fundamental physical properties.
 ///
@@ -46,7 +53,7 @@ use crate::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared};
 ///
 /// ```rust
 /// use almagest::celestials::{CelestialBody, celestial_bodies::EARTH};
-/// use almagest::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared};
+/// use almagest::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared, RadiansPerSecond};
 ///
 /// // Using predefined bodies
 /// let earth = EARTH;
@@ -54,12 +61,23 @@ use crate::utils::{Kilograms, Kilometers, MetersCubedPerSecondSquared};
 ///
 /// // Creating custom bodies
 /// let custom_body = CelestialBody {
-///     name: "Asteroid Ceres",
-///     mass: Kilograms(9.1e20),      // kg
-///     radius: Kilometers(473.0),    // km (mean radius)
-///     mu: MetersCubedPerSecondSquared(6.3e10), // m³/s²
+///     equatorial_radius: Kilometers(482.1),
+///     rotation_rate: RadiansPerSecond(1.923e-4),
+///     semi_major_axis: Some(Kilometers(413_690_000.0)),
+///     sphere_of_influence: Some(Kilometers(76_000.0)),
+///     ..CelestialBody::new(
+///         "Asteroid Ceres",
+///         Kilograms(9.1e20),                  // kg
+///         Kilometers(473.0),                  // km (mean radius)
+///         MetersCubedPerSecondSquared(6.3e10), // m³/s²
+///     )
 /// };
 /// ```
+///
+/// The shape, spin, atmosphere and orbit fields were added after the first
+/// four, breaking struct literals that list every field. Build custom bodies
+/// from [`CelestialBody::new`] with struct update syntax, as above, so they
+/// keep compiling as fields are added.
 #[derive(Debug)]
 pub struct CelestialBody<'a> {
     /// Human-readable name of the celestial body
@@ -70,6 +88,59 @@ pub struct CelestialBody<'a> {
     pub radius: Kilometers,
     /// Standard gravitational parameter (μ = GM) in m³/s²
     pub mu: MetersCubedPerSecondSquared,
+    /// Reference radius of the gravity field, in kilometers
+    pub equatorial_radius: Kilometers,
+    /// Sidereal rotation rate about the body's z-axis
+    pub rotation_rate: RadiansPerSecond,
+    /// Unnormalized zonal harmonic coefficients, starting with J2
+    pub zonal_harmonics: &'a [Real],
+    /// Reference data for atmospheric density models, if the body has an
+    /// atmosphere
+    pub atmosphere: Option<&'a BodyAtmosphere<'a>>,
+    /// Mean distance from the body it orbits, in kilometers; `None` for the
+    /// Sun
+    pub semi_major_axis: Option<Kilometers>,
+    /// Laplace sphere-of-influence radius `a (m/M)^(2/5)`, in kilometers.
+    /// Inside it the body, rather than its primary, is treated as the
+    /// central body of a patched conic.
+    pub sphere_of_influence: Option<Kilometers>,
+}
+
+impl<'a> CelestialBody<'a> {
+    /// A spherical, non-rotating point mass with no atmosphere or orbit data
+    pub const fn new(
+        name: &'a str,
+        mass: Kilograms,
+        radius: Kilometers,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Self {
+        Self {
+            name,
+            mass,
+            radius,
+            mu,
+            equatorial_radius: radius,
+            rotation_rate: RadiansPerSecond(0.0),
+            zonal_harmonics: &[],
+            atmosphere: None,
+            semi_major_axis: None,
+            sphere_of_influence: None,
+        }
+    }
+
+    /// Zonal harmonic coefficient Jₙ, or zero if the body does not provide it
+    pub fn zonal_harmonic(&self, degree: usize) -> Real {
+        degree
+            .checked_sub(2)
+            .and_then(|index| self.zonal_harmonics.get(index))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Oblateness coefficient J2, the dominant perturbation for most orbits
+    pub fn j2(&self) -> Real {
+        self.zonal_harmonic(2)
+    }
 }
 
 /// Pre-defined celestial bodies with accurate physical properties.
@@ -78,6 +149,7 @@ pub struct CelestialBody<'a> {
 /// with properties sourced from authoritative astronomical databases.
 pub mod celestial_bodies {
     use super::*;
+    use crate::atmosphere::{EARTH_ATMOSPHERE, MARS_ATMOSPHERE};
 
     /// Earth - Third planet from the Sun
     ///
@@ -86,6 +158,7 @@ pub mod celestial_bodies {
     /// - **Radius**: 6,371 km (mean radius)
     /// - **Surface gravity**: ~9.81 m/s²
     /// - **Escape velocity**: ~11.2 km/s
+    /// - **Sphere of influence**: ~924,500 km
     /// - **Standard gravitational parameter (μ)**: 3.986004418×10¹⁴ m³/s²
     ///
     /// # References
@@ -96,6 +169,19 @@ pub mod celestial_bodies {
         mass: Kilograms(5.972e24),
         radius: Kilometers(6_371.0),
         mu: MetersCubedPerSecondSquared(3.986004418e14),
+        equatorial_radius: Kilometers(6_378.137),
+        rotation_rate: RadiansPerSecond(7.292115e-5),
+        // EGM-96, through J6
+        zonal_harmonics: &[
+            1.08262668e-3,
+            -2.53265649e-6,
+            -1.61962159e-6,
+            -2.27296083e-7,
+            5.40681239e-7,
+        ],
+        atmosphere: Some(&EARTH_ATMOSPHERE),
+        semi_major_axis: Some(Kilometers(149_598_023.0)),
+        sphere_of_influence: Some(Kilometers(924_500.0)),
     };
 
     /// Mars - Fourth planet from the Sun, "The Red Planet"
@@ -105,6 +191,7 @@ pub mod celestial_bodies {
     /// - **Radius**: 3,390 km (mean radius)
     /// - **Surface gravity**: ~3.71 m/s² (38% of Earth's)
     /// - **Escape velocity**: ~5.03 km/s
+    /// - **Sphere of influence**: ~577,200 km
     /// - **Day length**: ~24.62 hours (1.026 Earth days)
     /// - **Standard gravitational parameter (μ)**: 4.282837×10¹³ m³/s²
     ///
@@ -116,6 +203,12 @@ pub mod celestial_bodies {
         mass: Kilograms(6.417e23),
         radius: Kilometers(3_390.0),
         mu: MetersCubedPerSecondSquared(4.282837e13),
+        equatorial_radius: Kilometers(3_396.19),
+        rotation_rate: RadiansPerSecond(7.088218e-5),
+        zonal_harmonics: &[1.96045e-3, 3.6e-5],
+        atmosphere: Some(&MARS_ATMOSPHERE),
+        semi_major_axis: Some(Kilometers(227_939_200.0)),
+        sphere_of_influence: Some(Kilometers(577_200.0)),
     };
 
     /// Moon - Earth's natural satellite
@@ -126,6 +219,7 @@ pub mod celestial_bodies {
     /// - **Surface gravity**: ~1.62 m/s² (16.5% of Earth's)
     /// - **Escape velocity**: ~2.38 km/s
     /// - **Orbital distance**: ~384,400 km from Earth (mean)
+    /// - **Sphere of influence**: ~66,200 km
     /// - **Standard gravitational parameter (μ)**: 4.9028×10¹² m³/s²
     ///
     /// # References
@@ -136,5 +230,36 @@ pub mod celestial_bodies {
         mass: Kilograms(7.35e22),
         radius: Kilometers(1_737.48),
         mu: MetersCubedPerSecondSquared(4.9028e12),
+        equatorial_radius: Kilometers(1_738.0),
+        rotation_rate: RadiansPerSecond(2.6617e-6),
+        // GRAIL, through J4
+        zonal_harmonics: &[2.0321568e-4, 8.4759e-6, -9.5919e-6],
+        atmosphere: None,
+        semi_major_axis: Some(Kilometers(384_400.0)),
+        sphere_of_influence: Some(Kilometers(66_200.0)),
+    };
+
+    /// Sun - The star at the center of the Solar System
+    ///
+    /// Physical properties:
+    /// - **Mass**: 1.989×10³⁰ kg
+    /// - **Radius**: 695,700 km (nominal)
+    /// - **Rotation period**: ~25.4 days (sidereal, at the equator)
+    /// - **Standard gravitational parameter (μ)**: 1.32712440018×10²⁰ m³/s²
+    ///
+    /// # References
+    /// - IAU 2015 Resolution B3
+    /// - NASA Sun Fact Sheet
+    pub const SUN: CelestialBody<'static> = CelestialBody {
+        name: "Sun",
+        mass: Kilograms(1.989e30),
+        radius: Kilometers(695_700.0),
+        mu: MetersCubedPerSecondSquared(1.32712440018e20),
+        equatorial_radius: Kilometers(695_700.0),
+        rotation_rate: RadiansPerSecond(2.865e-6),
+        zonal_harmonics: &[2.2e-7],
+        atmosphere: None,
+        semi_major_axis: None,
+        sphere_of_influence: None,
     };
 }
@@ -0,0 +1,539 @@
+//! # Circular Restricted Three-Body Problem
+//!
+//! A spacecraft moving under the gravity of two bodies that circle their
+//! common barycenter, such as the Earth and the Moon. In a frame rotating
+//! with the primaries the problem is time-invariant, and it is usually
+//! written without dimensions:
+//!
+//! - length: the distance between the primaries
+//! - mass: their total mass, with the mass ratio `μ = m₂ / (m₁ + m₂)`
+//! - time: `1/n`, so the primaries complete one revolution in `2π`
+//!
+//! The larger primary sits at `(-μ, 0, 0)` and the smaller at `(1 - μ, 0, 0)`.
+//! The equations of motion are
+//!
+//! - `ẍ - 2ẏ = ∂U/∂x`
+//! - `ÿ + 2ẋ = ∂U/∂y`
+//! - `z̈ = ∂U/∂z`
+//!
+//! with the pseudo-potential `U = ½(x² + y²) + (1 - μ)/r₁ + μ/r₂`. They admit
+//! one integral, the Jacobi constant `C = 2U - v²`. Since `v² ≥ 0`, a
+//! trajectory with a given `C` can never enter the region where `2U < C`;
+//! the boundary is the zero-velocity surface. The five Lagrange points are
+//! the equilibria of the rotating frame, and the Jacobi constants at the
+//! collinear points `L1` and `L2` are the energies at which the necks to the
+//! Moon's vicinity and to escape open.
+//!
+//! Periodic orbits about the Lagrange points and the smaller primary are
+//! generated by differential correction in [`periodic`].
+//!
+//! Two-body estimates such as
+//! [`momentum_exchange_orbital_velocity`](crate::tethers::momentum_exchange_orbital_velocity)
+//! ignore the other primary entirely, which misstates the energy of
+//! cislunar transfers badly; the Jacobi constant is the measure to use
+//! instead.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::{EARTH, MOON};
+//! use almagest::cr3bp::{EARTH_MOON_DISTANCE, LagrangePoint, ThreeBodySystem};
+//!
+//! let system = ThreeBodySystem::new(&EARTH, &MOON, EARTH_MOON_DISTANCE.into())
+//!     .expect("Valid system");
+//! let l1 = system.lagrange_point(LagrangePoint::L1).expect("Converges");
+//! let from_moon = (1.0 - system.mass_ratio() - l1.x) * system.length_unit().value();
+//! println!("L1 is {:.0} km from the Moon", from_moon / 1000.0);
+//!
+//! // Energy needed to pass through the L1 neck
+//! let c1 = system.jacobi_constant_at(LagrangePoint::L1).expect("Converges");
+//! assert!(c1 > system.jacobi_constant_at(LagrangePoint::L2).expect("Converges"));
+//! ```
+
+pub mod periodic;
+
+use libm::{cbrt, fabs, sincos, sqrt};
+
+use crate::celestials::CelestialBody;
+use crate::integrators::{Integrator, integrate};
+use crate::kepler::StateVector;
+use crate::utils::{Kilometers, Meters, MetersPerSecond, Real, Seconds, Vector3};
+
+/// Mean distance between the Earth and the Moon
+pub const EARTH_MOON_DISTANCE: Kilometers = Kilometers(384_400.0);
+
+/// Newton iterations allowed when locating a collinear Lagrange point
+const LAGRANGE_MAX_ITERATIONS: usize = 50;
+
+/// Bisection iterations used to refine a point on a zero-velocity curve
+const CURVE_ITERATIONS: usize = 60;
+
+/// Largest |y| searched when tracing zero-velocity curves
+const CURVE_Y_LIMIT: Real = 2.0;
+
+/// The five equilibrium points of the rotating frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LagrangePoint {
+    /// Between the primaries
+    L1,
+    /// Beyond the smaller primary
+    L2,
+    /// Beyond the larger primary
+    L3,
+    /// Leading the smaller primary by 60°
+    L4,
+    /// Trailing the smaller primary by 60°
+    L5,
+}
+
+impl LagrangePoint {
+    pub const ALL: [LagrangePoint; 5] = [
+        LagrangePoint::L1,
+        LagrangePoint::L2,
+        LagrangePoint::L3,
+        LagrangePoint::L4,
+        LagrangePoint::L5,
+    ];
+}
+
+/// Two primaries on circular orbits about their barycenter, and the
+/// nondimensional units of the rotating frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThreeBodySystem {
+    mass_ratio: Real,
+    length_unit: Meters,
+    time_unit: Seconds,
+}
+
+impl ThreeBodySystem {
+    /// System of `primary` and the lighter `secondary`, `distance` apart.
+    pub fn new(
+        primary: &CelestialBody,
+        secondary: &CelestialBody,
+        distance: Meters,
+    ) -> Result<Self, &'static str> {
+        let (mu1, mu2) = (primary.mu.value(), secondary.mu.value());
+        if mu1 <= 0.0 || mu2 <= 0.0 || !mu1.is_finite() || !mu2.is_finite() {
+            return Err("Gravitational parameters must be positive");
+        }
+        if mu2 > mu1 {
+            return Err("Secondary must not be more massive than the primary");
+        }
+        if distance.value() <= 0.0 || !distance.value().is_finite() {
+            return Err("Distance between the primaries must be positive");
+        }
+        let l = distance.value();
+        Ok(Self {
+            mass_ratio: mu2 / (mu1 + mu2),
+            length_unit: distance,
+            time_unit: Seconds(sqrt(l * l * l / (mu1 + mu2))),
+        })
+    }
+
+    /// `μ = m₂ / (m₁ + m₂)`
+    pub fn mass_ratio(&self) -> Real {
+        self.mass_ratio
+    }
+
+    /// Distance between the primaries
+    pub fn length_unit(&self) -> Meters {
+        self.length_unit
+    }
+
+    /// Inverse of the primaries' mean motion; one revolution takes 2π units
+    pub fn time_unit(&self) -> Seconds {
+        self.time_unit
+    }
+
+    /// Orbital speed of the secondary relative to the primary
+    pub fn velocity_unit(&self) -> MetersPerSecond {
+        MetersPerSecond(self.length_unit.value() / self.time_unit.value())
+    }
+
+    /// Nondimensional position of the larger primary
+    pub fn primary_position(&self) -> Vector3 {
+        Vector3::new(-self.mass_ratio, 0.0, 0.0)
+    }
+
+    /// Nondimensional position of the smaller primary
+    pub fn secondary_position(&self) -> Vector3 {
+        Vector3::new(1.0 - self.mass_ratio, 0.0, 0.0)
+    }
+
+    /// `U = ½(x² + y²) + (1 - μ)/r₁ + μ/r₂`
+    pub fn pseudo_potential(&self, position: &Vector3) -> Real {
+        let mu = self.mass_ratio;
+        let r1 = (*position - self.primary_position()).norm();
+        let r2 = (*position - self.secondary_position()).norm();
+        0.5 * (position.x * position.x + position.y * position.y) + (1.0 - mu) / r1 + mu / r2
+    }
+
+    /// Gradient of the pseudo-potential
+    pub fn pseudo_potential_gradient(&self, position: &Vector3) -> Vector3 {
+        let mu = self.mass_ratio;
+        let d1 = *position - self.primary_position();
+        let d2 = *position - self.secondary_position();
+        let (r1, r2) = (d1.norm(), d2.norm());
+        let centrifugal = Vector3::new(position.x, position.y, 0.0);
+        centrifugal - d1 * ((1.0 - mu) / (r1 * r1 * r1)) - d2 * (mu / (r2 * r2 * r2))
+    }
+
+    /// Matrix of second derivatives of the pseudo-potential, which drives
+    /// the variational equations
+    pub fn pseudo_potential_hessian(&self, position: &Vector3) -> [[Real; 3]; 3] {
+        let mu = self.mass_ratio;
+        let mut hessian = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]];
+        for (mass, center) in [
+            (1.0 - mu, self.primary_position()),
+            (mu, self.secondary_position()),
+        ] {
+            let d = *position - center;
+            let d = [d.x, d.y, d.z];
+            let r2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+            let r3 = r2 * sqrt(r2);
+            for i in 0..3 {
+                for j in 0..3 {
+                    let identity = if i == j { 1.0 } else { 0.0 };
+                    hessian[i][j] -= mass * (identity - 3.0 * d[i] * d[j] / r2) / r3;
+                }
+            }
+        }
+        hessian
+    }
+
+    /// Jacobi constant `C = 2U - v²` of a nondimensional rotating-frame state
+    pub fn jacobi_constant(&self, state: &StateVector) -> Real {
+        let v = state.velocity;
+        2.0 * self.pseudo_potential(&state.position) - v.dot(&v)
+    }
+
+    /// Nondimensional position of a Lagrange point
+    pub fn lagrange_point(&self, point: LagrangePoint) -> Result<Vector3, &'static str> {
+        let mu = self.mass_ratio;
+        let gamma = cbrt(mu / 3.0);
+        let guess = match point {
+            LagrangePoint::L1 => 1.0 - mu - gamma,
+            LagrangePoint::L2 => 1.0 - mu + gamma,
+            LagrangePoint::L3 => -1.0 - 5.0 * mu / 12.0,
+            LagrangePoint::L4 => return Ok(Vector3::new(0.5 - mu, sqrt(0.75), 0.0)),
+            LagrangePoint::L5 => return Ok(Vector3::new(0.5 - mu, -sqrt(0.75), 0.0)),
+        };
+        Ok(Vector3::new(self.collinear_point(guess)?, 0.0, 0.0))
+    }
+
+    /// Jacobi constant of a particle at rest at a Lagrange point, the
+    /// energy at which the zero-velocity surface opens there
+    pub fn jacobi_constant_at(&self, point: LagrangePoint) -> Result<Real, &'static str> {
+        Ok(2.0 * self.pseudo_potential(&self.lagrange_point(point)?))
+    }
+
+    /// Whether a trajectory with Jacobi constant `jacobi` can reach
+    /// `position`, where `2U ≥ C`
+    pub fn is_accessible(&self, position: &Vector3, jacobi: Real) -> bool {
+        2.0 * self.pseudo_potential(position) >= jacobi
+    }
+
+    /// Trace the zero-velocity curve `2U(x, y, 0) = C` in the plane of the
+    /// primaries.
+    ///
+    /// Searches `samples` vertical lines evenly spaced over `x_range`, and
+    /// calls `on_point(x, y)` for every crossing found with `0 < y ≤ 2`,
+    /// together with its mirror image `(x, -y)`. Unordered points suitable
+    /// for plotting.
+    ///
+    /// `samples` also sets the resolution along each line: crossings are
+    /// bracketed on a grid of `2 / samples` in `y`, so two crossings closer
+    /// than that on one line, such as the narrow neck near a Lagrange point
+    /// just after it opens, are missed. Raise `samples` to resolve them.
+    pub fn zero_velocity_curve<P: FnMut(Real, Real)>(
+        &self,
+        jacobi: Real,
+        x_range: (Real, Real),
+        samples: usize,
+        mut on_point: P,
+    ) -> Result<(), &'static str> {
+        let (x_min, x_max) = x_range;
+        if !x_min.is_finite() || !x_max.is_finite() || x_min >= x_max {
+            return Err("Range of x must be finite and increasing");
+        }
+        if samples < 2 {
+            return Err("At least two samples are needed");
+        }
+        let excess =
+            |x: Real, y: Real| 2.0 * self.pseudo_potential(&Vector3::new(x, y, 0.0)) - jacobi;
+        let dy = CURVE_Y_LIMIT / samples as Real;
+        for i in 0..samples {
+            let x = x_min + (x_max - x_min) * i as Real / (samples - 1) as Real;
+            // Start just off the axis, where the primaries are singular
+            let mut y0 = 1e-9;
+            let mut g0 = excess(x, y0);
+            for j in 1..=samples {
+                let y1 = j as Real * dy;
+                let g1 = excess(x, y1);
+                if (g0 < 0.0) != (g1 < 0.0) {
+                    let (mut a, mut b, mut ga) = (y0, y1, g0);
+                    for _ in 0..CURVE_ITERATIONS {
+                        let mid = 0.5 * (a + b);
+                        let gm = excess(x, mid);
+                        if (gm < 0.0) == (ga < 0.0) {
+                            a = mid;
+                            ga = gm;
+                        } else {
+                            b = mid;
+                        }
+                    }
+                    let y = 0.5 * (a + b);
+                    on_point(x, y);
+                    on_point(x, -y);
+                }
+                y0 = y1;
+                g0 = g1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotating-frame equations of motion for the nondimensional state
+    /// `[x, y, z, ẋ, ẏ, ż]`
+    pub fn equations_of_motion(&self) -> impl Fn(Real, &[Real; 6]) -> [Real; 6] + '_ {
+        move |_t, y| {
+            let grad = self.pseudo_potential_gradient(&Vector3::new(y[0], y[1], y[2]));
+            [
+                y[3],
+                y[4],
+                y[5],
+                grad.x + 2.0 * y[4],
+                grad.y - 2.0 * y[3],
+                grad.z,
+            ]
+        }
+    }
+
+    /// Propagate a nondimensional rotating-frame state for `dt` time units
+    /// (negative to go backward), starting with steps of `initial_step`.
+    pub fn propagate<I: Integrator>(
+        &self,
+        integrator: &I,
+        state: &StateVector,
+        dt: Real,
+        initial_step: Real,
+    ) -> Result<StateVector, &'static str> {
+        let f = self.equations_of_motion();
+        let y = integrate(
+            integrator,
+            &f,
+            0.0,
+            (*state).into(),
+            dt,
+            initial_step,
+            |_| {},
+        )?;
+        Ok(y.into())
+    }
+
+    /// Convert a nondimensional state to meters and meters per second, in
+    /// the same rotating barycentric frame
+    pub fn to_dimensional(&self, state: &StateVector) -> StateVector {
+        StateVector {
+            position: state.position * self.length_unit.value(),
+            velocity: state.velocity * self.velocity_unit().value(),
+        }
+    }
+
+    /// Convert a rotating-frame state in meters and meters per second to
+    /// nondimensional units
+    pub fn to_nondimensional(&self, state: &StateVector) -> StateVector {
+        StateVector {
+            position: state.position / self.length_unit.value(),
+            velocity: state.velocity / self.velocity_unit().value(),
+        }
+    }
+
+    /// Rotate a nondimensional state from the rotating frame into the
+    /// inertial barycentric frame that coincides with it at `t = 0`
+    pub fn to_inertial(&self, state: &StateVector, t: Real) -> StateVector {
+        let (sin, cos) = sincos(t);
+        let rotate = |v: Vector3| Vector3::new(cos * v.x - sin * v.y, sin * v.x + cos * v.y, v.z);
+        let omega = Vector3::new(0.0, 0.0, 1.0);
+        StateVector {
+            position: rotate(state.position),
+            velocity: rotate(state.velocity + omega.cross(&state.position)),
+        }
+    }
+
+    /// Inverse of [`ThreeBodySystem::to_inertial`]
+    pub fn to_rotating(&self, state: &StateVector, t: Real) -> StateVector {
+        let (sin, cos) = sincos(t);
+        let rotate = |v: Vector3| Vector3::new(cos * v.x + sin * v.y, -sin * v.x + cos * v.y, v.z);
+        let omega = Vector3::new(0.0, 0.0, 1.0);
+        let position = rotate(state.position);
+        StateVector {
+            position,
+            velocity: rotate(state.velocity) - omega.cross(&position),
+        }
+    }
+
+    /// Newton's method on the x-axis force balance, starting from `x`
+    fn collinear_point(&self, mut x: Real) -> Result<Real, &'static str> {
+        let mu = self.mass_ratio;
+        for _ in 0..LAGRANGE_MAX_ITERATIONS {
+            let (d1, d2) = (x + mu, x - 1.0 + mu);
+            let (r1, r2) = (fabs(d1), fabs(d2));
+            let (r1_3, r2_3) = (r1 * r1 * r1, r2 * r2 * r2);
+            let f = x - (1.0 - mu) * d1 / r1_3 - mu * d2 / r2_3;
+            let df = 1.0 + 2.0 * (1.0 - mu) / r1_3 + 2.0 * mu / r2_3;
+            let step = f / df;
+            x -= step;
+            if fabs(step) <= 4.0 * Real::EPSILON {
+                return Ok(x);
+            }
+        }
+        Err("Lagrange point did not converge")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS, MOON, SUN};
+    use crate::integrators::{RungeKuttaFehlberg78, Tolerances};
+    use crate::test_helpers::earth_moon;
+    use crate::utils::TAU;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_units() {
+        let system = earth_moon();
+        assert_relative_eq!(system.mass_ratio(), 0.012_150_6, epsilon = 1e-7);
+        // The sidereal month, 27.3 days
+        let month = TAU * system.time_unit().value() / 86_400.0;
+        assert_relative_eq!(month, 27.3, epsilon = 0.1);
+        assert_relative_eq!(system.velocity_unit().value(), 1_024.5, epsilon = 1.0);
+    }
+
+    #[test]
+    fn test_lagrange_points() {
+        // Szebehely's values for the Earth-Moon system
+        let system = earth_moon();
+        let x = |point| system.lagrange_point(point).unwrap().x;
+        assert_relative_eq!(x(LagrangePoint::L1), 0.836_915, epsilon = 1e-5);
+        assert_relative_eq!(x(LagrangePoint::L2), 1.155_682, epsilon = 1e-5);
+        assert_relative_eq!(x(LagrangePoint::L3), -1.005_063, epsilon = 1e-5);
+
+        // Every point is an equilibrium
+        for point in LagrangePoint::ALL {
+            let gradient = system.pseudo_potential_gradient(&system.lagrange_point(point).unwrap());
+            assert!(gradient.norm() < 1e-12, "{point:?}");
+        }
+    }
+
+    #[test]
+    fn test_critical_jacobi_constants() {
+        let system = earth_moon();
+        let c = |point| system.jacobi_constant_at(point).unwrap();
+        assert_relative_eq!(c(LagrangePoint::L1), 3.188_34, epsilon = 1e-4);
+        assert_relative_eq!(c(LagrangePoint::L2), 3.172_16, epsilon = 1e-4);
+        assert_relative_eq!(c(LagrangePoint::L3), 3.012_15, epsilon = 1e-4);
+        assert_relative_eq!(c(LagrangePoint::L4), c(LagrangePoint::L5), epsilon = 1e-12);
+        assert_relative_eq!(
+            c(LagrangePoint::L4),
+            3.0 - system.mass_ratio() * (1.0 - system.mass_ratio()),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_jacobi_constant_is_conserved() {
+        let system = earth_moon();
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-13, 1e-13));
+        // An inclined lunar orbit about 7,700 km up, perturbed by the Earth
+        let state = StateVector {
+            position: Vector3::new(1.0 - system.mass_ratio() + 0.025, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.65, 0.15),
+        };
+        let later = system.propagate(&integrator, &state, 3.0, 1e-3).unwrap();
+        assert_relative_eq!(
+            system.jacobi_constant(&later),
+            system.jacobi_constant(&state),
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_l4_is_stationary() {
+        let system = earth_moon();
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-13, 1e-13));
+        let state = StateVector {
+            position: system.lagrange_point(LagrangePoint::L4).unwrap(),
+            velocity: Vector3::ZERO,
+        };
+        let later = system.propagate(&integrator, &state, TAU, 0.1).unwrap();
+        assert!((later.position - state.position).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_frames() {
+        let system = earth_moon();
+        // The Moon at rest in the rotating frame moves at unit speed inertially
+        let moon = StateVector {
+            position: system.secondary_position(),
+            velocity: Vector3::ZERO,
+        };
+        let inertial = system.to_inertial(&moon, TAU / 4.0);
+        assert_relative_eq!(
+            inertial.position.y,
+            1.0 - system.mass_ratio(),
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            inertial.velocity.norm(),
+            1.0 - system.mass_ratio(),
+            epsilon = 1e-15
+        );
+
+        let back = system.to_rotating(&inertial, TAU / 4.0);
+        assert!((back.position - moon.position).norm() < 1e-15);
+        assert!(back.velocity.norm() < 1e-15);
+
+        let dimensional = system.to_dimensional(&moon);
+        assert_relative_eq!(
+            dimensional.position.x,
+            (1.0 - system.mass_ratio()) * 384_400_000.0
+        );
+        let round_trip = system.to_nondimensional(&dimensional);
+        assert_relative_eq!(round_trip.position.x, moon.position.x, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_zero_velocity_curve() {
+        // Below the L1 energy the Moon's region is closed off from the Earth's
+        let system = earth_moon();
+        let c = system.jacobi_constant_at(LagrangePoint::L1).unwrap() + 0.01;
+        let l1 = system.lagrange_point(LagrangePoint::L1).unwrap();
+        assert!(!system.is_accessible(&l1, c));
+        assert!(system.is_accessible(&l1, c - 0.02));
+
+        let mut count = 0;
+        system
+            .zero_velocity_curve(c, (-1.5, 1.5), 50, |x, y| {
+                let excess = 2.0 * system.pseudo_potential(&Vector3::new(x, y, 0.0)) - c;
+                assert!(excess.abs() < 1e-9);
+                count += 1;
+            })
+            .unwrap();
+        assert!(count > 50);
+    }
+
+    #[test]
+    fn test_invalid_systems() {
+        let distance = Meters::from(EARTH_MOON_DISTANCE);
+        assert!(ThreeBodySystem::new(&MOON, &EARTH, distance).is_err());
+        assert!(ThreeBodySystem::new(&EARTH, &MOON, Meters(0.0)).is_err());
+        assert!(ThreeBodySystem::new(&SUN, &MARS, Meters(2.279e11)).is_ok());
+        assert!(
+            earth_moon()
+                .zero_velocity_curve(3.0, (1.0, 0.0), 10, |_, _| {})
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,654 @@
+//! # Periodic Orbits
+//!
+//! Families of periodic orbits of the circular restricted three-body
+//! problem, found by differential correction of symmetric orbits. An orbit
+//! symmetric about the x–z plane crosses it perpendicularly twice per
+//! period, so it is enough to start on the plane with `ẋ = ż = 0`,
+//! propagate to the next crossing, and adjust the initial state until the
+//! velocity there is perpendicular too. The state transition matrix,
+//! integrated alongside the trajectory, supplies the Newton corrections.
+//!
+//! - [`lyapunov`] - planar orbits about `L1`, `L2` or `L3`
+//! - [`halo`] - three-dimensional orbits about `L1` or `L2`, from
+//!   Richardson's third-order approximation
+//! - [`distant_retrograde`] - planar retrograde orbits about the smaller
+//!   primary (DROs)
+//!
+//! Each family is reached by natural-parameter continuation from a small
+//! orbit with a reliable first guess, so large orbits converge too.
+//!
+//! ## Stability
+//!
+//! The monodromy matrix, the state transition matrix over one period, has
+//! eigenvalues in reciprocal pairs `λ, 1/λ`, one of them the trivial pair
+//! at 1. The other two pairs give the stability indices
+//! `ν = ½(λ + 1/λ)`: an orbit is linearly stable when both satisfy
+//! `|ν| ≤ 1`, and larger values mean faster divergence from it. Lyapunov and
+//! most halo orbits are strongly unstable; DROs are stable. Where the two
+//! pairs collide and leave the real axis as a complex quadruplet, as for
+//! large L1 halos, the orbit is unstable and has no real indices.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::{EARTH, MOON};
+//! use almagest::cr3bp::periodic::{HaloFamily, halo};
+//! use almagest::cr3bp::{EARTH_MOON_DISTANCE, LagrangePoint, ThreeBodySystem};
+//!
+//! let system = ThreeBodySystem::new(&EARTH, &MOON, EARTH_MOON_DISTANCE.into()).unwrap();
+//! // A halo orbit about L1 rising about 8,000 km out of the Moon's orbit plane
+//! let orbit = halo(&system, LagrangePoint::L1, 0.02, HaloFamily::Northern)
+//!     .expect("Converges");
+//! let days = orbit.period * system.time_unit().value() / 86_400.0;
+//! println!("Period {days:.1} days, stability {:?}", orbit.stability_indices);
+//! ```
+
+use core::ops::ControlFlow;
+
+use libm::{ceil, fabs, sqrt};
+
+use super::{LagrangePoint, ThreeBodySystem};
+use crate::events::locate;
+use crate::integrators::{RungeKuttaFehlberg78, Tolerances, integrate, integrate_until};
+use crate::kepler::StateVector;
+use crate::utils::{PI, Real, Vector3};
+
+/// A 6×6 matrix, such as a state transition matrix, stored by rows
+pub type Matrix6 = [[Real; 6]; 6];
+
+/// Trajectory and state transition matrix, `[x, y, z, ẋ, ẏ, ż, Φ₀₀, Φ₀₁, …]`
+type Extended = [Real; 42];
+
+/// Integration tolerances for the trajectory and its variations
+const TOLERANCES: Tolerances = Tolerances {
+    absolute: 1e-13,
+    relative: 1e-13,
+};
+
+/// Largest velocity across the x–z plane accepted at the half-period
+/// crossing
+const CORRECTION_TOLERANCE: Real = 1e-9;
+
+/// Newton iterations allowed for a single orbit
+const CORRECTION_MAX_ITERATIONS: usize = 25;
+
+/// Longest half period searched for the return to the x–z plane
+const MAX_HALF_PERIOD: Real = 2.0 * PI;
+
+/// Largest change of the family parameter between continuation steps
+const CONTINUATION_STEP: Real = 0.005;
+
+/// Largest change of `[x₀, z₀, ẏ₀]` from the extrapolated guess accepted
+/// as the same family
+const MAX_CONTINUATION_JUMP: Real = 0.1;
+
+/// Smallest orbit in each family, where the first guess is accurate
+const SMALLEST_AMPLITUDE: Real = 1e-3;
+
+/// Smallest halo height, where Richardson's approximation is accurate
+const SMALLEST_HALO: Real = 0.01;
+
+/// Smallest DRO, where the secondary's gravity dominates
+const SMALLEST_DRO: Real = 0.02;
+
+/// Which of the two mirror-image halo families to follow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HaloFamily {
+    /// Starting above the plane of the primaries
+    Northern,
+    /// Starting below the plane of the primaries
+    Southern,
+}
+
+/// A periodic orbit of the rotating frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeriodicOrbit {
+    /// Nondimensional state at the perpendicular crossing of the x–z plane
+    /// where the orbit starts
+    pub initial_state: StateVector,
+    /// Nondimensional period
+    pub period: Real,
+    pub jacobi_constant: Real,
+    /// The two nontrivial stability indices, largest magnitude first;
+    /// `None` when the eigenvalues form a complex quadruplet
+    pub stability_indices: Option<[Real; 2]>,
+}
+
+impl PeriodicOrbit {
+    /// Whether small deviations stay bounded, `|ν| ≤ 1` for both indices.
+    /// A complex quadruplet lies off the unit circle, so is unstable.
+    pub fn is_stable(&self) -> bool {
+        self.stability_indices
+            .is_some_and(|indices| indices.iter().all(|nu| fabs(*nu) <= 1.0 + 1e-6))
+    }
+}
+
+/// Planar Lyapunov orbit about a collinear Lagrange point.
+///
+/// `amplitude` is the nondimensional distance from the Lagrange point to
+/// the orbit's crossing of the x-axis on the side of smaller x.
+pub fn lyapunov(
+    system: &ThreeBodySystem,
+    point: LagrangePoint,
+    amplitude: Real,
+) -> Result<PeriodicOrbit, &'static str> {
+    if matches!(point, LagrangePoint::L4 | LagrangePoint::L5) {
+        return Err("Lyapunov orbits are only generated about L1, L2 and L3");
+    }
+    if amplitude <= 0.0 || !amplitude.is_finite() {
+        return Err("Amplitude must be positive");
+    }
+    let x_l = system.lagrange_point(point)?.x;
+    // Linearized in-plane oscillation: x = -A cos λt, y = kA sin λt
+    let c2 = collinear_coefficient(system, x_l);
+    let lambda = sqrt(0.5 * (2.0 - c2 + sqrt(9.0 * c2 * c2 - 8.0 * c2)));
+    let k = (lambda * lambda + 1.0 + 2.0 * c2) / (2.0 * lambda);
+
+    let start = amplitude.min(SMALLEST_AMPLITUDE);
+    let first = |a: Real| Ok([x_l - a, 0.0, k * lambda * a]);
+    continuation(start, amplitude, first, |a, guess| {
+        correct(system, [x_l - a, 0.0, guess[2]], Hold::X)
+    })
+}
+
+/// Halo orbit about `L1` or `L2`.
+///
+/// `z_amplitude` is the nondimensional height above or below the plane of
+/// the primaries at the orbit's crossing of the x–z plane on the side of
+/// smaller x. The first guess comes from Richardson's third-order
+/// analytic solution. Near-side heights stop growing where the `L2` family
+/// turns toward the near-rectilinear orbits, about 0.075 for Earth–Moon;
+/// larger requests fail rather than return an orbit of another family.
+pub fn halo(
+    system: &ThreeBodySystem,
+    point: LagrangePoint,
+    z_amplitude: Real,
+    family: HaloFamily,
+) -> Result<PeriodicOrbit, &'static str> {
+    if !matches!(point, LagrangePoint::L1 | LagrangePoint::L2) {
+        return Err("Halo orbits are only generated about L1 and L2");
+    }
+    if z_amplitude <= 0.0 || !z_amplitude.is_finite() {
+        return Err("Amplitude must be positive");
+    }
+    let sign = match family {
+        HaloFamily::Northern => 1.0,
+        HaloFamily::Southern => -1.0,
+    };
+    let start = z_amplitude.min(SMALLEST_HALO);
+    let first = |z| richardson(system, point, z);
+    continuation(start, z_amplitude, first, |z, guess| {
+        correct(system, [guess[0], z, guess[2]], Hold::Z)
+    })
+    .map(|orbit| mirror(orbit, sign))
+}
+
+/// Distant retrograde orbit about the smaller primary.
+///
+/// `distance` is the nondimensional distance from the smaller primary to
+/// the orbit's crossing of the x-axis between the primaries.
+pub fn distant_retrograde(
+    system: &ThreeBodySystem,
+    distance: Real,
+) -> Result<PeriodicOrbit, &'static str> {
+    let x_l1 = system.lagrange_point(LagrangePoint::L1)?.x;
+    let x_secondary = system.secondary_position().x;
+    if distance <= 0.0 || x_secondary - distance <= system.primary_position().x {
+        return Err("Distance must be positive and short of the larger primary");
+    }
+    let mu = system.mass_ratio();
+    // Retrograde two-body circular speed, plus the frame rotation
+    let start = distance.min(SMALLEST_DRO).min(x_secondary - x_l1);
+    let first = |d: Real| Ok([x_secondary - d, 0.0, sqrt(mu / d) + d]);
+    continuation(start, distance, first, |d, guess| {
+        correct(system, [x_secondary - d, 0.0, guess[2]], Hold::X)
+    })
+}
+
+/// State transition matrix over one `period` from `state`, whose
+/// eigenvalues decide the orbit's stability
+pub fn monodromy(
+    system: &ThreeBodySystem,
+    state: &StateVector,
+    period: Real,
+) -> Result<Matrix6, &'static str> {
+    let f = variational_equations(system);
+    let y = integrate(
+        &RungeKuttaFehlberg78::new(TOLERANCES),
+        &f,
+        0.0,
+        extend(state),
+        period,
+        1e-3,
+        |_| {},
+    )?;
+    Ok(transition_matrix(&y))
+}
+
+/// Stability indices `ν = ½(λ + 1/λ)` of the two nontrivial reciprocal
+/// eigenvalue pairs of a monodromy matrix, largest magnitude first.
+///
+/// With the trivial pair at 1, the sums `sᵢ = λᵢ + 1/λᵢ` follow from the
+/// traces of `M` and `M²` alone. `None` when the pairs form a complex
+/// quadruplet, which has no real indices.
+pub fn stability_indices(monodromy: &Matrix6) -> Option<[Real; 2]> {
+    let trace: Real = (0..6).map(|i| monodromy[i][i]).sum();
+    let trace_squared: Real = (0..6)
+        .flat_map(|i| (0..6).map(move |j| monodromy[i][j] * monodromy[j][i]))
+        .sum();
+    // s₁ + s₂ = tr M - 2 and s₁² + s₂² = tr M² + 2
+    let sum = trace - 2.0;
+    let sum_of_squares = trace_squared + 2.0;
+    let discriminant = 2.0 * sum_of_squares - sum * sum;
+    if discriminant < -1e-9 * sum_of_squares.max(1.0) {
+        return None;
+    }
+    let root = sqrt(discriminant.max(0.0));
+    let (a, b) = (0.25 * (sum + root), 0.25 * (sum - root));
+    Some(if fabs(a) >= fabs(b) { [a, b] } else { [b, a] })
+}
+
+/// Which initial coordinate stays fixed while the others are corrected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Hold {
+    /// Correct ẏ₀, with x₀ unchanged, to zero ẋ at the half period
+    X,
+    /// Correct x₀ and ẏ₀ (with z₀ unchanged) to zero ẋ and ż
+    Z,
+}
+
+/// Follow a family from parameter `start` to `target`. The first two
+/// orbits start from the analytic `first_guess`; later guesses
+/// `[x₀, z₀, ẏ₀]` extrapolate from the two previous orbits.
+fn continuation<G, C>(
+    start: Real,
+    target: Real,
+    first_guess: G,
+    mut correct: C,
+) -> Result<PeriodicOrbit, &'static str>
+where
+    G: Fn(Real) -> Result<[Real; 3], &'static str>,
+    C: FnMut(Real, [Real; 3]) -> Result<(PeriodicOrbit, [Real; 3]), &'static str>,
+{
+    let steps = ceil(fabs(target - start) / CONTINUATION_STEP) as usize;
+    let (mut orbit, mut current) = correct(start, first_guess(start)?)?;
+    let mut previous = current;
+    for i in 1..=steps {
+        let parameter = start + (target - start) * i as Real / steps as Real;
+        let guess = if i == 1 {
+            first_guess(parameter)?
+        } else {
+            core::array::from_fn(|j| 2.0 * current[j] - previous[j])
+        };
+        let (next, solution) = correct(parameter, guess)?;
+        // Past a fold in the family the corrector lands on an unrelated
+        // orbit far from the extrapolated one
+        if solution
+            .iter()
+            .zip(guess)
+            .any(|(s, g)| fabs(s - g) > MAX_CONTINUATION_JUMP)
+        {
+            return Err(
+                "Continuation left the orbit family; the amplitude may be past its turning point",
+            );
+        }
+        previous = current;
+        current = solution;
+        orbit = next;
+    }
+    Ok(orbit)
+}
+
+/// Newton iteration on a symmetric orbit starting at `(x₀, 0, z₀)` with
+/// velocity `(0, ẏ₀, 0)`
+fn correct(
+    system: &ThreeBodySystem,
+    mut initial: [Real; 3],
+    hold: Hold,
+) -> Result<(PeriodicOrbit, [Real; 3]), &'static str> {
+    let f = variational_equations(system);
+    for _ in 0..CORRECTION_MAX_ITERATIONS {
+        let state = symmetric_state(initial);
+        let (half_period, y) = half_period(system, &state)?;
+        let (vx, vy, vz) = (y[3], y[4], y[5]);
+        if fabs(vx) <= CORRECTION_TOLERANCE && fabs(vz) <= CORRECTION_TOLERANCE {
+            let period = 2.0 * half_period;
+            let monodromy = monodromy(system, &state, period)?;
+            let orbit = PeriodicOrbit {
+                initial_state: state,
+                period,
+                jacobi_constant: system.jacobi_constant(&state),
+                stability_indices: stability_indices(&monodromy),
+            };
+            return Ok((orbit, initial));
+        }
+
+        // Variations at the crossing, with the crossing time free: a change
+        // δ in the initial state moves the crossing by -Φ₁ⱼδ/ẏ
+        let phi = transition_matrix(&y);
+        let acceleration = f(half_period, &y);
+        let column = |row: usize, j: usize| phi[row][j] - acceleration[row] / vy * phi[1][j];
+        match hold {
+            Hold::X => {
+                initial[2] -= vx / column(3, 4);
+            }
+            Hold::Z => {
+                let (a, b, c, d) = (column(3, 0), column(3, 4), column(5, 0), column(5, 4));
+                let determinant = a * d - b * c;
+                if determinant == 0.0 || !determinant.is_finite() {
+                    return Err("Differential correction is singular");
+                }
+                initial[0] -= (d * vx - b * vz) / determinant;
+                initial[2] -= (a * vz - c * vx) / determinant;
+            }
+        }
+        if initial.iter().any(|v| !v.is_finite()) {
+            return Err("Differential correction diverged");
+        }
+    }
+    Err("Differential correction did not converge")
+}
+
+/// Propagate until the trajectory returns to the x–z plane, returning the
+/// crossing time and the extended state there
+fn half_period(
+    system: &ThreeBodySystem,
+    state: &StateVector,
+) -> Result<(Real, Extended), &'static str> {
+    let f = variational_equations(system);
+    let integrator = RungeKuttaFehlberg78::new(TOLERANCES);
+    let mut bracket = None;
+    integrate_until(
+        &integrator,
+        &f,
+        0.0,
+        extend(state),
+        MAX_HALF_PERIOD,
+        1e-3,
+        |step| {
+            if step.y0[1] * step.y1[1] < 0.0 || (step.y1[1] == 0.0 && step.t1 > 0.0) {
+                bracket = Some(*step);
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )?;
+    let step = bracket.ok_or("Orbit does not return to the x-z plane")?;
+    let at = |t: Real| integrate(&integrator, &f, step.t0, step.y0, t, step.size(), |_| {});
+    let t = locate(|t| Ok(at(t)?[1]), step.t0, step.y0[1], step.t1, step.y1[1])?;
+    Ok((t, at(t)?))
+}
+
+/// Equations of motion together with `Φ̇ = AΦ`
+fn variational_equations(system: &ThreeBodySystem) -> impl Fn(Real, &Extended) -> Extended + '_ {
+    let motion = system.equations_of_motion();
+    move |t, y| {
+        let state = core::array::from_fn(|i| y[i]);
+        let derivative = motion(t, &state);
+        let hessian = system.pseudo_potential_hessian(&Vector3::new(y[0], y[1], y[2]));
+        let mut out = [0.0; 42];
+        out[..6].copy_from_slice(&derivative);
+        for j in 0..6 {
+            let phi = |row: usize| y[6 + 6 * row + j];
+            for row in 0..3 {
+                // Position rows: Φ̇ = velocity rows of Φ
+                out[6 + 6 * row + j] = phi(row + 3);
+                // Velocity rows: U'' Φ_position + 2Ω Φ_velocity
+                let gradient: Real = (0..3).map(|k| hessian[row][k] * phi(k)).sum();
+                let coriolis = match row {
+                    0 => 2.0 * phi(4),
+                    1 => -2.0 * phi(3),
+                    _ => 0.0,
+                };
+                out[6 + 6 * (row + 3) + j] = gradient + coriolis;
+            }
+        }
+        out
+    }
+}
+
+fn symmetric_state([x, z, vy]: [Real; 3]) -> StateVector {
+    StateVector {
+        position: Vector3::new(x, 0.0, z),
+        velocity: Vector3::new(0.0, vy, 0.0),
+    }
+}
+
+/// State followed by the identity matrix
+fn extend(state: &StateVector) -> Extended {
+    let mut y = [0.0; 42];
+    y[..6].copy_from_slice(&<[Real; 6]>::from(*state));
+    for i in 0..6 {
+        y[6 + 7 * i] = 1.0;
+    }
+    y
+}
+
+fn transition_matrix(y: &Extended) -> Matrix6 {
+    core::array::from_fn(|i| core::array::from_fn(|j| y[6 + 6 * i + j]))
+}
+
+/// Reflect a northern orbit through the plane of the primaries if needed
+fn mirror(orbit: PeriodicOrbit, sign: Real) -> PeriodicOrbit {
+    let mut orbit = orbit;
+    orbit.initial_state.position.z *= sign;
+    orbit
+}
+
+/// `c₂ = (1 - μ)/r₁³ + μ/r₂³` at a point on the x-axis
+fn collinear_coefficient(system: &ThreeBodySystem, x: Real) -> Real {
+    let mu = system.mass_ratio();
+    let (r1, r2) = (fabs(x + mu), fabs(x - 1.0 + mu));
+    (1.0 - mu) / (r1 * r1 * r1) + mu / (r2 * r2 * r2)
+}
+
+/// Richardson's third-order halo orbit at the crossing of the x–z plane,
+/// as `[x₀, z₀, ẏ₀]` for a northern orbit rising `z_amplitude`
+fn richardson(
+    system: &ThreeBodySystem,
+    point: LagrangePoint,
+    z_amplitude: Real,
+) -> Result<[Real; 3], &'static str> {
+    let mu = system.mass_ratio();
+    let x_l = system.lagrange_point(point)?.x;
+    let l1 = point == LagrangePoint::L1;
+    let gamma = if l1 { 1.0 - mu - x_l } else { x_l - 1.0 + mu };
+    let c = |n: i32| {
+        let sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+        let ratio = gamma / if l1 { 1.0 - gamma } else { 1.0 + gamma };
+        let far = (1.0 - mu) * libm::pow(ratio, (n + 1) as Real);
+        if l1 {
+            (mu + sign * far) / (gamma * gamma * gamma)
+        } else {
+            sign * (mu + far) / (gamma * gamma * gamma)
+        }
+    };
+    let (c2, c3, c4) = (c(2), c(3), c(4));
+
+    let lambda = sqrt(0.5 * (2.0 - c2 + sqrt(9.0 * c2 * c2 - 8.0 * c2)));
+    let l2 = lambda * lambda;
+    let k = 2.0 * lambda / (l2 + 1.0 - c2);
+    let d1 = 3.0 * l2 / k * (k * (6.0 * l2 - 1.0) - 2.0 * lambda);
+    let d2 = 8.0 * l2 / k * (k * (11.0 * l2 - 1.0) - 2.0 * lambda);
+
+    let a21 = 3.0 * c3 * (k * k - 2.0) / (4.0 * (1.0 + 2.0 * c2));
+    let a22 = 3.0 * c3 / (4.0 * (1.0 + 2.0 * c2));
+    let a23 = -3.0 * c3 * lambda / (4.0 * k * d1)
+        * (3.0 * k * k * k * lambda - 6.0 * k * (k - lambda) + 4.0);
+    let a24 = -3.0 * c3 * lambda / (4.0 * k * d1) * (2.0 + 3.0 * k * lambda);
+    let b21 = -3.0 * c3 * lambda / (2.0 * d1) * (3.0 * k * lambda - 4.0);
+    let b22 = 3.0 * c3 * lambda / d1;
+    let d21 = -c3 / (2.0 * l2);
+
+    let a31 = -9.0 * lambda / (4.0 * d2) * (4.0 * c3 * (k * a23 - b21) + k * c4 * (4.0 + k * k))
+        + (9.0 * l2 + 1.0 - c2) / (2.0 * d2)
+            * (3.0 * c3 * (2.0 * a23 - k * b21) + c4 * (2.0 + 3.0 * k * k));
+    let a32 = -1.0 / d2
+        * (9.0 * lambda / 4.0 * (4.0 * c3 * (k * a24 - b22) + k * c4)
+            + 1.5 * (9.0 * l2 + 1.0 - c2) * (c3 * (k * b22 + d21 - 2.0 * a24) - c4));
+    let b31 = 3.0 / (8.0 * d2)
+        * (8.0 * lambda * (3.0 * c3 * (k * b21 - 2.0 * a23) - c4 * (2.0 + 3.0 * k * k))
+            + (9.0 * l2 + 1.0 + 2.0 * c2) * (4.0 * c3 * (k * a23 - b21) + k * c4 * (4.0 + k * k)));
+    let b32 = 1.0 / d2
+        * (9.0 * lambda * (c3 * (k * b22 + d21 - 2.0 * a24) - c4)
+            + 3.0 / 8.0 * (9.0 * l2 + 1.0 + 2.0 * c2) * (4.0 * c3 * (k * a24 - b22) + k * c4));
+
+    let denominator = 2.0 * lambda * (lambda * (1.0 + k * k) - 2.0 * k);
+    let s1 = (1.5 * c3 * (2.0 * a21 * (k * k - 2.0) - a23 * (k * k + 2.0) - 2.0 * k * b21)
+        - 3.0 / 8.0 * c4 * (3.0 * k * k * k * k - 8.0 * k * k + 8.0))
+        / denominator;
+    let s2 =
+        (1.5 * c3 * (2.0 * a22 * (k * k - 2.0) + a24 * (k * k + 2.0) + 2.0 * k * b22 + 5.0 * d21)
+            + 3.0 / 8.0 * c4 * (12.0 - k * k))
+            / denominator;
+    let l1_coefficient =
+        -1.5 * c3 * (2.0 * a21 + a23 + 5.0 * d21) - 3.0 / 8.0 * c4 * (12.0 - k * k) + 2.0 * l2 * s1;
+    let l2_coefficient = 1.5 * c3 * (a24 - 2.0 * a22) + 9.0 / 8.0 * c4 + 2.0 * l2 * s2;
+
+    // Amplitudes in units of γ; Ax is fixed by Az through the frequency
+    // constraint that makes the in-plane and out-of-plane motions
+    // commensurate. Az stands in for the height to first order, which the
+    // corrector then holds exactly
+    let az = z_amplitude / gamma;
+    let delta = l2 - c2;
+    let ax_squared = (-delta - l2_coefficient * az * az) / l1_coefficient;
+    if ax_squared <= 0.0 || !ax_squared.is_finite() {
+        return Err("No halo orbit approximation at this amplitude");
+    }
+    let ax = sqrt(ax_squared);
+    let omega = 1.0 + s1 * ax * ax + s2 * az * az;
+
+    let x = a21 * ax * ax + a22 * az * az - ax
+        + (a23 * ax * ax - a24 * az * az)
+        + (a31 * ax * ax * ax - a32 * ax * az * az);
+    let vy = lambda
+        * omega
+        * (k * ax
+            + 2.0 * (b21 * ax * ax - b22 * az * az)
+            + 3.0 * (b31 * ax * ax * ax - b32 * ax * az * az));
+    Ok([x_l + gamma * x, z_amplitude, gamma * vy])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::earth_moon;
+    use approx::assert_relative_eq;
+
+    /// The orbit returns to its initial state after one period
+    fn assert_closes(system: &ThreeBodySystem, orbit: &PeriodicOrbit) {
+        let integrator = RungeKuttaFehlberg78::new(TOLERANCES);
+        let end = system
+            .propagate(&integrator, &orbit.initial_state, orbit.period, 1e-3)
+            .unwrap();
+        assert!((end.position - orbit.initial_state.position).norm() < 1e-8);
+        assert!((end.velocity - orbit.initial_state.velocity).norm() < 1e-8);
+    }
+
+    #[test]
+    fn test_small_lyapunov_matches_linear_period() {
+        let system = earth_moon();
+        let orbit = lyapunov(&system, LagrangePoint::L1, 1e-3).unwrap();
+        let c2 =
+            collinear_coefficient(&system, system.lagrange_point(LagrangePoint::L1).unwrap().x);
+        let lambda = sqrt(0.5 * (2.0 - c2 + sqrt(9.0 * c2 * c2 - 8.0 * c2)));
+        assert_relative_eq!(orbit.period, 2.0 * PI / lambda, max_relative = 1e-3);
+        assert_closes(&system, &orbit);
+    }
+
+    #[test]
+    fn test_lyapunov_family() {
+        let system = earth_moon();
+        let orbit = lyapunov(&system, LagrangePoint::L2, 0.03).unwrap();
+        assert_closes(&system, &orbit);
+        // Collinear orbits are strongly unstable
+        assert!(!orbit.is_stable());
+        assert!(orbit.stability_indices.unwrap()[0] > 100.0);
+        // Energy lies just above the L2 value
+        assert!(orbit.jacobi_constant < system.jacobi_constant_at(LagrangePoint::L2).unwrap());
+    }
+
+    #[test]
+    fn test_halo() {
+        let system = earth_moon();
+        let north = halo(&system, LagrangePoint::L1, 0.02, HaloFamily::Northern).unwrap();
+        assert_closes(&system, &north);
+        assert_relative_eq!(north.initial_state.position.z, 0.02);
+        // About 12 days, and unstable
+        let days = north.period * system.time_unit().value() / 86_400.0;
+        assert!(days > 10.0 && days < 14.0, "{days} days");
+        assert!(!north.is_stable());
+
+        let south = halo(&system, LagrangePoint::L1, 0.02, HaloFamily::Southern).unwrap();
+        assert_relative_eq!(south.initial_state.position.z, -0.02);
+        assert_relative_eq!(south.period, north.period);
+        assert_closes(&system, &south);
+    }
+
+    #[test]
+    fn test_l2_halo() {
+        let system = earth_moon();
+        let orbit = halo(&system, LagrangePoint::L2, 0.03, HaloFamily::Northern).unwrap();
+        assert_closes(&system, &orbit);
+        // Past the family's turning point
+        assert!(halo(&system, LagrangePoint::L2, 0.08, HaloFamily::Northern).is_err());
+    }
+
+    #[test]
+    fn test_distant_retrograde_orbit() {
+        let system = earth_moon();
+        // About 70,000 km from the Moon
+        let orbit = distant_retrograde(&system, 0.18).unwrap();
+        assert_closes(&system, &orbit);
+        assert!(orbit.is_stable(), "{:?}", orbit.stability_indices);
+        assert!(orbit.initial_state.velocity.y > 0.0);
+    }
+
+    #[test]
+    fn test_halo_with_complex_instability() {
+        let system = earth_moon();
+        // Large L1 halos lie past the collision of their two indices
+        let orbit = halo(&system, LagrangePoint::L1, 0.3, HaloFamily::Northern).unwrap();
+        assert_closes(&system, &orbit);
+        assert_eq!(orbit.stability_indices, None);
+        assert!(!orbit.is_stable());
+    }
+
+    #[test]
+    fn test_stability_indices_of_known_matrix() {
+        // Block-diagonal monodromy with eigenvalues 1, 1, 4, 1/4, e^{±iθ}
+        let (sin, cos) = libm::sincos(0.3);
+        let mut m = [[0.0; 6]; 6];
+        m[0][0] = 1.0;
+        m[0][1] = 0.5;
+        m[1][1] = 1.0;
+        m[2][2] = 4.0;
+        m[3][3] = 0.25;
+        m[4][4] = cos;
+        m[4][5] = -sin;
+        m[5][4] = sin;
+        m[5][5] = cos;
+        let [large, small] = stability_indices(&m).unwrap();
+        assert_relative_eq!(large, 0.5 * (4.0 + 0.25), epsilon = 1e-12);
+        assert_relative_eq!(small, cos, epsilon = 1e-12);
+
+        // Eigenvalues 2e^{±iθ} and ½e^{±iθ}: a complex quadruplet
+        for (i, scale) in [(2, 2.0), (4, 0.5)] {
+            m[i][i] = scale * cos;
+            m[i][i + 1] = -scale * sin;
+            m[i + 1][i] = scale * sin;
+            m[i + 1][i + 1] = scale * cos;
+        }
+        assert_eq!(stability_indices(&m), None);
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let system = earth_moon();
+        assert!(lyapunov(&system, LagrangePoint::L4, 0.01).is_err());
+        assert!(lyapunov(&system, LagrangePoint::L1, -0.01).is_err());
+        assert!(halo(&system, LagrangePoint::L3, 0.01, HaloFamily::Northern).is_err());
+        assert!(distant_retrograde(&system, 2.0).is_err());
+    }
+}
@@ -0,0 +1,395 @@
+//! # Orbital Decay
+//!
+//! Orbit-averaged decay of near-circular orbits under atmospheric drag.
+//! Averaged over a revolution, drag on a circular orbit removes energy
+//! without making it eccentric, and the altitude falls at
+//!
+//! `ḣ = -ρ √(μ a) / B`
+//!
+//! where `a` is the orbit radius and `B = m / (C_D A)` the ballistic
+//! coefficient. Integrating the time per unit altitude from the starting
+//! altitude down to a reentry altitude gives the orbital lifetime, without
+//! having to follow every revolution. The same drag sets the Δv needed to
+//! hold a facility at a fixed altitude.
+//!
+//! The rotation of the atmosphere, which lowers the relative speed by a few
+//! percent, is neglected; so is any eccentricity. Lifetimes are as uncertain
+//! as the density, which varies by an order of magnitude over the solar
+//! cycle; bracket estimates with the low and high
+//! [`SolarActivity`](crate::atmosphere::SolarActivity) profiles.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::atmosphere::{Atmosphere, AtmosphereKind, SolarActivity};
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::decay::{JULIAN_YEAR, ballistic_coefficient, lifetime};
+//! use almagest::utils::{Kilograms, Kilometers, MetersSquared};
+//!
+//! let atmosphere = Atmosphere::for_body(&EARTH, AtmosphereKind::Tabulated(SolarActivity::Moderate))
+//!     .expect("Earth has an atmosphere");
+//! let b = ballistic_coefficient(Kilograms(500.0), 2.2, MetersSquared(2.0)).unwrap();
+//!
+//! let years = lifetime(&EARTH, &atmosphere, b, Kilometers(400.0), Kilometers(120.0))
+//!     .expect("Decays from 400 km")
+//!     .value()
+//!     / JULIAN_YEAR.value();
+//! println!("Reenters after {years:.1} years");
+//! ```
+
+use libm::sqrt;
+
+use crate::atmosphere::AtmosphereModel;
+use crate::celestials::CelestialBody;
+use crate::integrators::{DormandPrince54, Tolerances, integrate};
+use crate::utils::{
+    Kilograms, KilogramsPerMetersSquared, Kilometers, Meters, MetersPerSecond, MetersSquared, Real,
+    Seconds,
+};
+
+/// 365.25 days, the year over which reboost budgets are quoted
+pub const JULIAN_YEAR: Seconds = Seconds(31_557_600.0);
+
+/// Lifetime integration tolerances, in seconds and as a fraction of the
+/// elapsed time
+const LIFETIME_TOLERANCES: Tolerances = Tolerances {
+    absolute: 1e-3,
+    relative: 1e-10,
+};
+
+/// One sample of an altitude history.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecayPoint {
+    /// Time since the start of the decay
+    pub time: Seconds,
+    /// Altitude above the body's equatorial radius
+    pub altitude: Kilometers,
+}
+
+/// Ballistic coefficient `B = m / (C_D A)`. Larger values decay more
+/// slowly.
+pub fn ballistic_coefficient(
+    mass: Kilograms,
+    drag_coefficient: Real,
+    area: MetersSquared,
+) -> Result<KilogramsPerMetersSquared, &'static str> {
+    if mass.0 <= 0.0 || !mass.0.is_finite() {
+        return Err("Mass must be positive");
+    }
+    if drag_coefficient <= 0.0 || !drag_coefficient.is_finite() {
+        return Err("Drag coefficient must be positive");
+    }
+    if area.value() <= 0.0 || !area.value().is_finite() {
+        return Err("Area must be positive");
+    }
+    Ok(KilogramsPerMetersSquared(
+        mass.0 / (drag_coefficient * area.value()),
+    ))
+}
+
+/// Orbit-averaged rate of change of altitude of a circular orbit at
+/// `altitude`; negative, or zero where the model has no air.
+pub fn decay_rate<A: AtmosphereModel>(
+    body: &CelestialBody,
+    atmosphere: &A,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+) -> Result<MetersPerSecond, &'static str> {
+    validate(body, ballistic, altitude)?;
+    Ok(MetersPerSecond(rate(body, atmosphere, ballistic, altitude)))
+}
+
+/// Δv needed over `duration` to hold a circular orbit at `altitude`
+/// against drag, `Δv = ρ v² t / 2B`.
+///
+/// Use [`JULIAN_YEAR`] for an annual reboost budget.
+pub fn reboost_delta_v<A: AtmosphereModel>(
+    body: &CelestialBody,
+    atmosphere: &A,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+    duration: Seconds,
+) -> Result<MetersPerSecond, &'static str> {
+    validate(body, ballistic, altitude)?;
+    if duration.value() < 0.0 || !duration.value().is_finite() {
+        return Err("Duration must be non-negative");
+    }
+    let rho = atmosphere.density(altitude.into()).0;
+    let v_squared = body.mu.value() / radius(body, altitude);
+    Ok(MetersPerSecond(
+        0.5 * rho * v_squared / ballistic.value() * duration.value(),
+    ))
+}
+
+/// Time for a circular orbit to decay from `altitude` to
+/// `reentry_altitude`, where it is considered lost; around 100 to 120 km
+/// for Earth.
+pub fn lifetime<A: AtmosphereModel>(
+    body: &CelestialBody,
+    atmosphere: &A,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+    reentry_altitude: Kilometers,
+) -> Result<Seconds, &'static str> {
+    let range = Kilometers(altitude.value() - reentry_altitude.value());
+    decay_history(
+        body,
+        atmosphere,
+        ballistic,
+        altitude,
+        reentry_altitude,
+        range,
+        |_| {},
+    )
+}
+
+/// Altitude-versus-time history of a decaying circular orbit.
+///
+/// Calls `on_point` at the starting altitude, at every `spacing` below it,
+/// and at `reentry_altitude`, in order of increasing time. Returns the
+/// lifetime. Fails if there is no air at the starting altitude, since the
+/// orbit would never decay.
+pub fn decay_history<A, P>(
+    body: &CelestialBody,
+    atmosphere: &A,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+    reentry_altitude: Kilometers,
+    spacing: Kilometers,
+    mut on_point: P,
+) -> Result<Seconds, &'static str>
+where
+    A: AtmosphereModel,
+    P: FnMut(DecayPoint),
+{
+    validate(body, ballistic, altitude)?;
+    if !reentry_altitude.value().is_finite() || reentry_altitude.value() >= altitude.value() {
+        return Err("Reentry altitude must be below the starting altitude");
+    }
+    if radius(body, reentry_altitude) <= 0.0 {
+        return Err("Reentry altitude must be above the center of the body");
+    }
+    if spacing.value() <= 0.0 || !spacing.value().is_finite() {
+        return Err("Spacing must be positive");
+    }
+    if rate(body, atmosphere, ballistic, altitude) >= 0.0 {
+        return Err("No atmospheric density at the starting altitude");
+    }
+
+    // Altitude is the independent variable and elapsed time the state,
+    // which keeps the step count small however slowly the orbit decays
+    let seconds_per_meter =
+        |h: Real, _: &[Real; 1]| [1.0 / rate(body, atmosphere, ballistic, Kilometers(h / 1000.0))];
+    let start = Meters::from(altitude).value();
+    let end = Meters::from(reentry_altitude).value();
+    let spacing = Meters::from(spacing).value();
+
+    on_point(DecayPoint {
+        time: Seconds(0.0),
+        altitude,
+    });
+    // Integrate to each sample altitude in turn, rather than interpolating
+    // within steps, so every sample is as accurate as the final lifetime
+    let integrator = DormandPrince54::new(LIFETIME_TOLERANCES);
+    let mut from = start;
+    let mut time = 0.0;
+    let mut step = spacing.min(1_000.0);
+    loop {
+        let to = (from - spacing).max(end);
+        [time] = integrate(
+            &integrator,
+            &seconds_per_meter,
+            from,
+            [time],
+            to,
+            step,
+            |accepted| step = accepted.next_step,
+        )?;
+        if to <= end {
+            break;
+        }
+        on_point(DecayPoint {
+            time: Seconds(time),
+            altitude: Kilometers(to / 1000.0),
+        });
+        from = to;
+    }
+    if !time.is_finite() {
+        return Err("Decay time is not finite");
+    }
+    on_point(DecayPoint {
+        time: Seconds(time),
+        altitude: reentry_altitude,
+    });
+    Ok(Seconds(time))
+}
+
+fn validate(
+    body: &CelestialBody,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+) -> Result<(), &'static str> {
+    if body.mu.value() <= 0.0 || !body.mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    if ballistic.value() <= 0.0 || !ballistic.value().is_finite() {
+        return Err("Ballistic coefficient must be positive");
+    }
+    if !altitude.value().is_finite() || radius(body, altitude) <= 0.0 {
+        return Err("Altitude must be above the center of the body");
+    }
+    Ok(())
+}
+
+/// Orbit radius in meters at `altitude` above the equatorial radius
+fn radius(body: &CelestialBody, altitude: Kilometers) -> Real {
+    Meters::from(Kilometers(
+        body.equatorial_radius.value() + altitude.value(),
+    ))
+    .value()
+}
+
+/// `ḣ = -ρ √(μ a) / B`, in m/s
+fn rate<A: AtmosphereModel>(
+    body: &CelestialBody,
+    atmosphere: &A,
+    ballistic: KilogramsPerMetersSquared,
+    altitude: Kilometers,
+) -> Real {
+    let rho = atmosphere.density(altitude.into()).0;
+    -rho * sqrt(body.mu.value() * radius(body, altitude)) / ballistic.value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atmosphere::{AtmosphereKind, SolarActivity};
+    use crate::celestials::celestial_bodies::{EARTH, MOON};
+    use crate::test_helpers::earth_atmosphere;
+    use crate::utils::KilogramsPerMetersCubed;
+    use approx::assert_relative_eq;
+
+    const B: KilogramsPerMetersSquared = KilogramsPerMetersSquared(100.0);
+
+    #[test]
+    fn test_ballistic_coefficient() {
+        let b = ballistic_coefficient(Kilograms(1_100.0), 2.2, MetersSquared(5.0)).unwrap();
+        assert_relative_eq!(b.value(), 100.0);
+        assert!(ballistic_coefficient(Kilograms(0.0), 2.2, MetersSquared(5.0)).is_err());
+        assert!(ballistic_coefficient(Kilograms(1.0), 2.2, MetersSquared(-5.0)).is_err());
+    }
+
+    #[test]
+    fn test_lifetime_constant_density() {
+        // With uniform density the decay integrates in closed form:
+        // t = 2B (√r₀ - √r₁) / (ρ √μ)
+        let rho = 1e-11;
+        let uniform = |_h: Meters| KilogramsPerMetersCubed(rho);
+        let t = lifetime(&EARTH, &uniform, B, Kilometers(500.0), Kilometers(300.0)).unwrap();
+        let r0 = radius(&EARTH, Kilometers(500.0));
+        let r1 = radius(&EARTH, Kilometers(300.0));
+        let expected = 2.0 * B.value() * (sqrt(r0) - sqrt(r1)) / (rho * sqrt(EARTH.mu.value()));
+        assert_relative_eq!(t.value(), expected, max_relative = 1e-7);
+    }
+
+    #[test]
+    fn test_lifetime_follows_solar_activity() {
+        let years = |activity| {
+            lifetime(
+                &EARTH,
+                &earth_atmosphere(AtmosphereKind::Tabulated(activity)),
+                B,
+                Kilometers(400.0),
+                Kilometers(120.0),
+            )
+            .unwrap()
+            .value()
+                / JULIAN_YEAR.value()
+        };
+        let (low, moderate, high) = (
+            years(SolarActivity::Low),
+            years(SolarActivity::Moderate),
+            years(SolarActivity::High),
+        );
+        assert!(low > moderate && moderate > high);
+        // A 100 kg/m² satellite at 400 km lasts on the order of a year
+        assert!(moderate > 0.3 && moderate < 3.0, "{moderate} years");
+    }
+
+    #[test]
+    fn test_history() {
+        let atmosphere = earth_atmosphere(AtmosphereKind::Tabulated(SolarActivity::Moderate));
+        let mut points = [DecayPoint {
+            time: Seconds(-1.0),
+            altitude: Kilometers(0.0),
+        }; 16];
+        let mut count = 0;
+        let t = decay_history(
+            &EARTH,
+            &atmosphere,
+            B,
+            Kilometers(400.0),
+            Kilometers(120.0),
+            Kilometers(20.0),
+            |point| {
+                points[count] = point;
+                count += 1;
+            },
+        )
+        .unwrap();
+
+        // 400, 380, ..., 140, then 120 at reentry
+        assert_eq!(count, 15);
+        assert_eq!(points[0].time, Seconds(0.0));
+        assert_relative_eq!(points[1].altitude.value(), 380.0);
+        assert_eq!(points[14].time, t);
+        assert_eq!(points[14].altitude, Kilometers(120.0));
+        assert!(
+            points[..count]
+                .windows(2)
+                .all(|w| w[1].time.value() > w[0].time.value())
+        );
+
+        // Sampled points agree with direct lifetimes from their altitude,
+        // which match quadrature of the tabulated profile
+        let rest = lifetime(&EARTH, &atmosphere, B, Kilometers(300.0), Kilometers(120.0)).unwrap();
+        assert_relative_eq!(
+            t.value() - points[5].time.value(),
+            rest.value(),
+            max_relative = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_reboost_balances_decay() {
+        // Δv per unit time equals the drag deceleration, ȧ v / 2a
+        let atmosphere = earth_atmosphere(AtmosphereKind::Tabulated(SolarActivity::Moderate));
+        let altitude = Kilometers(400.0);
+        let dv = reboost_delta_v(&EARTH, &atmosphere, B, altitude, Seconds(1.0)).unwrap();
+        let hdot = decay_rate(&EARTH, &atmosphere, B, altitude).unwrap();
+        let a = radius(&EARTH, altitude);
+        let v = sqrt(EARTH.mu.value() / a);
+        assert_relative_eq!(
+            dv.value(),
+            -hdot.value() * v / (2.0 * a),
+            max_relative = 1e-12
+        );
+
+        // A year at 400 km costs several m/s
+        let annual = reboost_delta_v(&EARTH, &atmosphere, B, altitude, JULIAN_YEAR).unwrap();
+        assert!(annual.value() > 5.0 && annual.value() < 50.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let atmosphere = earth_atmosphere(AtmosphereKind::Tabulated(SolarActivity::Moderate));
+        let vacuum = |_h: Meters| KilogramsPerMetersCubed(0.0);
+        let h = Kilometers(400.0);
+        assert!(lifetime(&EARTH, &vacuum, B, h, Kilometers(120.0)).is_err());
+        assert!(lifetime(&EARTH, &atmosphere, B, h, Kilometers(500.0)).is_err());
+        assert!(lifetime(&MOON, &atmosphere, B, h, Kilometers(-2_000.0)).is_err());
+        let zero = KilogramsPerMetersSquared(0.0);
+        assert!(decay_rate(&EARTH, &atmosphere, zero, h).is_err());
+        assert!(reboost_delta_v(&EARTH, &atmosphere, B, h, Seconds(-1.0)).is_err());
+    }
+}
@@ -0,0 +1,889 @@
+//! # Event Detection
+//!
+//! Many questions about a trajectory are about *when* something happens:
+//! periapsis passage, crossing an altitude or the equator, entering the
+//! Earth's shadow, or the moment a rotating tether's tip comes to rest
+//! relative to the ground so a payload can be caught. Each is the time at
+//! which a scalar event function `g(t, state)` changes sign.
+//!
+//! Propagation is watched step by step. When `g` changes sign across a step
+//! in the requested [`Crossing`] direction, the root is located with the
+//! Illinois variant of regula falsi. Trial states come from the step's
+//! dense output under Cowell propagation, so no extra force evaluations are
+//! spent, and from the propagator itself for analytic orbits. Sign changes
+//! are only seen at step ends, so keep steps shorter than the interval
+//! between successive roots of any one function.
+//!
+//! - [`propagate_with_events`] - Cowell propagation under a [`ForceModel`]
+//! - [`find_events`] - any analytic propagator, sampled at a fixed spacing
+//!
+//! Predefined event functions:
+//!
+//! - [`Periapsis`] and [`Apoapsis`] - sign changes of `r · v`
+//! - [`Altitude`] - crossing an altitude above the body's equatorial radius
+//! - [`Node`] - crossing the equatorial plane
+//! - [`Eclipse`] - entering or leaving the body's cylindrical shadow
+//! - [`TetherTip`] - a rotating tether's tip slowing to a catch speed
+//!   relative to the rotating surface
+//!
+//! Any closure can be used through [`Condition`].
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::events::{Action, Altitude, Crossing, Periapsis, find_events};
+//! use almagest::kepler::{KeplerianElements, propagation::KeplerPropagator};
+//! use almagest::utils::{Eccentricity, Meters, Radians, Seconds};
+//!
+//! let elements = KeplerianElements::new(
+//!     Meters(8_000_000.0),
+//!     Eccentricity::new(0.15).unwrap(),
+//!     Radians(0.5),
+//!     Radians::ZERO,
+//!     Radians::ZERO,
+//!     Radians::ZERO,
+//!     EARTH.mu,
+//! )
+//! .unwrap();
+//! let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+//! let below_1000_km = Altitude::new(&EARTH, Meters(1_000_000.0), Crossing::Decreasing);
+//!
+//! let mut first_descent = None;
+//! find_events(
+//!     |t| orbit.state_at(t),
+//!     Seconds(0.0),
+//!     Seconds(20_000.0),
+//!     Seconds(60.0),
+//!     &[&Periapsis, &below_1000_km],
+//!     |event| {
+//!         if event.index == 1 {
+//!             first_descent = Some(event.time);
+//!             return Action::Stop;
+//!         }
+//!         Action::Continue
+//!     },
+//! )
+//! .expect("Propagates");
+//! assert!(first_descent.is_some());
+//! ```
+
+use core::ops::ControlFlow;
+
+use libm::fabs;
+
+use crate::celestials::CelestialBody;
+use crate::forces::{ForceModel, equations_of_motion};
+use crate::integrators::{Integrator, integrate_until};
+use crate::kepler::StateVector;
+use crate::utils::{Meters, MetersPerSecond, Radians, RadiansPerSecond, Real, Seconds, Vector3};
+
+/// Most event functions that can be watched at once
+pub const MAX_EVENTS: usize = 16;
+
+/// Absolute tolerance on event times, in seconds
+const TIME_TOLERANCE: Real = 1e-6;
+
+/// Iterations allowed when locating a single event
+const ROOT_MAX_ITERATIONS: usize = 100;
+
+/// Which sign changes of an event function count as events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crossing {
+    /// From negative to positive
+    Increasing,
+    /// From positive to negative
+    Decreasing,
+    Either,
+}
+
+impl Crossing {
+    fn accepts(self, increasing: bool) -> bool {
+        match self {
+            Crossing::Increasing => increasing,
+            Crossing::Decreasing => !increasing,
+            Crossing::Either => true,
+        }
+    }
+}
+
+/// What to do after an event has been reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    /// End propagation at the event
+    Stop,
+}
+
+/// A scalar function of time and state whose sign changes mark events.
+pub trait EventFunction {
+    fn value(&self, t: Seconds, state: &StateVector) -> Real;
+
+    /// Sign changes to report; both by default
+    fn crossing(&self) -> Crossing {
+        Crossing::Either
+    }
+}
+
+/// An event function built from a closure.
+#[derive(Clone, Copy, Debug)]
+pub struct Condition<G> {
+    pub crossing: Crossing,
+    g: G,
+}
+
+impl<G: Fn(Seconds, &StateVector) -> Real> Condition<G> {
+    pub fn new(crossing: Crossing, g: G) -> Self {
+        Self { crossing, g }
+    }
+}
+
+impl<G: Fn(Seconds, &StateVector) -> Real> EventFunction for Condition<G> {
+    fn value(&self, t: Seconds, state: &StateVector) -> Real {
+        (self.g)(t, state)
+    }
+
+    fn crossing(&self) -> Crossing {
+        self.crossing
+    }
+}
+
+/// An event located during propagation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Event {
+    /// Position of the event function in the slice being watched
+    pub index: usize,
+    pub time: Seconds,
+    /// State at the event
+    pub state: StateVector,
+    /// Whether the event function was increasing through zero
+    pub increasing: bool,
+}
+
+/// Closest approach to the central body, where `r · v` turns positive.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Periapsis;
+
+impl EventFunction for Periapsis {
+    fn value(&self, _t: Seconds, state: &StateVector) -> Real {
+        state.position.dot(&state.velocity)
+    }
+
+    fn crossing(&self) -> Crossing {
+        Crossing::Increasing
+    }
+}
+
+/// Farthest point from the central body, where `r · v` turns negative.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Apoapsis;
+
+impl EventFunction for Apoapsis {
+    fn value(&self, _t: Seconds, state: &StateVector) -> Real {
+        state.position.dot(&state.velocity)
+    }
+
+    fn crossing(&self) -> Crossing {
+        Crossing::Decreasing
+    }
+}
+
+/// Passing through an altitude above the body's equatorial radius, the
+/// reference for drag and [`Eclipse`]; decreasing while descending.
+#[derive(Clone, Copy, Debug)]
+pub struct Altitude<'a> {
+    pub body: &'a CelestialBody<'a>,
+    pub altitude: Meters,
+    pub crossing: Crossing,
+}
+
+impl<'a> Altitude<'a> {
+    pub fn new(body: &'a CelestialBody<'a>, altitude: Meters, crossing: Crossing) -> Self {
+        Self {
+            body,
+            altitude,
+            crossing,
+        }
+    }
+}
+
+impl EventFunction for Altitude<'_> {
+    fn value(&self, _t: Seconds, state: &StateVector) -> Real {
+        state.radius().value()
+            - Meters::from(self.body.equatorial_radius).value()
+            - self.altitude.value()
+    }
+
+    fn crossing(&self) -> Crossing {
+        self.crossing
+    }
+}
+
+/// Crossing the equatorial (xy) plane; increasing at the ascending node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Node {
+    pub crossing: Crossing,
+}
+
+impl Node {
+    pub const ASCENDING: Node = Node {
+        crossing: Crossing::Increasing,
+    };
+    pub const DESCENDING: Node = Node {
+        crossing: Crossing::Decreasing,
+    };
+}
+
+impl EventFunction for Node {
+    fn value(&self, _t: Seconds, state: &StateVector) -> Real {
+        state.position.z
+    }
+
+    fn crossing(&self) -> Crossing {
+        self.crossing
+    }
+}
+
+/// Entering or leaving the cylindrical shadow of the central body, the
+/// same shadow [`SolarRadiationPressure`](crate::forces::SolarRadiationPressure)
+/// uses. Decreasing on entry, increasing on exit.
+#[derive(Clone, Copy, Debug)]
+pub struct Eclipse<'a, E> {
+    pub body: &'a CelestialBody<'a>,
+    /// Position of the Sun relative to the central body at time `t`
+    pub sun: E,
+    pub crossing: Crossing,
+}
+
+impl<'a, E: Fn(Seconds) -> Vector3> Eclipse<'a, E> {
+    pub fn new(body: &'a CelestialBody<'a>, sun: E, crossing: Crossing) -> Self {
+        Self {
+            body,
+            sun,
+            crossing,
+        }
+    }
+}
+
+impl<E: Fn(Seconds) -> Vector3> EventFunction for Eclipse<'_, E> {
+    /// Distance outside the shadow cylinder on the night side, or above the
+    /// surface on the day side; continuous across the terminator
+    fn value(&self, t: Seconds, state: &StateVector) -> Real {
+        let radius = Meters::from(self.body.equatorial_radius).value();
+        let sun_direction = (self.sun)(t).unit();
+        let along = state.position.dot(&sun_direction);
+        if along >= 0.0 {
+            return state.radius().value() - radius;
+        }
+        (state.position - sun_direction * along).norm() - radius
+    }
+
+    fn crossing(&self) -> Crossing {
+        self.crossing
+    }
+}
+
+/// The tip of a tether spinning in the orbit plane about the propagated
+/// center of mass, slowing below `speed` relative to the rotating surface
+/// beneath it (decreasing) or speeding back up (increasing).
+///
+/// The arm's angle from the local nadir is `phase + spin_rate·t`, measured
+/// in the direction of orbital motion about the orbit normal, so a positive
+/// spin sweeps the lower tip backward. A tip whose relative speed just
+/// touches zero never crosses it; a small positive `speed` brackets the
+/// catch window instead.
+#[derive(Clone, Copy, Debug)]
+pub struct TetherTip<'a> {
+    pub body: &'a CelestialBody<'a>,
+    /// Distance from the center of mass to the tip
+    pub arm_length: Meters,
+    /// Spin rate relative to the local vertical
+    pub spin_rate: RadiansPerSecond,
+    /// Angle of the arm from nadir at `t = 0`
+    pub phase: Radians,
+    /// Relative speed below which the tip can catch a payload
+    pub speed: MetersPerSecond,
+}
+
+impl<'a> TetherTip<'a> {
+    pub fn new(
+        body: &'a CelestialBody<'a>,
+        arm_length: Meters,
+        spin_rate: RadiansPerSecond,
+        phase: Radians,
+        speed: MetersPerSecond,
+    ) -> Self {
+        Self {
+            body,
+            arm_length,
+            spin_rate,
+            phase,
+            speed,
+        }
+    }
+
+    /// Inertial state of the tip when the center of mass is at `state`
+    pub fn tip_state(&self, t: Seconds, state: &StateVector) -> StateVector {
+        let r = state.position;
+        let h = r.cross(&state.velocity);
+        let radial = r.unit();
+        let normal = h.unit();
+        let along = normal.cross(&radial);
+        // The local vertical turns at h/r², the arm at that plus its spin
+        let orbit_rate = h.norm() / r.dot(&r);
+        let (sin, cos) = libm::sincos(self.phase.value() + self.spin_rate.value() * t.value());
+        let arm = (radial * -cos - along * sin) * self.arm_length.value();
+        StateVector {
+            position: r + arm,
+            velocity: state.velocity + normal.cross(&arm) * (orbit_rate + self.spin_rate.value()),
+        }
+    }
+
+    /// Tip velocity relative to the surface rotating beneath it
+    pub fn surface_relative_velocity(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        let tip = self.tip_state(t, state);
+        let omega = Vector3::new(0.0, 0.0, self.body.rotation_rate.value());
+        tip.velocity - omega.cross(&tip.position)
+    }
+}
+
+impl EventFunction for TetherTip<'_> {
+    fn value(&self, t: Seconds, state: &StateVector) -> Real {
+        self.surface_relative_velocity(t, state).norm() - self.speed.value()
+    }
+}
+
+/// Propagate `state` under `model` for `dt` by Cowell's method, reporting
+/// each event of `events` to `on_event` in time order.
+///
+/// Events are located on each step's dense output
+/// ([`Step::interpolate`](crate::integrators::Step::interpolate)), so their
+/// times are as accurate as the integrator's interpolant: tight with
+/// [`DormandPrince54`](crate::integrators::DormandPrince54), which has a
+/// continuous extension, and only as good as a cubic Hermite fit over the
+/// step for the other methods.
+///
+/// Event times count from the start of propagation, like
+/// [`forces::propagate`](crate::forces::propagate). Returns the time and
+/// state at which propagation ended: `dt`, or the event that stopped it.
+pub fn propagate_with_events<M, I, H>(
+    model: &M,
+    integrator: &I,
+    state: &StateVector,
+    dt: Seconds,
+    initial_step: Seconds,
+    events: &[&dyn EventFunction],
+    mut on_event: H,
+) -> Result<(Seconds, StateVector), &'static str>
+where
+    M: ForceModel + ?Sized,
+    I: Integrator,
+    H: FnMut(&Event) -> Action,
+{
+    if events.len() > MAX_EVENTS {
+        return Err("Too many event functions");
+    }
+    let f = equations_of_motion(model);
+    let mut outcome = Ok(None);
+    let (t, y) = integrate_until(
+        integrator,
+        &f,
+        0.0,
+        (*state).into(),
+        dt.value(),
+        initial_step.value(),
+        |step| {
+            let state_at = |t: Real| Ok(StateVector::from(step.interpolate(t)));
+            let start = (step.t0, step.y0.into());
+            let end = (step.t1, step.y1.into());
+            match scan(events, start, end, state_at, &mut on_event) {
+                Ok(None) => ControlFlow::Continue(()),
+                stopped => {
+                    outcome = stopped;
+                    ControlFlow::Break(())
+                }
+            }
+        },
+    )?;
+    Ok(outcome?.unwrap_or((Seconds(t), y.into())))
+}
+
+/// Search an analytic propagator for events between `start` and `end`,
+/// sampling it every `step` to bracket sign changes.
+///
+/// `ephemeris` gives the state at an absolute time, such as
+/// [`KeplerPropagator::state_at`](crate::kepler::propagation::KeplerPropagator::state_at).
+/// Returns the time and state at which the search ended: `end`, or the
+/// event that stopped it.
+pub fn find_events<P, H>(
+    ephemeris: P,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+    events: &[&dyn EventFunction],
+    mut on_event: H,
+) -> Result<(Seconds, StateVector), &'static str>
+where
+    P: Fn(Seconds) -> Result<StateVector, &'static str>,
+    H: FnMut(&Event) -> Action,
+{
+    if events.len() > MAX_EVENTS {
+        return Err("Too many event functions");
+    }
+    if !start.value().is_finite() || !end.value().is_finite() {
+        return Err("Search bounds must be finite");
+    }
+    if step.value() <= 0.0 || !step.value().is_finite() {
+        return Err("Step must be positive");
+    }
+    let state_at = |t: Real| ephemeris(Seconds(t));
+    let (t_end, direction) = (end.value(), if end >= start { 1.0 } else { -1.0 });
+    let mut previous = (start.value(), state_at(start.value())?);
+    while previous.0 != t_end {
+        let t = previous.0 + direction * step.value();
+        let t = if (t - t_end) * direction >= 0.0 {
+            t_end
+        } else {
+            t
+        };
+        let next = (t, state_at(t)?);
+        if let Some((t, state)) = scan(events, previous, next, state_at, &mut on_event)? {
+            return Ok((t, state));
+        }
+        previous = next;
+    }
+    Ok((end, previous.1))
+}
+
+/// Report the events between two states in time order, returning the stop
+/// point if `on_event` asks for one
+fn scan<S, H>(
+    events: &[&dyn EventFunction],
+    (t0, s0): (Real, StateVector),
+    (t1, s1): (Real, StateVector),
+    state_at: S,
+    on_event: &mut H,
+) -> Result<Option<(Seconds, StateVector)>, &'static str>
+where
+    S: Fn(Real) -> Result<StateVector, &'static str>,
+    H: FnMut(&Event) -> Action,
+{
+    let mut found = [(0.0, 0, false); MAX_EVENTS];
+    let mut count = 0;
+    for (index, event) in events.iter().enumerate() {
+        let g0 = event.value(Seconds(t0), &s0);
+        let g1 = event.value(Seconds(t1), &s1);
+        let increasing = g0 < 0.0 && g1 >= 0.0;
+        let decreasing = g0 > 0.0 && g1 <= 0.0;
+        if !(increasing || decreasing) || !event.crossing().accepts(increasing) {
+            continue;
+        }
+        let g = |t: Real| Ok(event.value(Seconds(t), &state_at(t)?));
+        found[count] = (locate(g, t0, g0, t1, g1)?, index, increasing);
+        count += 1;
+    }
+
+    let found = &mut found[..count];
+    let backward = t1 < t0;
+    found.sort_unstable_by(|a, b| {
+        let order = a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+        if backward { order.reverse() } else { order }
+    });
+    for &(t, index, increasing) in found.iter() {
+        let event = Event {
+            index,
+            time: Seconds(t),
+            state: state_at(t)?,
+            increasing,
+        };
+        if on_event(&event) == Action::Stop {
+            return Ok(Some((event.time, event.state)));
+        }
+    }
+    Ok(None)
+}
+
+/// Root of `g` between `a` and `b`, where it has opposite signs, by the
+/// Illinois method
+pub(crate) fn locate<G>(
+    g: G,
+    mut a: Real,
+    mut ga: Real,
+    mut b: Real,
+    mut gb: Real,
+) -> Result<Real, &'static str>
+where
+    G: Fn(Real) -> Result<Real, &'static str>,
+{
+    if gb == 0.0 {
+        return Ok(b);
+    }
+    let mut previous = a;
+    let mut retained = 0;
+    for _ in 0..ROOT_MAX_ITERATIONS {
+        let c = (a * gb - b * ga) / (gb - ga);
+        let gc = g(c)?;
+        let tolerance = TIME_TOLERANCE + 4.0 * Real::EPSILON * fabs(c);
+        if gc == 0.0 || fabs(c - previous) <= tolerance || fabs(b - a) <= tolerance {
+            return Ok(c);
+        }
+        // Halve the weight of an endpoint kept twice in a row, so it
+        // cannot stall convergence
+        if (gc > 0.0) == (gb > 0.0) {
+            b = c;
+            gb = gc;
+            if retained < 0 {
+                ga *= 0.5;
+            }
+            retained = -1;
+        } else {
+            a = c;
+            ga = gc;
+            if retained > 0 {
+                gb *= 0.5;
+            }
+            retained = 1;
+        }
+        previous = c;
+    }
+    Err("Event location did not converge")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::forces::PointMass;
+    use crate::integrators::{DormandPrince54, RungeKuttaFehlberg78, Tolerances};
+    use crate::kepler::KeplerianElements;
+    use crate::kepler::propagation::KeplerPropagator;
+    use crate::utils::{Eccentricity, PI, TAU};
+    use approx::assert_relative_eq;
+    use libm::{asin, sqrt};
+
+    fn eccentric() -> KeplerianElements {
+        KeplerianElements::new(
+            Meters(9_000_000.0),
+            Eccentricity::new(0.2).unwrap(),
+            Radians(0.7),
+            Radians(0.4),
+            Radians(1.1),
+            Radians(2.0),
+            EARTH.mu,
+        )
+        .unwrap()
+    }
+
+    /// Times since `t = 0` at which the mean anomaly of `elements` reaches
+    /// `m` (mod 2π)
+    fn mean_anomaly_time(elements: &KeplerianElements, m: Real) -> Real {
+        let n = elements.mean_motion().value();
+        (m - elements.mean_anomaly().unwrap().value()).rem_euclid(TAU) / n
+    }
+
+    #[test]
+    fn test_apsides_analytic() {
+        let elements = eccentric();
+        let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+        let period = elements.period().unwrap().value();
+        let (mut periapses, mut apoapses) = (0, 0);
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            Seconds(2.0 * period),
+            Seconds(120.0),
+            &[&Periapsis, &Apoapsis],
+            |event| {
+                let (m, count) = if event.index == 0 {
+                    (0.0, &mut periapses)
+                } else {
+                    (PI, &mut apoapses)
+                };
+                let expected = mean_anomaly_time(&elements, m) + *count as Real * period;
+                assert_relative_eq!(event.time.value(), expected, epsilon = 1e-5);
+                *count += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert_eq!((periapses, apoapses), (2, 2));
+    }
+
+    #[test]
+    fn test_cowell_matches_analytic() {
+        let elements = eccentric();
+        let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+        let integrator = DormandPrince54::new(Tolerances::new(1e-6, 1e-12));
+        let model = PointMass::new(&EARTH);
+        let descending = Altitude::new(&EARTH, Meters(2_000_000.0), Crossing::Decreasing);
+        let events: [&dyn EventFunction; 3] = [&Node::ASCENDING, &descending, &Periapsis];
+        let span = Seconds(30_000.0);
+
+        let mut analytic = [(0, 0.0); 16];
+        let mut expected = 0;
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            span,
+            Seconds(60.0),
+            &events,
+            |event| {
+                analytic[expected] = (event.index, event.time.value());
+                expected += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert!(expected >= 6);
+
+        let mut seen = 0;
+        let (end, _) = propagate_with_events(
+            &model,
+            &integrator,
+            &elements.to_state_vector(),
+            span,
+            Seconds(60.0),
+            &events,
+            |event| {
+                let (index, time) = analytic[seen];
+                assert_eq!(event.index, index);
+                assert_relative_eq!(event.time.value(), time, epsilon = 1e-4);
+                match event.index {
+                    0 => {
+                        assert!(event.state.position.z.abs() < 1e-3);
+                        assert!(event.state.velocity.z > 0.0);
+                    }
+                    1 => {
+                        let altitude = event.state.radius().value() - 6_378_137.0;
+                        assert_relative_eq!(altitude, 2_000_000.0, epsilon = 1e-3);
+                    }
+                    _ => {}
+                }
+                seen += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert_eq!(end, span);
+        assert_eq!(seen, expected);
+
+        // Stopping at the first periapsis ends propagation there
+        let (stop, state) = propagate_with_events(
+            &model,
+            &integrator,
+            &elements.to_state_vector(),
+            span,
+            Seconds(60.0),
+            &[&Periapsis],
+            |_| Action::Stop,
+        )
+        .unwrap();
+        assert_relative_eq!(
+            stop.value(),
+            mean_anomaly_time(&elements, 0.0),
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(
+            state.radius().value(),
+            elements.periapsis().value(),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_hermite_events_match_analytic() {
+        // RKF 7(8) takes long steps and has no continuous extension, so its
+        // events come from the cubic Hermite fit over each step
+        let elements = eccentric();
+        let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-6, 1e-12));
+        let rising = Altitude::new(&EARTH, Meters(3_000_000.0), Crossing::Increasing);
+        let events: [&dyn EventFunction; 2] = [&rising, &Apoapsis];
+        let span = Seconds(20_000.0);
+
+        let mut analytic = [0.0; 8];
+        let mut expected = 0;
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            span,
+            Seconds(60.0),
+            &events,
+            |event| {
+                analytic[expected] = event.time.value();
+                expected += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert!(expected >= 4);
+
+        let mut seen = 0;
+        propagate_with_events(
+            &PointMass::new(&EARTH),
+            &integrator,
+            &elements.to_state_vector(),
+            span,
+            Seconds(60.0),
+            &events,
+            |event| {
+                assert_relative_eq!(event.time.value(), analytic[seen], epsilon = 1e-2);
+                seen += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_eclipse_duration() {
+        // Equatorial circular orbit with the Sun fixed along +x: the shadow
+        // spans 2·asin(R/r) of the orbit, centered on -x
+        let r = 7_000_000.0;
+        let elements = KeplerianElements::new(
+            Meters(r),
+            Eccentricity::new(0.0).unwrap(),
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            EARTH.mu,
+        )
+        .unwrap();
+        let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+        let sun = |_t: Seconds| Vector3::new(1.5e11, 0.0, 0.0);
+        let entry = Eclipse::new(&EARTH, sun, Crossing::Decreasing);
+        let exit = Eclipse::new(&EARTH, sun, Crossing::Increasing);
+
+        let mut times = [0.0; 2];
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            elements.period().unwrap(),
+            Seconds(30.0),
+            &[&entry, &exit],
+            |event| {
+                times[event.index] = event.time.value();
+                Action::Continue
+            },
+        )
+        .unwrap();
+        let n = elements.mean_motion().value();
+        let radius = 6_378_137.0;
+        assert_relative_eq!(
+            times[1] - times[0],
+            2.0 * asin(radius / r) / n,
+            epsilon = 1e-4
+        );
+        assert_relative_eq!(0.5 * (times[0] + times[1]), PI / n, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_tether_catch_window() {
+        // Equatorial rotovator whose lower tip just matches the ground speed
+        // at the bottom of each swing
+        let r = 6_971_000.0;
+        let arm = 500_000.0;
+        let elements = KeplerianElements::new(
+            Meters(r),
+            Eccentricity::new(0.0).unwrap(),
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            EARTH.mu,
+        )
+        .unwrap();
+        let v = sqrt(EARTH.mu.value() / r);
+        let ground = EARTH.rotation_rate.value() * (r - arm);
+        let spin = (v - ground) / arm - v / r;
+        let tip = TetherTip::new(
+            &EARTH,
+            Meters(arm),
+            RadiansPerSecond(spin),
+            Radians(1.0),
+            MetersPerSecond(500.0),
+        );
+
+        // At the bottom of the swing the tip is at rest relative to the ground
+        let bottom = (TAU - 1.0) / spin;
+        let orbit = KeplerPropagator::new(elements, Seconds(0.0));
+        let state = orbit.state_at(Seconds(bottom)).unwrap();
+        let rest = tip
+            .surface_relative_velocity(Seconds(bottom), &state)
+            .norm();
+        assert!(rest < 1e-6, "{rest} m/s");
+
+        // The catch window brackets it; it lasts seconds, so sample finely
+        let mut window = [0.0; 2];
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            Seconds(TAU / spin),
+            Seconds(1.0),
+            &[&tip],
+            |event| {
+                window[usize::from(event.increasing)] = event.time.value();
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert!(window[0] < bottom && bottom < window[1]);
+        assert_relative_eq!(0.5 * (window[0] + window[1]), bottom, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_closure_condition() {
+        // Radius crossing 8000 km, either way
+        let orbit = KeplerPropagator::new(eccentric(), Seconds(0.0));
+        let condition = Condition::new(Crossing::Either, |_t: Seconds, s: &StateVector| {
+            s.radius().value() - 8_000_000.0
+        });
+        let mut count = 0;
+        find_events(
+            |t| orbit.state_at(t),
+            Seconds(0.0),
+            eccentric().period().unwrap(),
+            Seconds(60.0),
+            &[&condition],
+            |event| {
+                assert_relative_eq!(event.state.radius().value(), 8_000_000.0, epsilon = 1e-3);
+                count += 1;
+                Action::Continue
+            },
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let orbit = KeplerPropagator::new(eccentric(), Seconds(0.0));
+        let ephemeris = |t| orbit.state_at(t);
+        let continue_ = |_: &Event| Action::Continue;
+        assert!(
+            find_events(
+                ephemeris,
+                Seconds(0.0),
+                Seconds(1.0),
+                Seconds(0.0),
+                &[],
+                continue_
+            )
+            .is_err()
+        );
+        let many: [&dyn EventFunction; MAX_EVENTS + 1] = [&Periapsis; MAX_EVENTS + 1];
+        assert!(
+            find_events(
+                ephemeris,
+                Seconds(0.0),
+                Seconds(1.0),
+                Seconds(1.0),
+                &many,
+                continue_
+            )
+            .is_err()
+        );
+    }
+}
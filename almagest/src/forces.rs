@@ -0,0 +1,615 @@
+//! # Force Models
+//!
+//! Accelerations acting on a spacecraft, for Cowell propagation with the
+//! integrators in [`integrators`](crate::integrators). Each model implements
+//! [`ForceModel`], and models combine with [`ForceModel::plus`] (or by
+//! collecting them in an array or slice) into a single model whose
+//! acceleration is the sum of its parts.
+//!
+//! Available models:
+//!
+//! - [`PointMass`] - Central-body gravity, `-μr/r³`
+//! - [`ZonalHarmonics`] - Oblateness and pear-shape terms J2 and above
+//! - [`ThirdBody`] - Differential gravity of a distant body such as the Sun or Moon
+//! - [`Drag`] - Atmospheric drag relative to the co-rotating atmosphere
+//! - [`SolarRadiationPressure`] - Sunlight pressure, switched off in the central body's shadow
+//!
+//! Physical parameters — μ, reference radius, rotation rate and zonal
+//! coefficients — come from [`CelestialBody`]. Positions are relative to the
+//! central body in an inertial frame whose z-axis is the body's spin axis.
+//! Models that need to know where another body is take an ephemeris closure
+//! returning that body's position relative to the central body at a given
+//! time.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::forces::{propagate, ForceModel, PointMass, ZonalHarmonics};
+//! use almagest::integrators::{RungeKuttaFehlberg78, Tolerances};
+//! use almagest::kepler::StateVector;
+//! use almagest::utils::{Seconds, Vector3};
+//!
+//! let model = PointMass::new(&EARTH)
+//!     .plus(ZonalHarmonics::new(&EARTH, 6).expect("Earth has J2 through J6"));
+//! let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-6, 1e-12));
+//!
+//! let leo = StateVector::new(
+//!     Vector3::new(6_778_000.0, 0.0, 0.0),
+//!     Vector3::new(0.0, 5_422.6, 5_422.6),
+//! );
+//! let later = propagate(&model, &integrator, &leo, Seconds(5_400.0), Seconds(30.0))
+//!     .expect("Propagates");
+//! assert!(later.radius().value() > EARTH.radius.value() * 1000.0);
+//! ```
+
+use libm::pow;
+
+use crate::atmosphere::AtmosphereModel;
+use crate::celestials::CelestialBody;
+use crate::integrators::{Integrator, integrate};
+use crate::kepler::StateVector;
+use crate::utils::{Kilograms, Meters, MetersSquared, Real, Seconds, Vector3};
+
+/// Solar radiation pressure on a perfect absorber at 1 AU, in N/m²
+pub const SOLAR_PRESSURE_AT_1_AU: Real = 4.56e-6;
+
+/// One astronomical unit, in meters
+pub const ASTRONOMICAL_UNIT: Meters = Meters(1.495_978_707e11);
+
+/// A source of acceleration on a spacecraft.
+pub trait ForceModel {
+    /// Acceleration, in m/s², on a spacecraft in `state` at time `t` past the
+    /// propagation epoch
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3;
+
+    /// Combine with another model, summing their accelerations
+    fn plus<M: ForceModel>(self, other: M) -> Sum<Self, M>
+    where
+        Self: Sized,
+    {
+        Sum(self, other)
+    }
+}
+
+/// Two force models acting together. Built with [`ForceModel::plus`].
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<A, B>(pub A, pub B);
+
+impl<A: ForceModel, B: ForceModel> ForceModel for Sum<A, B> {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        self.0.acceleration(t, state) + self.1.acceleration(t, state)
+    }
+}
+
+impl<M: ForceModel + ?Sized> ForceModel for &M {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        (**self).acceleration(t, state)
+    }
+}
+
+/// Every model in the slice acting together, e.g. `[&dyn ForceModel]`
+impl<M: ForceModel> ForceModel for [M] {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        self.iter().fold(Vector3::ZERO, |sum, model| {
+            sum + model.acceleration(t, state)
+        })
+    }
+}
+
+impl<M: ForceModel, const K: usize> ForceModel for [M; K] {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        self.as_slice().acceleration(t, state)
+    }
+}
+
+/// Equations of motion `[ṙ, v̇] = [v, a(t, r, v)]` for use with
+/// [`integrate`], with time measured in seconds past the epoch.
+pub fn equations_of_motion<M: ForceModel + ?Sized>(
+    model: &M,
+) -> impl Fn(Real, &[Real; 6]) -> [Real; 6] + '_ {
+    move |t, y| {
+        let state = StateVector::from(*y);
+        let a = model.acceleration(Seconds(t), &state);
+        [y[3], y[4], y[5], a.x, a.y, a.z]
+    }
+}
+
+/// Propagate `state` under `model` for `dt` (negative to go backward) by
+/// Cowell's method, starting with steps of `initial_step`.
+pub fn propagate<M, I>(
+    model: &M,
+    integrator: &I,
+    state: &StateVector,
+    dt: Seconds,
+    initial_step: Seconds,
+) -> Result<StateVector, &'static str>
+where
+    M: ForceModel + ?Sized,
+    I: Integrator,
+{
+    let f = equations_of_motion(model);
+    let y = integrate(
+        integrator,
+        &f,
+        0.0,
+        (*state).into(),
+        dt.value(),
+        initial_step.value(),
+        |_| {},
+    )?;
+    Ok(y.into())
+}
+
+/// Newtonian gravity of a spherically symmetric central body.
+#[derive(Clone, Copy, Debug)]
+pub struct PointMass<'a> {
+    pub body: &'a CelestialBody<'a>,
+}
+
+impl<'a> PointMass<'a> {
+    pub fn new(body: &'a CelestialBody<'a>) -> Self {
+        Self { body }
+    }
+}
+
+impl ForceModel for PointMass<'_> {
+    fn acceleration(&self, _t: Seconds, state: &StateVector) -> Vector3 {
+        let r = state.position.norm();
+        state.position * (-self.body.mu.value() / (r * r * r))
+    }
+}
+
+/// Gravity of the body's zonal harmonics J2 through J`degree`, excluding
+/// the point-mass term.
+///
+/// Derived from the axially symmetric potential
+/// `U = -(μ/r) Σ Jₙ (R/r)ⁿ Pₙ(z/r)`, with Legendre polynomials evaluated by
+/// recurrence, so any degree the body provides coefficients for is supported.
+#[derive(Clone, Copy, Debug)]
+pub struct ZonalHarmonics<'a> {
+    pub body: &'a CelestialBody<'a>,
+    degree: usize,
+}
+
+impl<'a> ZonalHarmonics<'a> {
+    /// Zonal terms from J2 up to and including J`degree`
+    pub fn new(body: &'a CelestialBody<'a>, degree: usize) -> Result<Self, &'static str> {
+        if degree < 2 {
+            return Err("Zonal harmonics start at degree 2");
+        }
+        if degree > body.zonal_harmonics.len() + 1 {
+            return Err("Body has no zonal harmonic coefficients at that degree");
+        }
+        Ok(Self { body, degree })
+    }
+
+    /// Only the J2 oblateness term
+    pub fn j2(body: &'a CelestialBody<'a>) -> Result<Self, &'static str> {
+        Self::new(body, 2)
+    }
+
+    /// Highest degree included
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl ForceModel for ZonalHarmonics<'_> {
+    fn acceleration(&self, _t: Seconds, state: &StateVector) -> Vector3 {
+        let p = state.position;
+        let r = p.norm();
+        let s = p.z / r;
+        let mu = self.body.mu.value();
+        let ratio = Meters::from(self.body.equatorial_radius).value() / r;
+
+        // Pₙ(s) and Pₙ'(s) by the standard recurrences, starting from P₀ and P₁
+        let (mut p_prev, mut p_curr) = (1.0, s);
+        let mut dp_curr = 1.0;
+        // Components along (x, y)/r and along z
+        let mut horizontal = 0.0;
+        let mut vertical = 0.0;
+        for n in 2..=self.degree {
+            let n_real = n as Real;
+            let p_next = ((2.0 * n_real - 1.0) * s * p_curr - (n_real - 1.0) * p_prev) / n_real;
+            let dp_next = s * dp_curr + n_real * p_curr;
+            (p_prev, p_curr) = (p_curr, p_next);
+            dp_curr = dp_next;
+
+            let scale = self.body.zonal_harmonics[n - 2] * pow(ratio, n_real);
+            horizontal += scale * ((n_real + 1.0) * p_curr + s * dp_curr);
+            vertical += scale * ((n_real + 1.0) * s * p_curr - (1.0 - s * s) * dp_curr);
+        }
+
+        let k = mu / (r * r);
+        Vector3::new(
+            k * horizontal * p.x / r,
+            k * horizontal * p.y / r,
+            k * vertical,
+        )
+    }
+}
+
+/// Differential gravity of a third body, such as the Sun or Moon acting on
+/// an Earth orbiter.
+///
+/// The central body is itself accelerated toward the third body, so only
+/// the difference `μ₃ ((r₃ - r)/|r₃ - r|³ - r₃/|r₃|³)` perturbs the orbit.
+#[derive(Clone, Copy, Debug)]
+pub struct ThirdBody<'a, E> {
+    pub body: &'a CelestialBody<'a>,
+    /// Position of the third body relative to the central body at time `t`
+    pub ephemeris: E,
+}
+
+impl<'a, E: Fn(Seconds) -> Vector3> ThirdBody<'a, E> {
+    pub fn new(body: &'a CelestialBody<'a>, ephemeris: E) -> Self {
+        Self { body, ephemeris }
+    }
+}
+
+impl<E: Fn(Seconds) -> Vector3> ForceModel for ThirdBody<'_, E> {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        let r3 = (self.ephemeris)(t);
+        let d = r3 - state.position;
+        let d_norm = d.norm();
+        let r3_norm = r3.norm();
+        let mu = self.body.mu.value();
+        d * (mu / (d_norm * d_norm * d_norm)) - r3 * (mu / (r3_norm * r3_norm * r3_norm))
+    }
+}
+
+/// Atmospheric drag, `a = -½ ρ (C_D A / m) |v_rel| v_rel`.
+///
+/// The atmosphere co-rotates with the body, so drag acts on the velocity
+/// relative to the rotating air, `v_rel = v - ω × r`. Density comes from any
+/// [`AtmosphereModel`] through
+/// [`density_at`](AtmosphereModel::density_at), so models such as
+/// [`DiurnalHarrisPriester`](crate::atmosphere::DiurnalHarrisPriester) can
+/// vary it with position as well as altitude.
+#[derive(Clone, Copy, Debug)]
+pub struct Drag<'a, D> {
+    pub body: &'a CelestialBody<'a>,
+    /// Dimensionless drag coefficient, typically 2.0 to 2.5
+    pub drag_coefficient: Real,
+    /// Cross-sectional area facing the flow
+    pub area: MetersSquared,
+    pub mass: Kilograms,
+    /// Atmosphere, queried through
+    /// [`density_at`](AtmosphereModel::density_at) with the time, position
+    /// and altitude above the body's equatorial radius
+    pub density: D,
+}
+
+impl<'a, D: AtmosphereModel> Drag<'a, D> {
+    pub fn new(
+        body: &'a CelestialBody<'a>,
+        drag_coefficient: Real,
+        area: MetersSquared,
+        mass: Kilograms,
+        density: D,
+    ) -> Self {
+        Self {
+            body,
+            drag_coefficient,
+            area,
+            mass,
+            density,
+        }
+    }
+
+    /// Velocity relative to the co-rotating atmosphere
+    pub fn relative_velocity(&self, state: &StateVector) -> Vector3 {
+        let omega = Vector3::new(0.0, 0.0, self.body.rotation_rate.value());
+        state.velocity - omega.cross(&state.position)
+    }
+}
+
+impl<D: AtmosphereModel> ForceModel for Drag<'_, D> {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        let altitude = state.radius().value() - Meters::from(self.body.equatorial_radius).value();
+        let rho = self
+            .density
+            .density_at(t, &state.position, Meters(altitude))
+            .0;
+        let v_rel = self.relative_velocity(state);
+        let ballistic = self.drag_coefficient * self.area.value() / self.mass.0;
+        v_rel * (-0.5 * rho * ballistic * v_rel.norm())
+    }
+}
+
+/// Solar radiation pressure on a sphere-like spacecraft, with a cylindrical
+/// shadow cast by the central body.
+///
+/// `a = P (AU/d)² C_R (A/m) d̂`, where `d` runs from the Sun to the
+/// spacecraft and `P` is [`SOLAR_PRESSURE_AT_1_AU`].
+#[derive(Clone, Copy, Debug)]
+pub struct SolarRadiationPressure<'a, E> {
+    /// The central body, which casts the shadow
+    pub body: &'a CelestialBody<'a>,
+    /// Radiation pressure coefficient: 1 for a perfect absorber, 2 for a mirror
+    pub reflectivity: Real,
+    /// Cross-sectional area facing the Sun
+    pub area: MetersSquared,
+    pub mass: Kilograms,
+    /// Position of the Sun relative to the central body at time `t`
+    pub sun: E,
+}
+
+impl<'a, E: Fn(Seconds) -> Vector3> SolarRadiationPressure<'a, E> {
+    pub fn new(
+        body: &'a CelestialBody<'a>,
+        reflectivity: Real,
+        area: MetersSquared,
+        mass: Kilograms,
+        sun: E,
+    ) -> Self {
+        Self {
+            body,
+            reflectivity,
+            area,
+            mass,
+            sun,
+        }
+    }
+
+    /// Whether the central body blocks the Sun, treating its shadow as a
+    /// cylinder of the body's equatorial radius
+    pub fn in_shadow(&self, t: Seconds, position: &Vector3) -> bool {
+        let sun_direction = (self.sun)(t).unit();
+        let along = position.dot(&sun_direction);
+        let across = (*position - sun_direction * along).norm();
+        along < 0.0 && across < Meters::from(self.body.equatorial_radius).value()
+    }
+}
+
+impl<E: Fn(Seconds) -> Vector3> ForceModel for SolarRadiationPressure<'_, E> {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        if self.in_shadow(t, &state.position) {
+            return Vector3::ZERO;
+        }
+        let d = state.position - (self.sun)(t);
+        let distance = d.norm();
+        let au = ASTRONOMICAL_UNIT.value();
+        let pressure = SOLAR_PRESSURE_AT_1_AU * (au / distance) * (au / distance);
+        d * (pressure * self.reflectivity * self.area.value() / (self.mass.0 * distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atmosphere::{DiurnalHarrisPriester, EARTH_ATMOSPHERE, HarrisPriester};
+    use crate::celestials::celestial_bodies::{EARTH, MOON, SUN};
+    use crate::integrators::{RungeKuttaFehlberg78, Tolerances};
+    use crate::kepler::propagation::propagate_universal;
+    use crate::test_helpers::assert_vectors_close;
+    use crate::utils::KilogramsPerMetersCubed;
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    fn leo() -> StateVector {
+        let r = 6_778_000.0;
+        let v = sqrt(EARTH.mu.value() / r);
+        StateVector::new(
+            Vector3::new(r, 0.0, 0.0),
+            Vector3::new(0.0, v * 0.8, v * 0.6),
+        )
+    }
+
+    #[test]
+    fn test_point_mass_propagation_matches_kepler() {
+        let state = leo();
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-6, 1e-13));
+        let dt = Seconds(4_000.0);
+        let cowell = propagate(
+            &PointMass::new(&EARTH),
+            &integrator,
+            &state,
+            dt,
+            Seconds(10.0),
+        )
+        .unwrap();
+        let kepler = propagate_universal(&state, EARTH.mu, dt).unwrap();
+        assert!((cowell.position - kepler.position).norm() < 1e-2);
+        assert!((cowell.velocity - kepler.velocity).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_zonal_harmonics_match_closed_forms() {
+        // Vallado eqs. 8-30 and 8-32 for the J2 and J3 accelerations
+        let p = Vector3::new(4_000_000.0, -3_000_000.0, 5_000_000.0);
+        let state = StateVector::new(p, Vector3::ZERO);
+        let (x, y, z) = (p.x, p.y, p.z);
+        let r = p.norm();
+        let mu = EARTH.mu.value();
+        let re = 6_378_137.0;
+        let j2 = EARTH.zonal_harmonics[0];
+        let j3 = EARTH.zonal_harmonics[1];
+
+        let zr2 = z * z / (r * r);
+        let k2 = -1.5 * j2 * mu * re * re / pow(r, 5.0);
+        let a_j2 = Vector3::new(
+            k2 * x * (1.0 - 5.0 * zr2),
+            k2 * y * (1.0 - 5.0 * zr2),
+            k2 * z * (3.0 - 5.0 * zr2),
+        );
+        let k3 = -2.5 * j3 * mu * re * re * re / pow(r, 7.0);
+        let a_j3 = Vector3::new(
+            k3 * x * (3.0 * z - 7.0 * z * zr2),
+            k3 * y * (3.0 * z - 7.0 * z * zr2),
+            k3 * (6.0 * z * z - 7.0 * z * z * zr2 - 0.6 * r * r),
+        );
+
+        let j2_only = ZonalHarmonics::j2(&EARTH).unwrap();
+        assert_vectors_close(j2_only.acceleration(Seconds(0.0), &state), a_j2, 1e-15);
+        let through_j3 = ZonalHarmonics::new(&EARTH, 3).unwrap();
+        assert_vectors_close(
+            through_j3.acceleration(Seconds(0.0), &state),
+            a_j2 + a_j3,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn test_zonal_degree_limits() {
+        assert!(ZonalHarmonics::new(&EARTH, 6).is_ok());
+        assert!(ZonalHarmonics::new(&EARTH, 7).is_err());
+        assert!(ZonalHarmonics::new(&EARTH, 1).is_err());
+        assert_eq!(ZonalHarmonics::new(&MOON, 4).unwrap().degree(), 4);
+    }
+
+    #[test]
+    fn test_third_body_tidal_acceleration() {
+        let moon_distance = 384_400_000.0;
+        let moon = ThirdBody::new(&MOON, move |_t| Vector3::new(moon_distance, 0.0, 0.0));
+
+        // Nothing at the center of the central body
+        let center = StateVector::new(Vector3::ZERO, Vector3::ZERO);
+        assert_vectors_close(
+            moon.acceleration(Seconds(0.0), &center),
+            Vector3::ZERO,
+            1e-20,
+        );
+
+        // Along the Earth-Moon line the tide stretches: ~2μr/d³ toward the Moon
+        let r = 42_164_000.0;
+        let geo = StateVector::new(Vector3::new(r, 0.0, 0.0), Vector3::ZERO);
+        let a = moon.acceleration(Seconds(0.0), &geo);
+        let tidal = 2.0 * MOON.mu.value() * r / pow(moon_distance, 3.0);
+        assert_relative_eq!(a.x, tidal, max_relative = 0.2);
+        assert!(a.x > 0.0);
+
+        // Perpendicular to it the tide compresses: ~-μr/d³
+        let side = StateVector::new(Vector3::new(0.0, r, 0.0), Vector3::ZERO);
+        let a = moon.acceleration(Seconds(0.0), &side);
+        assert_relative_eq!(
+            a.y,
+            -MOON.mu.value() * r / pow(moon_distance, 3.0),
+            max_relative = 0.03
+        );
+    }
+
+    #[test]
+    fn test_drag_opposes_relative_velocity() {
+        let drag = Drag::new(
+            &EARTH,
+            2.2,
+            MetersSquared(10.0),
+            Kilograms(1_000.0),
+            |_altitude: Meters| KilogramsPerMetersCubed(1e-12),
+        );
+        let state = leo();
+        let a = drag.acceleration(Seconds(0.0), &state);
+        let v_rel = drag.relative_velocity(&state);
+
+        assert_relative_eq!(a.unit().dot(&v_rel.unit()), -1.0, epsilon = 1e-12);
+        let expected = 0.5 * 1e-12 * 2.2 * 10.0 / 1_000.0 * v_rel.norm() * v_rel.norm();
+        assert_relative_eq!(a.norm(), expected, max_relative = 1e-12);
+        // The co-rotating atmosphere slows the relative flow for a prograde orbit
+        assert!(v_rel.norm() < state.velocity.norm());
+    }
+
+    #[test]
+    fn test_drag_sees_the_diurnal_bulge() {
+        let model = HarrisPriester::new(EARTH_ATMOSPHERE.harris_priester).unwrap();
+        let sun = |_t: Seconds| Vector3::new(ASTRONOMICAL_UNIT.value(), 0.0, 0.0);
+        let drag = Drag::new(
+            &EARTH,
+            2.2,
+            MetersSquared(10.0),
+            Kilograms(1_000.0),
+            DiurnalHarrisPriester::new(model, sun),
+        );
+        let day = leo();
+        let night = StateVector::new(-day.position, -day.velocity);
+
+        let ratio = drag.acceleration(Seconds(0.0), &day).norm()
+            / drag.acceleration(Seconds(0.0), &night).norm();
+        let altitude = Meters(day.radius().value() - Meters::from(EARTH.equatorial_radius).value());
+        let cos_psi = HarrisPriester::bulge_cosine(&day.position, &sun(Seconds(0.0)));
+        assert_relative_eq!(
+            ratio,
+            model.diurnal_density(altitude, cos_psi).0
+                / model.diurnal_density(altitude, -cos_psi).0,
+            max_relative = 1e-12
+        );
+        assert!(ratio > 2.0);
+    }
+
+    #[test]
+    fn test_solar_radiation_pressure_and_shadow() {
+        let au = ASTRONOMICAL_UNIT.value();
+        let srp = SolarRadiationPressure::new(
+            &EARTH,
+            1.3,
+            MetersSquared(20.0),
+            Kilograms(500.0),
+            move |_t| Vector3::new(au, 0.0, 0.0),
+        );
+        let r = 7_000_000.0;
+
+        // Between Earth and Sun: pushed away from the Sun
+        let day = StateVector::new(Vector3::new(r, 0.0, 0.0), Vector3::ZERO);
+        let a = srp.acceleration(Seconds(0.0), &day);
+        let expected = SOLAR_PRESSURE_AT_1_AU * 1.3 * 20.0 / 500.0;
+        assert_relative_eq!(a.x, -expected, max_relative = 1e-3);
+
+        // Directly behind Earth: eclipsed
+        let night = StateVector::new(Vector3::new(-r, 0.0, 0.0), Vector3::ZERO);
+        assert!(srp.in_shadow(Seconds(0.0), &night.position));
+        assert_eq!(srp.acceleration(Seconds(0.0), &night), Vector3::ZERO);
+
+        // Behind Earth but outside the shadow cylinder: lit
+        let beside = Vector3::new(-r, 7_000_000.0, 0.0);
+        assert!(!srp.in_shadow(Seconds(0.0), &beside));
+    }
+
+    #[test]
+    fn test_models_sum() {
+        let state = leo();
+        let t = Seconds(0.0);
+        let point = PointMass::new(&EARTH);
+        let zonal = ZonalHarmonics::new(&EARTH, 6).unwrap();
+        let sun = ThirdBody::new(&SUN, |_t| Vector3::new(0.0, 1.496e11, 0.0));
+        let expected = point.acceleration(t, &state)
+            + zonal.acceleration(t, &state)
+            + sun.acceleration(t, &state);
+
+        let chained = point.plus(zonal).plus(&sun);
+        assert_vectors_close(chained.acceleration(t, &state), expected, 1e-15);
+
+        let models: [&dyn ForceModel; 3] = [&point, &zonal, &sun];
+        assert_vectors_close(models.acceleration(t, &state), expected, 1e-15);
+        assert_vectors_close(models[..].acceleration(t, &state), expected, 1e-15);
+    }
+
+    #[test]
+    fn test_j2_regresses_the_node() {
+        // Prograde orbits precess westward under J2
+        let state = leo();
+        let model = PointMass::new(&EARTH).plus(ZonalHarmonics::j2(&EARTH).unwrap());
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-6, 1e-12));
+        let day = propagate(
+            &model,
+            &integrator,
+            &state,
+            Seconds(86_400.0),
+            Seconds(30.0),
+        )
+        .unwrap();
+        let node = |s: &StateVector| {
+            let h = s.angular_momentum();
+            libm::atan2(h.x, -h.y)
+        };
+        let drift = node(&day) - node(&state);
+
+        // Secular rate: -3/2 n J2 (R/p)² cos i
+        let r = state.radius().value();
+        let n = sqrt(EARTH.mu.value() / (r * r * r));
+        let re = Meters::from(EARTH.equatorial_radius).value();
+        let cos_i = state.angular_momentum().unit().z;
+        let rate = -1.5 * n * EARTH.zonal_harmonics[0] * (re / r) * (re / r) * cos_i;
+        assert_relative_eq!(drift, rate * 86_400.0, max_relative = 0.05);
+    }
+}
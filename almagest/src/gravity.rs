@@ -0,0 +1,479 @@
+//! # Spherical-Harmonic Gravity
+//!
+//! The gravity of a real body departs from that of a point mass in ways the
+//! zonal terms of [`forces::ZonalHarmonics`](crate::forces::ZonalHarmonics)
+//! cannot capture: the Moon's mass concentrations (mascons), for instance,
+//! make most low lunar orbits crash within months. Gravity models express
+//! the full field as a spherical-harmonic series,
+//!
+//! `U = (μ/r) Σₙ (R/r)ⁿ Σₘ P̄ₙₘ(sin φ) (C̄ₙₘ cos mλ + S̄ₙₘ sin mλ)`
+//!
+//! with fully normalized coefficients C̄ₙₘ, S̄ₙₘ in a frame fixed to the
+//! rotating body.
+//!
+//! [`SphericalHarmonics`] evaluates the potential and acceleration up to any
+//! degree and order using Pines' formulation: normalized derived Legendre
+//! functions built by recursion in the Cartesian direction cosines, which
+//! stays well behaved at the poles and needs no allocation. Coefficients are
+//! borrowed from caller-owned slices in triangular order (see
+//! [`coefficient_index`]); [`GravityField`] turns them into a
+//! [`ForceModel`] by rotating with the body.
+//!
+//! Coefficient files are read by the [`files`] module. Parsing works on any
+//! string in `no_std`; reading from a local path requires the `std` feature.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::gravity::SphericalHarmonics;
+//! use almagest::utils::{Meters, Vector3};
+//!
+//! // Point mass plus J2, with C̄₂₀ = -J2/√5
+//! let c = [1.0, 0.0, 0.0, -EARTH.j2() / 5.0_f64.sqrt(), 0.0, 0.0];
+//! let s = [0.0; 6];
+//! let field = SphericalHarmonics::new(EARTH.mu, Meters(6_378_137.0), 2, &c, &s)
+//!     .expect("Enough coefficients for degree 2");
+//!
+//! let a = field.acceleration(&Vector3::new(7_000_000.0, 0.0, 0.0));
+//! assert!(a.x < -8.0);
+//! ```
+
+pub mod files;
+
+use libm::{sincos, sqrt};
+
+use crate::celestials::CelestialBody;
+use crate::forces::ForceModel;
+use crate::kepler::StateVector;
+use crate::utils::{
+    Meters, MetersCubedPerSecondSquared, Radians, RadiansPerSecond, Real, Seconds, Vector3,
+};
+
+/// Position of C̄ₙₘ and S̄ₙₘ in a triangular coefficient slice, which lists
+/// degree 0 first and orders 0 through n within each degree
+pub const fn coefficient_index(n: usize, m: usize) -> usize {
+    n * (n + 1) / 2 + m
+}
+
+/// Length of a triangular coefficient slice complete through `degree`
+pub const fn coefficient_count(degree: usize) -> usize {
+    (degree + 1) * (degree + 2) / 2
+}
+
+/// Factor converting an unnormalized coefficient to a fully normalized one,
+/// `√((n+m)! / ((2 - δ₀ₘ)(2n+1)(n-m)!))`. Zero when `m > n`, where there
+/// is no such coefficient.
+pub fn normalization_factor(n: usize, m: usize) -> Real {
+    if m > n {
+        return 0.0;
+    }
+    let mut factor = sqrt(if m == 0 { 1.0 } else { 0.5 } / (2 * n + 1) as Real);
+    // √((n+m)!/(n-m)!) as a product of square roots, which stays finite
+    // far beyond the degree where the ratio itself overflows
+    for k in (n - m + 1)..=(n + m) {
+        factor *= sqrt(k as Real);
+    }
+    factor
+}
+
+/// A gravity field expanded in fully normalized spherical harmonics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SphericalHarmonics<'a> {
+    /// Gravitational parameter the coefficients are scaled to
+    pub mu: MetersCubedPerSecondSquared,
+    /// Reference radius the coefficients are scaled to
+    pub radius: Meters,
+    degree: usize,
+    order: usize,
+    c: &'a [Real],
+    s: &'a [Real],
+}
+
+impl<'a> SphericalHarmonics<'a> {
+    /// A field through `degree` and order, from triangular coefficient
+    /// slices of at least [`coefficient_count`]`(degree)` entries.
+    ///
+    /// C̄₀₀ is normally 1, which makes the series include the point-mass
+    /// term.
+    pub fn new(
+        mu: MetersCubedPerSecondSquared,
+        radius: Meters,
+        degree: usize,
+        c: &'a [Real],
+        s: &'a [Real],
+    ) -> Result<Self, &'static str> {
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        if radius.value() <= 0.0 || !radius.value().is_finite() {
+            return Err("Reference radius must be positive");
+        }
+        let count = coefficient_count(degree);
+        if c.len() < count || s.len() < count {
+            return Err("Too few coefficients for the requested degree");
+        }
+        Ok(Self {
+            mu,
+            radius,
+            degree,
+            order: degree,
+            c,
+            s,
+        })
+    }
+
+    /// The same field cut off at a lower degree and order
+    pub fn truncated(&self, degree: usize, order: usize) -> Result<Self, &'static str> {
+        if degree > self.degree || order > self.order {
+            return Err("Cannot truncate to a higher degree or order");
+        }
+        Ok(Self {
+            degree,
+            order: order.min(degree),
+            ..*self
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Normalized coefficients (C̄ₙₘ, S̄ₙₘ)
+    pub fn coefficients(&self, n: usize, m: usize) -> Option<(Real, Real)> {
+        if m > n || n > self.degree {
+            return None;
+        }
+        let index = coefficient_index(n, m);
+        Some((self.c[index], self.s[index]))
+    }
+
+    /// Gravitational potential at a body-fixed position, in J/kg, positive
+    /// and equal to μ/r for a point mass
+    pub fn potential(&self, position: &Vector3) -> Real {
+        let mut sum = 0.0;
+        self.for_each_term(position, |term| sum += term.scale * term.a * term.d);
+        sum * self.mu.value() / position.norm()
+    }
+
+    /// Gravitational acceleration, in m/s², at a body-fixed position
+    pub fn acceleration(&self, position: &Vector3) -> Vector3 {
+        let r = position.norm();
+        let q = *position / r;
+        let (mut a1, mut a2, mut a3, mut a4) = (0.0, 0.0, 0.0, 0.0);
+        self.for_each_term(position, |term| {
+            let m = term.m as Real;
+            a1 += term.scale * m * term.a * term.e;
+            a2 += term.scale * m * term.a * term.f;
+            a3 += term.scale * term.da * term.d;
+            a4 -= term.scale * ((term.n + term.m + 1) as Real * term.a + q.z * term.da) * term.d;
+        });
+        let k = self.mu.value() / (r * r);
+        Vector3::new(
+            k * (a1 + a4 * q.x),
+            k * (a2 + a4 * q.y),
+            k * (a3 + a4 * q.z),
+        )
+    }
+
+    /// Visit every (n, m) term of the series at `position`.
+    ///
+    /// Works column by column in m, carrying the derived Legendre functions
+    /// Āₙₘ(u) and Āₙ,ₘ₊₁(u) up in degree with the standard three-term
+    /// recurrence; the latter gives dĀₙₘ/du.
+    fn for_each_term<F: FnMut(&Term)>(&self, position: &Vector3, mut visit: F) {
+        let r = position.norm();
+        let (s, t, u) = (position.x / r, position.y / r, position.z / r);
+        let ratio = self.radius.value() / r;
+
+        // Āₘₘ, (R/r)ᵐ and Re/Im of (s + it)ᵐ, advanced once per column
+        let mut diagonal = 1.0;
+        let mut ratio_m = 1.0;
+        let (mut re, mut im) = (1.0, 0.0);
+        let (mut re_prev, mut im_prev) = (0.0, 0.0);
+        for m in 0..=self.order {
+            let next_diagonal = diagonal * diagonal_step(m + 1);
+            // Āₙₘ and Āₙ,ₘ₊₁ for the previous two degrees
+            let (mut a_prev, mut a_curr) = (0.0, diagonal);
+            let (mut b_prev, mut b_curr) = (0.0, 0.0);
+            let mut ratio_n = ratio_m;
+            for n in m..=self.degree {
+                if n > m {
+                    (a_prev, a_curr) = (a_curr, recur(n, m, u, a_curr, a_prev));
+                    let b_next = if n == m + 1 {
+                        next_diagonal
+                    } else {
+                        recur(n, m + 1, u, b_curr, b_prev)
+                    };
+                    (b_prev, b_curr) = (b_curr, b_next);
+                    ratio_n *= ratio;
+                }
+                let index = coefficient_index(n, m);
+                let (c, sn) = (self.c[index], self.s[index]);
+                let derivative_scale = if m == 0 { 0.5 } else { 1.0 };
+                visit(&Term {
+                    n,
+                    m,
+                    scale: ratio_n,
+                    a: a_curr,
+                    da: sqrt(derivative_scale * ((n - m) * (n + m + 1)) as Real) * b_curr,
+                    d: c * re + sn * im,
+                    e: c * re_prev + sn * im_prev,
+                    f: sn * re_prev - c * im_prev,
+                });
+            }
+            diagonal = next_diagonal;
+            ratio_m *= ratio;
+            (re_prev, im_prev) = (re, im);
+            (re, im) = (s * re - t * im, s * im + t * re);
+        }
+    }
+}
+
+/// One term of the series, with the pieces each quantity needs
+struct Term {
+    n: usize,
+    m: usize,
+    /// (R/r)ⁿ
+    scale: Real,
+    /// Āₙₘ(u)
+    a: Real,
+    /// dĀₙₘ/du
+    da: Real,
+    /// C̄ rₘ + S̄ iₘ
+    d: Real,
+    /// C̄ rₘ₋₁ + S̄ iₘ₋₁
+    e: Real,
+    /// S̄ rₘ₋₁ - C̄ iₘ₋₁
+    f: Real,
+}
+
+/// Āₘₘ / Āₘ₋₁,ₘ₋₁
+fn diagonal_step(m: usize) -> Real {
+    match m {
+        0 => 1.0,
+        1 => sqrt(3.0),
+        _ => sqrt((2 * m + 1) as Real / (2 * m) as Real),
+    }
+}
+
+/// Āₙₘ from Āₙ₋₁,ₘ and Āₙ₋₂,ₘ, for n > m
+fn recur(n: usize, m: usize, u: Real, prev: Real, prev2: Real) -> Real {
+    let (n, m) = (n as Real, m as Real);
+    let xi = sqrt((2.0 * n - 1.0) * (2.0 * n + 1.0) / ((n - m) * (n + m)));
+    let eta = sqrt(
+        (2.0 * n + 1.0) * (n + m - 1.0) * (n - m - 1.0) / ((2.0 * n - 3.0) * (n + m) * (n - m)),
+    );
+    u * xi * prev - eta * prev2
+}
+
+/// A spherical-harmonic field attached to a rotating body, usable as a
+/// [`ForceModel`] in the inertial frame.
+///
+/// The body-fixed frame turns about the inertial z-axis at the body's
+/// rotation rate, starting from `prime_meridian` at `t = 0`. The field
+/// includes the point-mass term whenever C̄₀₀ = 1, so it replaces
+/// [`PointMass`](crate::forces::PointMass) rather than adding to it.
+#[derive(Clone, Copy, Debug)]
+pub struct GravityField<'a> {
+    pub harmonics: SphericalHarmonics<'a>,
+    pub rotation_rate: RadiansPerSecond,
+    /// Angle from the inertial x-axis to the body's prime meridian at `t = 0`
+    pub prime_meridian: Radians,
+}
+
+impl<'a> GravityField<'a> {
+    pub fn new(harmonics: SphericalHarmonics<'a>, body: &CelestialBody) -> Self {
+        Self {
+            harmonics,
+            rotation_rate: body.rotation_rate,
+            prime_meridian: Radians::ZERO,
+        }
+    }
+
+    /// Angle of the prime meridian from the inertial x-axis at `t`
+    pub fn rotation_angle(&self, t: Seconds) -> Radians {
+        Radians(self.prime_meridian.value() + self.rotation_rate.value() * t.value())
+    }
+}
+
+impl ForceModel for GravityField<'_> {
+    fn acceleration(&self, t: Seconds, state: &StateVector) -> Vector3 {
+        let (sin, cos) = sincos(self.rotation_angle(t).value());
+        let p = state.position;
+        let body_fixed = Vector3::new(cos * p.x + sin * p.y, -sin * p.x + cos * p.y, p.z);
+        let a = self.harmonics.acceleration(&body_fixed);
+        Vector3::new(cos * a.x - sin * a.y, sin * a.x + cos * a.y, a.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MOON};
+    use crate::forces::{PointMass, ZonalHarmonics};
+    use crate::test_helpers::assert_vectors_close;
+    use approx::assert_relative_eq;
+
+    const DEGREE: usize = 8;
+    const COUNT: usize = coefficient_count(DEGREE);
+
+    /// Earth's zonals through J6, as normalized coefficients
+    fn zonal_field() -> ([Real; COUNT], [Real; COUNT]) {
+        let mut c = [0.0; COUNT];
+        c[0] = 1.0;
+        for n in 2..=6 {
+            c[coefficient_index(n, 0)] = -EARTH.zonal_harmonic(n) * normalization_factor(n, 0);
+        }
+        (c, [0.0; COUNT])
+    }
+
+    /// Arbitrary but deterministic tesseral and sectoral terms
+    fn lumpy_field() -> ([Real; COUNT], [Real; COUNT]) {
+        let mut c = [0.0; COUNT];
+        let mut s = [0.0; COUNT];
+        c[0] = 1.0;
+        for n in 2..=DEGREE {
+            for m in 0..=n {
+                let k = coefficient_index(n, m) as Real;
+                c[coefficient_index(n, m)] = 1e-5 * libm::sin(1.7 * k + 0.3);
+                if m > 0 {
+                    s[coefficient_index(n, m)] = 1e-5 * libm::cos(2.3 * k);
+                }
+            }
+        }
+        (c, s)
+    }
+
+    fn gradient(field: &SphericalHarmonics, p: &Vector3) -> Vector3 {
+        let h = 1.0;
+        let du = |d: Vector3| (field.potential(&(*p + d)) - field.potential(&(*p - d))) / (2.0 * h);
+        Vector3::new(
+            du(Vector3::new(h, 0.0, 0.0)),
+            du(Vector3::new(0.0, h, 0.0)),
+            du(Vector3::new(0.0, 0.0, h)),
+        )
+    }
+
+    #[test]
+    fn test_indexing() {
+        assert_eq!(coefficient_index(0, 0), 0);
+        assert_eq!(coefficient_index(2, 0), 3);
+        assert_eq!(coefficient_index(2, 2), 5);
+        assert_eq!(coefficient_count(2), 6);
+        assert_relative_eq!(normalization_factor(2, 0), 1.0 / sqrt(5.0));
+        assert_relative_eq!(normalization_factor(2, 2), sqrt(2.4), epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_normalization_factor_at_high_degree() {
+        // Compared with the factorials in log space
+        for (n, m) in [(100, 0), (100, 50), (120, 120), (150, 140)] {
+            let log_ratio = libm::lgamma((n + m + 1) as Real)
+                - libm::lgamma((n - m + 1) as Real)
+                - libm::log(if m == 0 { 1.0 } else { 2.0 } * (2 * n + 1) as Real);
+            let factor = normalization_factor(n, m);
+            assert!(factor.is_finite());
+            assert_relative_eq!(factor, libm::exp(0.5 * log_ratio), max_relative = 1e-10);
+        }
+        assert_eq!(normalization_factor(2, 3), 0.0);
+        assert_eq!(normalization_factor(0, 1), 0.0);
+    }
+
+    #[test]
+    fn test_point_mass() {
+        let field =
+            SphericalHarmonics::new(EARTH.mu, Meters(6_378_137.0), 0, &[1.0], &[0.0]).unwrap();
+        let p = Vector3::new(3e6, -4e6, 5e6);
+        let expected = PointMass::new(&EARTH).acceleration(Seconds(0.0), &StateVector::new(p, p));
+        assert_vectors_close(field.acceleration(&p), expected, 1e-18);
+        assert_relative_eq!(field.potential(&p), EARTH.mu.value() / p.norm());
+    }
+
+    #[test]
+    fn test_zonal_terms_match_zonal_harmonics() {
+        let (c, s) = zonal_field();
+        let field = SphericalHarmonics::new(EARTH.mu, Meters(6_378_137.0), DEGREE, &c, &s).unwrap();
+        let zonal = PointMass::new(&EARTH).plus(ZonalHarmonics::new(&EARTH, 6).unwrap());
+        // Including both poles, where latitude-based formulations break down
+        for p in [
+            Vector3::new(7e6, 0.0, 0.0),
+            Vector3::new(4e6, -3e6, 5e6),
+            Vector3::new(-1e6, 2e6, -6.5e6),
+            Vector3::new(0.0, 0.0, 7e6),
+            Vector3::new(0.0, 0.0, -7e6),
+        ] {
+            let expected = zonal.acceleration(Seconds(0.0), &StateVector::new(p, Vector3::ZERO));
+            assert_vectors_close(field.acceleration(&p), expected, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_acceleration_is_gradient_of_potential() {
+        let (c, s) = lumpy_field();
+        let field = SphericalHarmonics::new(MOON.mu, Meters(1_738_000.0), DEGREE, &c, &s).unwrap();
+        for p in [
+            Vector3::new(1.8e6, 0.3e6, -0.2e6),
+            Vector3::new(-0.9e6, 1.2e6, 1.1e6),
+            Vector3::new(0.0, 0.0, 1.9e6),
+        ] {
+            let a = field.acceleration(&p);
+            let expected = gradient(&field, &p);
+            assert_vectors_close(a, expected, 1e-8);
+            // The non-central part is not negligible
+            let central = p * (-MOON.mu.value() / libm::pow(p.norm(), 3.0));
+            assert!((a - central).norm() > 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_truncation() {
+        let (c, s) = lumpy_field();
+        let field = SphericalHarmonics::new(MOON.mu, Meters(1_738_000.0), DEGREE, &c, &s).unwrap();
+        let low = field.truncated(2, 0).unwrap();
+        assert_eq!((low.degree(), low.order()), (2, 0));
+        assert_eq!(low.coefficients(2, 0), Some((c[3], 0.0)));
+        assert_eq!(low.coefficients(3, 0), None);
+
+        // Order 0 keeps only the zonal terms
+        let p = Vector3::new(1.8e6, 0.3e6, -0.2e6);
+        let mut zonal_only = [0.0; COUNT];
+        zonal_only[0] = 1.0;
+        zonal_only[3] = c[3];
+        let no_sines = [0.0; COUNT];
+        let expected =
+            SphericalHarmonics::new(MOON.mu, Meters(1_738_000.0), 2, &zonal_only, &no_sines)
+                .unwrap()
+                .acceleration(&p);
+        assert_vectors_close(low.acceleration(&p), expected, 1e-18);
+        assert!(field.truncated(DEGREE + 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_rotating_field() {
+        let (c, s) = lumpy_field();
+        let harmonics =
+            SphericalHarmonics::new(MOON.mu, Meters(1_738_000.0), DEGREE, &c, &s).unwrap();
+        let field = GravityField::new(harmonics, &MOON);
+
+        // A quarter turn later, a position a quarter turn further on sees the
+        // same field, rotated
+        let quarter = core::f64::consts::FRAC_PI_2 / MOON.rotation_rate.value();
+        let p = Vector3::new(1.8e6, 0.3e6, -0.2e6);
+        let turned = Vector3::new(-p.y, p.x, p.z);
+        let a0 = field.acceleration(Seconds(0.0), &StateVector::new(p, Vector3::ZERO));
+        let a1 = field.acceleration(Seconds(quarter), &StateVector::new(turned, Vector3::ZERO));
+        assert_vectors_close(a1, Vector3::new(-a0.y, a0.x, a0.z), 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        let c = [1.0, 0.0, 0.0];
+        assert!(SphericalHarmonics::new(MOON.mu, Meters(1.0), 2, &c, &c).is_err());
+        assert!(SphericalHarmonics::new(MOON.mu, Meters(0.0), 1, &c, &c).is_err());
+    }
+}
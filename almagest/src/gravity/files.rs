@@ -0,0 +1,513 @@
+//! # Gravity Coefficient Files
+//!
+//! Readers for the common distribution formats of spherical-harmonic gravity
+//! models:
+//!
+//! - **ICGEM** (`.gfc`): the International Centre for Global Earth Models
+//!   format used for EGM2008 and most modern Earth, lunar and planetary
+//!   fields. A `key value` header closed by `end_of_head`, then
+//!   `gfc n m C S [σC σS]` lines.
+//! - **PDS SHADR** (`.tab`): the Planetary Data System spherical-harmonic
+//!   ASCII format used by the GRAIL lunar fields. A comma-separated header
+//!   line `R_ref (km), GM (km³/s²), σGM, degree, order, normalized, ...`,
+//!   then `n, m, C, S, σC, σS` lines.
+//! - **Plain tables**: bare `n m C S ...` lines, as in the original EGM96
+//!   release, with μ and the reference radius supplied by the caller.
+//!
+//! The `parse_*` functions are `no_std`: they fill caller-provided
+//! triangular slices (see [`coefficient_count`]) up to a maximum degree and
+//! report the header. Unnormalized coefficients are converted, so the
+//! slices always hold fully normalized values ready for
+//! [`SphericalHarmonics`]. Fortran `D` exponents are accepted throughout.
+//!
+//! With the `std` feature, [`GravityModel`] owns the coefficients and
+//! loads them from a local path.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "std")]
+//! # {
+//! use almagest::gravity::files::GravityModel;
+//! use almagest::utils::Vector3;
+//!
+//! // GRAIL lunar field, truncated to degree and order 100
+//! let model = GravityModel::load("gggrx_1200a_sha.tab", 100).expect("Readable field");
+//! let field = model.harmonics();
+//! let a = field.acceleration(&Vector3::new(1_788_000.0, 0.0, 0.0));
+//! # }
+//! ```
+
+use crate::gravity::{coefficient_count, coefficient_index, normalization_factor};
+use crate::utils::{Meters, MetersCubedPerSecondSquared, Real};
+
+#[cfg(feature = "std")]
+use crate::gravity::SphericalHarmonics;
+#[cfg(feature = "std")]
+use std::{path::Path, vec, vec::Vec};
+
+/// Longest numeric token that can be rewritten from Fortran `D` notation
+const MAX_NUMBER_LENGTH: usize = 64;
+
+/// What a coefficient file says about the field it holds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldHeader {
+    /// Gravitational parameter the coefficients are scaled to
+    pub mu: MetersCubedPerSecondSquared,
+    /// Reference radius the coefficients are scaled to
+    pub radius: Meters,
+    /// Highest degree read, at most the requested maximum and never beyond
+    /// the last coefficient in the file
+    pub degree: usize,
+}
+
+/// Read an ICGEM `.gfc` file through `max_degree`.
+///
+/// Time-variable `gfct` lines contribute their reference-epoch values;
+/// trend and periodic terms are ignored.
+pub fn parse_icgem(
+    text: &str,
+    max_degree: usize,
+    c: &mut [Real],
+    s: &mut [Real],
+) -> Result<FieldHeader, &'static str> {
+    prepare(max_degree, c, s)?;
+    let mut lines = text.lines();
+    let (mut mu, mut radius, mut file_degree) = (None, None, None);
+    let mut normalized = true;
+    let mut header_closed = false;
+    for line in lines.by_ref() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("end_of_head") => {
+                header_closed = true;
+                break;
+            }
+            Some("earth_gravity_constant" | "gravity_constant") => {
+                mu = Some(parse_number(fields.next())?);
+            }
+            Some("radius") => radius = Some(parse_number(fields.next())?),
+            Some("max_degree") => file_degree = Some(parse_degree(fields.next())?),
+            Some("norm") => normalized = fields.next() != Some("unnormalized"),
+            _ => {}
+        }
+    }
+    if !header_closed {
+        return Err("ICGEM header is missing end_of_head");
+    }
+    let (Some(mu), Some(radius)) = (mu, radius) else {
+        return Err("ICGEM header is missing the gravity constant or radius");
+    };
+
+    let mut degree = 0;
+    for line in lines {
+        let mut fields = line.split_whitespace();
+        if !matches!(fields.next(), Some("gfc" | "gfct")) {
+            continue;
+        }
+        degree = degree.max(store(&mut fields, max_degree, normalized, c, s)?);
+    }
+    let degree = file_degree.map_or(degree, |file_degree| file_degree.min(degree));
+    header(mu, radius, degree.min(max_degree))
+}
+
+/// Read a PDS SHADR `.tab` file through `max_degree`.
+///
+/// The header gives the reference radius in km and μ in km³/s²; both are
+/// converted to SI.
+pub fn parse_shadr(
+    text: &str,
+    max_degree: usize,
+    c: &mut [Real],
+    s: &mut [Real],
+) -> Result<FieldHeader, &'static str> {
+    prepare(max_degree, c, s)?;
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let first = lines.next().ok_or("SHADR file is empty")?;
+    let mut fields = first.split(',').map(str::trim);
+    let radius_km = parse_number(fields.next())?;
+    let mu_km = parse_number(fields.next())?;
+    let _sigma = fields.next();
+    let file_degree = parse_degree(fields.next())?;
+    let _order = fields.next();
+    let normalized = parse_number(fields.next())? != 0.0;
+
+    let mut degree = 0;
+    for line in lines {
+        let mut fields = line.split(',').map(str::trim);
+        degree = degree.max(store(&mut fields, max_degree, normalized, c, s)?);
+    }
+    header(
+        mu_km * 1e9,
+        radius_km * 1e3,
+        file_degree.min(degree).min(max_degree),
+    )
+}
+
+/// Read a plain `n m C S ...` table through `max_degree`.
+///
+/// Blank lines and lines starting with `#` are skipped. Returns the highest
+/// degree found.
+pub fn parse_coefficient_table(
+    text: &str,
+    max_degree: usize,
+    normalized: bool,
+    c: &mut [Real],
+    s: &mut [Real],
+) -> Result<usize, &'static str> {
+    prepare(max_degree, c, s)?;
+    let mut degree = 0;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        degree = degree.max(store(&mut fields, max_degree, normalized, c, s)?);
+    }
+    Ok(degree.min(max_degree))
+}
+
+/// A gravity model that owns its coefficients.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GravityModel {
+    pub header: FieldHeader,
+    c: Vec<Real>,
+    s: Vec<Real>,
+}
+
+#[cfg(feature = "std")]
+impl GravityModel {
+    /// Load an ICGEM or SHADR file from `path`, truncated to `max_degree`.
+    ///
+    /// Files ending in `.gfc` are read as ICGEM and `.tab` as SHADR;
+    /// anything else is identified by the presence of an ICGEM header.
+    pub fn load(path: impl AsRef<Path>, max_degree: usize) -> Result<Self, &'static str> {
+        let path = path.as_ref();
+        let text = read(path)?;
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let icgem = match extension {
+            Some(ext) if ext.eq_ignore_ascii_case("gfc") => true,
+            Some(ext) if ext.eq_ignore_ascii_case("tab") => false,
+            _ => text.contains("end_of_head"),
+        };
+        if icgem {
+            Self::from_icgem(&text, max_degree)
+        } else {
+            Self::from_shadr(&text, max_degree)
+        }
+    }
+
+    /// Load a plain `n m C S` table from `path`, whose scaling is not
+    /// recorded in the file.
+    pub fn load_table(
+        path: impl AsRef<Path>,
+        mu: MetersCubedPerSecondSquared,
+        radius: Meters,
+        max_degree: usize,
+        normalized: bool,
+    ) -> Result<Self, &'static str> {
+        let text = read(path.as_ref())?;
+        let (mut c, mut s) = Self::buffers(max_degree);
+        let degree = parse_coefficient_table(&text, max_degree, normalized, &mut c, &mut s)?;
+        let header = header(mu.value(), radius.value(), degree)?;
+        Ok(Self::truncated(header, c, s))
+    }
+
+    pub fn from_icgem(text: &str, max_degree: usize) -> Result<Self, &'static str> {
+        let (mut c, mut s) = Self::buffers(max_degree);
+        let header = parse_icgem(text, max_degree, &mut c, &mut s)?;
+        Ok(Self::truncated(header, c, s))
+    }
+
+    pub fn from_shadr(text: &str, max_degree: usize) -> Result<Self, &'static str> {
+        let (mut c, mut s) = Self::buffers(max_degree);
+        let header = parse_shadr(text, max_degree, &mut c, &mut s)?;
+        Ok(Self::truncated(header, c, s))
+    }
+
+    /// The field, ready to evaluate
+    pub fn harmonics(&self) -> SphericalHarmonics<'_> {
+        SphericalHarmonics::new(
+            self.header.mu,
+            self.header.radius,
+            self.header.degree,
+            &self.c,
+            &self.s,
+        )
+        .expect("Header and coefficients were validated on load")
+    }
+
+    fn buffers(max_degree: usize) -> (Vec<Real>, Vec<Real>) {
+        let count = coefficient_count(max_degree);
+        (vec![0.0; count], vec![0.0; count])
+    }
+
+    fn truncated(header: FieldHeader, mut c: Vec<Real>, mut s: Vec<Real>) -> Self {
+        let count = coefficient_count(header.degree);
+        c.truncate(count);
+        s.truncate(count);
+        Self { header, c, s }
+    }
+}
+
+#[cfg(feature = "std")]
+fn read(path: &Path) -> Result<std::string::String, &'static str> {
+    std::fs::read_to_string(path).map_err(|_| "Could not read gravity coefficient file")
+}
+
+/// Check the buffers can hold `max_degree` and reset them to a bare point mass
+fn prepare(max_degree: usize, c: &mut [Real], s: &mut [Real]) -> Result<(), &'static str> {
+    let count = coefficient_count(max_degree);
+    if c.len() < count || s.len() < count {
+        return Err("Too few coefficients for the requested degree");
+    }
+    c[..count].fill(0.0);
+    s[..count].fill(0.0);
+    c[0] = 1.0;
+    Ok(())
+}
+
+/// Parse `n m C S` from the front of `fields` and store it if within
+/// `max_degree`, returning its degree
+fn store<'t>(
+    fields: &mut impl Iterator<Item = &'t str>,
+    max_degree: usize,
+    normalized: bool,
+    c: &mut [Real],
+    s: &mut [Real],
+) -> Result<usize, &'static str> {
+    let n = parse_degree(fields.next())?;
+    let m = parse_degree(fields.next())?;
+    let c_nm = parse_number(fields.next())?;
+    let s_nm = parse_number(fields.next())?;
+    if m > n {
+        return Err("Coefficient order exceeds its degree");
+    }
+    if n <= max_degree {
+        let scale = if normalized {
+            1.0
+        } else {
+            normalization_factor(n, m)
+        };
+        let index = coefficient_index(n, m);
+        c[index] = c_nm * scale;
+        s[index] = s_nm * scale;
+    }
+    Ok(n)
+}
+
+fn header(mu: Real, radius: Real, degree: usize) -> Result<FieldHeader, &'static str> {
+    if mu <= 0.0 || !mu.is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    if radius <= 0.0 || !radius.is_finite() {
+        return Err("Reference radius must be positive");
+    }
+    Ok(FieldHeader {
+        mu: MetersCubedPerSecondSquared(mu),
+        radius: Meters(radius),
+        degree,
+    })
+}
+
+fn parse_degree(field: Option<&str>) -> Result<usize, &'static str> {
+    field
+        .and_then(|f| f.parse().ok())
+        .ok_or("Malformed degree or order in gravity coefficient file")
+}
+
+/// Parse a floating-point field, accepting Fortran `D` exponents
+fn parse_number(field: Option<&str>) -> Result<Real, &'static str> {
+    const MALFORMED: &str = "Malformed number in gravity coefficient file";
+    let field = field.ok_or(MALFORMED)?;
+    if !field.contains(['D', 'd']) {
+        return field.parse().map_err(|_| MALFORMED);
+    }
+    let bytes = field.as_bytes();
+    if bytes.len() > MAX_NUMBER_LENGTH {
+        return Err(MALFORMED);
+    }
+    let mut buffer = [0u8; MAX_NUMBER_LENGTH];
+    for (out, &byte) in buffer.iter_mut().zip(bytes) {
+        *out = if byte == b'D' || byte == b'd' {
+            b'E'
+        } else {
+            byte
+        };
+    }
+    core::str::from_utf8(&buffer[..bytes.len()])
+        .ok()
+        .and_then(|text| text.parse().ok())
+        .ok_or(MALFORMED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const ICGEM: &str = "\
+generating_institute  example
+product_type          gravity_field
+modelname             test-field
+earth_gravity_constant  0.3986004415E+15
+radius                  0.6378136300E+07
+max_degree              3
+norm                    fully_normalized
+errors                  formal
+
+key    L    M    C                  S                  sigma C     sigma S
+end_of_head ==================================================================
+gfc    0    0    1.000000000000E+00  0.000000000000E+00 0.0 0.0
+gfc    2    0   -0.484165143790D-03  0.000000000000D+00 0.0 0.0
+gfc    2    1   -0.206615509074E-09  0.138441389137E-08 0.0 0.0
+gfc    2    2    0.243938357328E-05 -0.140027370385E-05 0.0 0.0
+gfc    3    0    0.957161207093E-06  0.000000000000E+00 0.0 0.0
+gfc    3    3    0.721072657057E-06  0.141435626958E-05 0.0 0.0
+";
+
+    const SHADR: &str = "\
+   1.7380000000000E+03,   4.9028001224453E+03,   0.0000000000000E+00,  660,  660,    1,   0.0000000000000E+00,   0.0000000000000E+00
+    2,    0,  -9.0881957503493E-05,   0.0000000000000E+00,   0.0E+00,   0.0E+00
+    2,    1,   1.2349374032001E-09,   2.8023893050853E-09,   0.0E+00,   0.0E+00
+    2,    2,   3.4673804245031E-05,   1.6451106911541E-09,   0.0E+00,   0.0E+00
+    3,    0,  -3.1960284283212E-06,   0.0000000000000E+00,   0.0E+00,   0.0E+00
+";
+
+    #[test]
+    fn test_parse_icgem() {
+        let mut c = [0.0; 10];
+        let mut s = [0.0; 10];
+        let header = parse_icgem(ICGEM, 3, &mut c, &mut s).unwrap();
+        assert_relative_eq!(header.mu.value(), 3.986004415e14);
+        assert_relative_eq!(header.radius.value(), 6_378_136.3);
+        assert_eq!(header.degree, 3);
+        assert_relative_eq!(c[0], 1.0);
+        assert_relative_eq!(c[coefficient_index(2, 0)], -0.484165143790e-3);
+        assert_relative_eq!(s[coefficient_index(2, 2)], -0.140027370385e-5);
+        assert_relative_eq!(s[coefficient_index(3, 3)], 0.141435626958e-5);
+        // Missing lines are zero
+        assert_eq!(c[coefficient_index(3, 1)], 0.0);
+
+        // C̄₂₀ corresponds to Earth's J2
+        let j2 = -c[coefficient_index(2, 0)] / normalization_factor(2, 0);
+        assert_relative_eq!(j2, 1.0826e-3, max_relative = 1e-4);
+    }
+
+    #[test]
+    fn test_icgem_truncates_to_requested_degree() {
+        let mut c = [0.0; 6];
+        let mut s = [0.0; 6];
+        let header = parse_icgem(ICGEM, 2, &mut c, &mut s).unwrap();
+        assert_eq!(header.degree, 2);
+        assert_relative_eq!(c[coefficient_index(2, 2)], 0.243938357328e-5);
+        assert!(parse_icgem(ICGEM, 3, &mut c, &mut s).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_icgem_unnormalized() {
+        let text = ICGEM.replace("fully_normalized", "unnormalized");
+        let mut c = [0.0; 10];
+        let mut s = [0.0; 10];
+        parse_icgem(&text, 3, &mut c, &mut s).unwrap();
+        assert_relative_eq!(
+            c[coefficient_index(2, 0)],
+            -0.484165143790e-3 * normalization_factor(2, 0)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_header_degree_stops_at_last_row() {
+        // Both headers promise more than the rows hold
+        let text = ICGEM.replace("max_degree              3", "max_degree              10");
+        let mut c = [0.0; 66];
+        let mut s = [0.0; 66];
+        assert_eq!(parse_icgem(&text, 10, &mut c, &mut s).unwrap().degree, 3);
+        assert_eq!(parse_shadr(SHADR, 10, &mut c, &mut s).unwrap().degree, 3);
+    }
+
+    #[test]
+    fn test_parse_shadr() {
+        let mut c = [0.0; 10];
+        let mut s = [0.0; 10];
+        let header = parse_shadr(SHADR, 3, &mut c, &mut s).unwrap();
+        assert_relative_eq!(header.mu.value(), 4.9028001224453e12);
+        assert_relative_eq!(header.radius.value(), 1_738_000.0);
+        assert_eq!(header.degree, 3);
+        assert_relative_eq!(c[0], 1.0);
+        assert_relative_eq!(c[coefficient_index(2, 2)], 3.4673804245031e-5);
+        assert_relative_eq!(s[coefficient_index(2, 1)], 2.8023893050853e-9);
+
+        // The lunar J2 from the normalized C̄₂₀
+        let j2 = -c[coefficient_index(2, 0)] / normalization_factor(2, 0);
+        assert_relative_eq!(j2, 2.0321e-4, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let table = "# n m C S\n2 0 -1.0826D-3 0.0\n\n2 2 1.57D-6 -9.0D-7 0 0\n";
+        let mut c = [0.0; 6];
+        let mut s = [0.0; 6];
+        let degree = parse_coefficient_table(table, 2, false, &mut c, &mut s).unwrap();
+        assert_eq!(degree, 2);
+        assert_relative_eq!(
+            c[coefficient_index(2, 0)],
+            -1.0826e-3 * normalization_factor(2, 0)
+        );
+        assert_relative_eq!(
+            s[coefficient_index(2, 2)],
+            -9.0e-7 * normalization_factor(2, 2)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_malformed_files() {
+        let mut c = [0.0; 10];
+        let mut s = [0.0; 10];
+        assert!(parse_icgem("radius 1.0\ngfc 2 0 1.0 0.0\n", 3, &mut c, &mut s).is_err());
+        assert!(parse_icgem("radius 1.0\nend_of_head\n", 3, &mut c, &mut s).is_err());
+        let bad_line = ICGEM.replace("gfc    3    3", "gfc    3    x");
+        assert!(parse_icgem(&bad_line, 3, &mut c, &mut s).is_err());
+        assert!(parse_shadr("", 3, &mut c, &mut s).is_err());
+        assert!(parse_coefficient_table("2 3 1.0 0.0", 3, true, &mut c, &mut s).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_load_from_path() {
+        // Unique per process, so concurrent test runs don't collide
+        let dir = std::env::temp_dir();
+        let name = std::format!("almagest-{}-test_load_from_path", std::process::id());
+        let icgem = dir.join(std::format!("{name}.gfc"));
+        let shadr = dir.join(std::format!("{name}.tab"));
+        std::fs::write(&icgem, ICGEM).unwrap();
+        std::fs::write(&shadr, SHADR).unwrap();
+
+        let earth = GravityModel::load(&icgem, 2);
+        let moon = GravityModel::load(&shadr, 10);
+        // Clean up before asserting, so a failure leaves nothing behind
+        std::fs::remove_file(&icgem).unwrap();
+        std::fs::remove_file(&shadr).unwrap();
+
+        let earth = earth.unwrap();
+        assert_eq!(earth.harmonics().degree(), 2);
+        assert_relative_eq!(earth.header.radius.value(), 6_378_136.3);
+
+        // The header claims degree 660, but the rows stop at 3
+        let moon = moon.unwrap();
+        assert_eq!(moon.header.degree, 3);
+        assert_eq!(moon.harmonics().degree(), 3);
+        assert_eq!(
+            moon.harmonics().coefficients(2, 2),
+            Some((3.4673804245031e-5, 1.6451106911541e-9))
+        );
+
+        // Both files are gone now
+        assert!(GravityModel::load(&icgem, 2).is_err());
+    }
+}
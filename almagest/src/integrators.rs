@@ -0,0 +1,1157 @@
+//! # Numerical Integrators
+//!
+//! Explicit Runge-Kutta methods for systems of first-order ordinary
+//! differential equations `dy/dt = f(t, y)`. This is the foundation for
+//! Cowell propagation — integrating the equations of motion directly, with
+//! whatever perturbing accelerations are needed — where the closed-form
+//! solutions in [`kepler`](crate::kepler) no longer apply.
+//!
+//! States are fixed-size arrays `[Real; N]`, so nothing here allocates. The
+//! right-hand side is any closure `Fn(Real, &[Real; N]) -> [Real; N]`.
+//!
+//! Three methods are provided behind the [`Integrator`] trait:
+//!
+//! - [`Rk4`] - classical fourth-order Runge-Kutta with a fixed step
+//! - [`DormandPrince54`] - adaptive fifth-order method with a fourth-order error estimate
+//! - [`RungeKuttaFehlberg78`] - adaptive seventh-order method with an eighth-order error estimate
+//!
+//! Every accepted [`Step`] carries the state and derivative at both ends, so
+//! the solution can be evaluated anywhere inside it with [`Step::interpolate`]
+//! (dense output) without taking extra steps. Only [`DormandPrince54`] has a
+//! native continuous extension; the other methods fall back to cubic Hermite
+//! interpolation, which is fourth-order accurate however high the order of the
+//! method. Where [`RungeKuttaFehlberg78`] accuracy matters at particular
+//! times, integrate to each of them instead of interpolating.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::integrators::{integrate, DormandPrince54, Tolerances};
+//!
+//! // Simple harmonic oscillator: x'' = -x
+//! let oscillator = |_t: f64, y: &[f64; 2]| [y[1], -y[0]];
+//! let integrator = DormandPrince54::new(Tolerances::new(1e-12, 1e-12));
+//!
+//! let mut halfway = [0.0; 2];
+//! let end = integrate(&integrator, &oscillator, 0.0, [1.0, 0.0], 10.0, 0.1, |step| {
+//!     if step.contains(5.0) {
+//!         halfway = step.interpolate(5.0);
+//!     }
+//! })
+//! .expect("Integrates");
+//!
+//! assert!((end[0] - 10.0_f64.cos()).abs() < 1e-9);
+//! assert!((halfway[0] - 5.0_f64.cos()).abs() < 1e-6);
+//! ```
+
+use core::ops::ControlFlow;
+
+use libm::{copysign, fabs, pow, sqrt};
+
+use crate::utils::Real;
+
+/// Largest factor by which an adaptive step may grow after an accepted step
+const MAX_GROWTH: Real = 5.0;
+
+/// Smallest factor by which an adaptive step may shrink after a rejected step
+const MIN_SHRINK: Real = 0.2;
+
+/// Safety factor applied to the optimal step size estimate
+const SAFETY: Real = 0.9;
+
+/// Rejected attempts allowed before an adaptive step gives up
+const MAX_REJECTIONS: usize = 64;
+
+/// Accepted steps allowed in a single call to [`integrate`]
+pub const MAX_STEPS: usize = 10_000_000;
+
+/// Error tolerances for adaptive step-size control.
+///
+/// A step is accepted when the root-mean-square of each component's error
+/// estimate, scaled by `absolute + relative × |yᵢ|`, does not exceed one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerances {
+    /// Error allowed regardless of the size of the state
+    pub absolute: Real,
+    /// Error allowed as a fraction of the size of the state
+    pub relative: Real,
+}
+
+impl Tolerances {
+    pub fn new(absolute: Real, relative: Real) -> Self {
+        Self { absolute, relative }
+    }
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Self::new(1e-9, 1e-9)
+    }
+}
+
+/// One accepted integration step from `t0` to `t1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step<const N: usize> {
+    /// Independent variable at the start of the step
+    pub t0: Real,
+    /// State at the start of the step
+    pub y0: [Real; N],
+    /// Derivative at the start of the step
+    pub dydt0: [Real; N],
+    /// Independent variable at the end of the step
+    pub t1: Real,
+    /// State at the end of the step
+    pub y1: [Real; N],
+    /// Derivative at the end of the step
+    pub dydt1: [Real; N],
+    /// Quartic term of the method's continuous extension, zero for methods
+    /// without one
+    pub dense: [Real; N],
+    /// Step size the integrator recommends for the next step
+    pub next_step: Real,
+}
+
+impl<const N: usize> Step<N> {
+    /// Signed size of the step, negative when integrating backward
+    pub fn size(&self) -> Real {
+        self.t1 - self.t0
+    }
+
+    /// Whether `t` lies within the step, inclusive of both ends
+    pub fn contains(&self, t: Real) -> bool {
+        let (lower, upper) = if self.t1 >= self.t0 {
+            (self.t0, self.t1)
+        } else {
+            (self.t1, self.t0)
+        };
+        t >= lower && t <= upper
+    }
+
+    /// Evaluate the solution at `t` within the step.
+    ///
+    /// Cubic Hermite interpolation on the state and derivative at both ends,
+    /// plus the [`dense`](Self::dense) term of the continuous extension. For
+    /// [`DormandPrince54`] the error is `O(h⁵)`, matching its error estimate;
+    /// for the other methods it is the `O(h⁴)` of the Hermite cubic alone.
+    /// Both endpoints are reproduced exactly. Values of `t` outside the step
+    /// extrapolate.
+    pub fn interpolate(&self, t: Real) -> [Real; N] {
+        let h = self.size();
+        if h == 0.0 {
+            return self.y0;
+        }
+        let s = (t - self.t0) / h;
+        let s1 = 1.0 - s;
+        core::array::from_fn(|i| {
+            let change = self.y1[i] - self.y0[i];
+            let start_slope = h * self.dydt0[i] - change;
+            let curvature = change - h * self.dydt1[i] - start_slope;
+            self.y0[i] + s * (change + s1 * (start_slope + s * (curvature + s1 * self.dense[i])))
+        })
+    }
+}
+
+/// A single-step method for `dy/dt = f(t, y)`.
+pub trait Integrator {
+    /// Advance from `(t, y)`, where `dydt = f(t, y)`, attempting a step of `h`.
+    ///
+    /// Fixed-step methods always take `h`. Adaptive methods shrink `h` until
+    /// the error estimate is within tolerance, so the returned step may be
+    /// shorter than requested; its [`Step::next_step`] suggests how large the
+    /// following step can be.
+    fn step<const N: usize, F>(
+        &self,
+        f: &F,
+        t: Real,
+        y: &[Real; N],
+        dydt: &[Real; N],
+        h: Real,
+    ) -> Result<Step<N>, &'static str>
+    where
+        F: Fn(Real, &[Real; N]) -> [Real; N];
+}
+
+/// Integrate `dy/dt = f(t, y)` from `(t0, y0)` to `t_end`.
+///
+/// Steps start at `initial_step` (its sign is ignored; the direction follows
+/// `t_end - t0`) and the final step is shortened to land exactly on `t_end`.
+/// `on_step` is called with every accepted step, in order, which is where
+/// dense output should be sampled. Returns the state at `t_end`.
+pub fn integrate<I, F, S, const N: usize>(
+    integrator: &I,
+    f: &F,
+    t0: Real,
+    y0: [Real; N],
+    t_end: Real,
+    initial_step: Real,
+    mut on_step: S,
+) -> Result<[Real; N], &'static str>
+where
+    I: Integrator,
+    F: Fn(Real, &[Real; N]) -> [Real; N],
+    S: FnMut(&Step<N>),
+{
+    let (_, y) = integrate_until(integrator, f, t0, y0, t_end, initial_step, |step| {
+        on_step(step);
+        ControlFlow::Continue(())
+    })?;
+    Ok(y)
+}
+
+/// Like [`integrate`], but `on_step` can end the integration early by
+/// returning [`ControlFlow::Break`].
+///
+/// Returns the independent variable and state where integration stopped:
+/// the end of the step that broke, or `t_end`.
+pub fn integrate_until<I, F, S, const N: usize>(
+    integrator: &I,
+    f: &F,
+    t0: Real,
+    y0: [Real; N],
+    t_end: Real,
+    initial_step: Real,
+    mut on_step: S,
+) -> Result<(Real, [Real; N]), &'static str>
+where
+    I: Integrator,
+    F: Fn(Real, &[Real; N]) -> [Real; N],
+    S: FnMut(&Step<N>) -> ControlFlow<()>,
+{
+    if !t0.is_finite() || !t_end.is_finite() {
+        return Err("Integration bounds must be finite");
+    }
+    if initial_step == 0.0 || !initial_step.is_finite() {
+        return Err("Initial step must be finite and non-zero");
+    }
+    if t_end == t0 {
+        return Ok((t0, y0));
+    }
+    let direction = if t_end > t0 { 1.0 } else { -1.0 };
+
+    let mut t = t0;
+    let mut y = y0;
+    let mut dydt = f(t, &y);
+    let mut h = direction * fabs(initial_step);
+    for _ in 0..MAX_STEPS {
+        let remaining = t_end - t;
+        let last = fabs(h) >= fabs(remaining);
+        if last {
+            h = remaining;
+        }
+        let mut step = integrator.step(f, t, &y, &dydt, h)?;
+        // Land exactly on the end point rather than within rounding of it
+        let finished =
+            last && fabs(t_end - step.t1) <= 4.0 * Real::EPSILON * fabs(t_end).max(fabs(t));
+        if finished {
+            step.t1 = t_end;
+        }
+        if on_step(&step).is_break() || finished {
+            return Ok((step.t1, step.y1));
+        }
+        if step.t1 == t {
+            return Err("Step size underflow");
+        }
+        t = step.t1;
+        y = step.y1;
+        dydt = step.dydt1;
+        h = step.next_step;
+    }
+    Err("Maximum number of integration steps exceeded")
+}
+
+/// Classical fourth-order Runge-Kutta with a fixed step size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rk4;
+
+impl Integrator for Rk4 {
+    fn step<const N: usize, F>(
+        &self,
+        f: &F,
+        t: Real,
+        y: &[Real; N],
+        dydt: &[Real; N],
+        h: Real,
+    ) -> Result<Step<N>, &'static str>
+    where
+        F: Fn(Real, &[Real; N]) -> [Real; N],
+    {
+        validate_step(h)?;
+        let k1 = dydt;
+        let k2 = f(t + 0.5 * h, &offset(y, h, &[(0.5, k1)]));
+        let k3 = f(t + 0.5 * h, &offset(y, h, &[(0.5, &k2)]));
+        let k4 = f(t + h, &offset(y, h, &[(1.0, &k3)]));
+        let y1 = offset(
+            y,
+            h,
+            &[
+                (1.0 / 6.0, k1),
+                (1.0 / 3.0, &k2),
+                (1.0 / 3.0, &k3),
+                (1.0 / 6.0, &k4),
+            ],
+        );
+        let t1 = t + h;
+        Ok(Step {
+            t0: t,
+            y0: *y,
+            dydt0: *dydt,
+            t1,
+            y1,
+            dydt1: f(t1, &y1),
+            dense: [0.0; N],
+            next_step: h,
+        })
+    }
+}
+
+/// Dormand-Prince 5(4): a seven-stage adaptive method that propagates the
+/// fifth-order solution and uses the embedded fourth-order one to control
+/// the step size.
+///
+/// The last stage is evaluated at the new state, so the end-of-step
+/// derivative needed for dense output comes for free, and the stages also
+/// give Dormand and Prince's fourth-order continuous extension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DormandPrince54 {
+    pub tolerances: Tolerances,
+    /// Upper bound on the magnitude of any step
+    pub max_step: Real,
+}
+
+impl DormandPrince54 {
+    pub fn new(tolerances: Tolerances) -> Self {
+        Self {
+            tolerances,
+            max_step: Real::INFINITY,
+        }
+    }
+}
+
+impl Default for DormandPrince54 {
+    fn default() -> Self {
+        Self::new(Tolerances::default())
+    }
+}
+
+impl Integrator for DormandPrince54 {
+    fn step<const N: usize, F>(
+        &self,
+        f: &F,
+        t: Real,
+        y: &[Real; N],
+        dydt: &[Real; N],
+        h: Real,
+    ) -> Result<Step<N>, &'static str>
+    where
+        F: Fn(Real, &[Real; N]) -> [Real; N],
+    {
+        adaptive_step(
+            &DORMAND_PRINCE_54,
+            self.tolerances,
+            self.max_step,
+            f,
+            t,
+            y,
+            dydt,
+            h,
+        )
+    }
+}
+
+/// Runge-Kutta-Fehlberg 7(8): a thirteen-stage adaptive method that
+/// propagates the seventh-order solution and uses the embedded eighth-order
+/// one to control the step size.
+///
+/// Well suited to long orbit propagations at tight tolerances, where its
+/// high order allows much larger steps than [`DormandPrince54`]. It has no
+/// continuous extension, so [`Step::interpolate`] is only fourth-order
+/// accurate within its long steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RungeKuttaFehlberg78 {
+    pub tolerances: Tolerances,
+    /// Upper bound on the magnitude of any step
+    pub max_step: Real,
+}
+
+impl RungeKuttaFehlberg78 {
+    pub fn new(tolerances: Tolerances) -> Self {
+        Self {
+            tolerances,
+            max_step: Real::INFINITY,
+        }
+    }
+}
+
+impl Default for RungeKuttaFehlberg78 {
+    fn default() -> Self {
+        Self::new(Tolerances::default())
+    }
+}
+
+impl Integrator for RungeKuttaFehlberg78 {
+    fn step<const N: usize, F>(
+        &self,
+        f: &F,
+        t: Real,
+        y: &[Real; N],
+        dydt: &[Real; N],
+        h: Real,
+    ) -> Result<Step<N>, &'static str>
+    where
+        F: Fn(Real, &[Real; N]) -> [Real; N],
+    {
+        adaptive_step(
+            &FEHLBERG_78,
+            self.tolerances,
+            self.max_step,
+            f,
+            t,
+            y,
+            dydt,
+            h,
+        )
+    }
+}
+
+/// Butcher tableau of an explicit embedded Runge-Kutta pair
+struct Tableau<const S: usize> {
+    c: [Real; S],
+    a: [[Real; S]; S],
+    /// Weights of the propagated solution
+    b: [Real; S],
+    /// Weights of the propagated solution minus those of the embedded one
+    e: [Real; S],
+    /// Order of the lower-order member of the pair
+    error_order: i32,
+    /// Whether the last stage is evaluated at the new state (first same as last)
+    fsal: bool,
+    /// Weights of the quartic term of the continuous extension, if any
+    dense: Option<[Real; S]>,
+}
+
+const DORMAND_PRINCE_54: Tableau<7> = Tableau {
+    c: [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0],
+    a: [
+        [0.0; 7],
+        [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0, 0.0],
+        [
+            19372.0 / 6561.0,
+            -25360.0 / 2187.0,
+            64448.0 / 6561.0,
+            -212.0 / 729.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            9017.0 / 3168.0,
+            -355.0 / 33.0,
+            46732.0 / 5247.0,
+            49.0 / 176.0,
+            -5103.0 / 18656.0,
+            0.0,
+            0.0,
+        ],
+        [
+            35.0 / 384.0,
+            0.0,
+            500.0 / 1113.0,
+            125.0 / 192.0,
+            -2187.0 / 6784.0,
+            11.0 / 84.0,
+            0.0,
+        ],
+    ],
+    b: [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+        0.0,
+    ],
+    e: [
+        35.0 / 384.0 - 5179.0 / 57600.0,
+        0.0,
+        500.0 / 1113.0 - 7571.0 / 16695.0,
+        125.0 / 192.0 - 393.0 / 640.0,
+        -2187.0 / 6784.0 + 92097.0 / 339200.0,
+        11.0 / 84.0 - 187.0 / 2100.0,
+        -1.0 / 40.0,
+    ],
+    error_order: 4,
+    fsal: true,
+    // Hairer, Nørsett & Wanner, Solving ODEs I, §II.6
+    dense: Some([
+        -12715105075.0 / 11282082432.0,
+        0.0,
+        87487479700.0 / 32700410799.0,
+        -10690763975.0 / 1880347072.0,
+        701980252875.0 / 199316789632.0,
+        -1453857185.0 / 822651844.0,
+        69997945.0 / 29380423.0,
+    ]),
+};
+
+/// Fehlberg's coefficients from NASA TR R-287 (1968)
+const FEHLBERG_78: Tableau<13> = Tableau {
+    c: [
+        0.0,
+        2.0 / 27.0,
+        1.0 / 9.0,
+        1.0 / 6.0,
+        5.0 / 12.0,
+        1.0 / 2.0,
+        5.0 / 6.0,
+        1.0 / 6.0,
+        2.0 / 3.0,
+        1.0 / 3.0,
+        1.0,
+        0.0,
+        1.0,
+    ],
+    a: [
+        [0.0; 13],
+        [
+            2.0 / 27.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            1.0 / 36.0,
+            1.0 / 12.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            1.0 / 24.0,
+            0.0,
+            1.0 / 8.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            5.0 / 12.0,
+            0.0,
+            -25.0 / 16.0,
+            25.0 / 16.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            1.0 / 20.0,
+            0.0,
+            0.0,
+            1.0 / 4.0,
+            1.0 / 5.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            -25.0 / 108.0,
+            0.0,
+            0.0,
+            125.0 / 108.0,
+            -65.0 / 27.0,
+            125.0 / 54.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            31.0 / 300.0,
+            0.0,
+            0.0,
+            0.0,
+            61.0 / 225.0,
+            -2.0 / 9.0,
+            13.0 / 900.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            2.0,
+            0.0,
+            0.0,
+            -53.0 / 6.0,
+            704.0 / 45.0,
+            -107.0 / 9.0,
+            67.0 / 90.0,
+            3.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            -91.0 / 108.0,
+            0.0,
+            0.0,
+            23.0 / 108.0,
+            -976.0 / 135.0,
+            311.0 / 54.0,
+            -19.0 / 60.0,
+            17.0 / 6.0,
+            -1.0 / 12.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            2383.0 / 4100.0,
+            0.0,
+            0.0,
+            -341.0 / 164.0,
+            4496.0 / 1025.0,
+            -301.0 / 82.0,
+            2133.0 / 4100.0,
+            45.0 / 82.0,
+            45.0 / 164.0,
+            18.0 / 41.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            3.0 / 205.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            -6.0 / 41.0,
+            -3.0 / 205.0,
+            -3.0 / 41.0,
+            3.0 / 41.0,
+            6.0 / 41.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+        [
+            -1777.0 / 4100.0,
+            0.0,
+            0.0,
+            -341.0 / 164.0,
+            4496.0 / 1025.0,
+            -289.0 / 82.0,
+            2193.0 / 4100.0,
+            51.0 / 82.0,
+            33.0 / 164.0,
+            12.0 / 41.0,
+            0.0,
+            1.0,
+            0.0,
+        ],
+    ],
+    b: [
+        41.0 / 840.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        34.0 / 105.0,
+        9.0 / 35.0,
+        9.0 / 35.0,
+        9.0 / 280.0,
+        9.0 / 280.0,
+        41.0 / 840.0,
+        0.0,
+        0.0,
+    ],
+    e: [
+        41.0 / 840.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        41.0 / 840.0,
+        -41.0 / 840.0,
+        -41.0 / 840.0,
+    ],
+    error_order: 7,
+    fsal: false,
+    dense: None,
+};
+
+/// Attempt steps with an embedded pair until one meets the tolerances
+#[allow(clippy::too_many_arguments)]
+fn adaptive_step<const S: usize, const N: usize, F>(
+    tableau: &Tableau<S>,
+    tolerances: Tolerances,
+    max_step: Real,
+    f: &F,
+    t: Real,
+    y: &[Real; N],
+    dydt: &[Real; N],
+    h: Real,
+) -> Result<Step<N>, &'static str>
+where
+    F: Fn(Real, &[Real; N]) -> [Real; N],
+{
+    validate_step(h)?;
+    if tolerances.absolute < 0.0
+        || tolerances.relative < 0.0
+        || tolerances.absolute + tolerances.relative <= 0.0
+    {
+        return Err("Tolerances must be non-negative and not both zero");
+    }
+    if max_step.is_nan() || max_step <= 0.0 {
+        return Err("Maximum step must be positive");
+    }
+
+    let exponent = -1.0 / (tableau.error_order as Real + 1.0);
+    let mut h = if fabs(h) > max_step {
+        copysign(max_step, h)
+    } else {
+        h
+    };
+    for _ in 0..MAX_REJECTIONS {
+        let t1 = t + h;
+        if t1 == t {
+            return Err("Step size underflow");
+        }
+        let (y1, error, k) = embedded_step(tableau, f, t, y, dydt, h);
+        let norm = error_norm(tolerances, y, &y1, &error);
+        if !norm.is_finite() {
+            h *= MIN_SHRINK;
+            continue;
+        }
+        let factor = if norm == 0.0 {
+            MAX_GROWTH
+        } else {
+            (SAFETY * pow(norm, exponent)).clamp(MIN_SHRINK, MAX_GROWTH)
+        };
+        if norm <= 1.0 {
+            let next = h * factor;
+            return Ok(Step {
+                t0: t,
+                y0: *y,
+                dydt0: *dydt,
+                t1,
+                y1,
+                dydt1: if tableau.fsal { k[S - 1] } else { f(t1, &y1) },
+                dense: match tableau.dense {
+                    Some(d) => {
+                        core::array::from_fn(|i| h * (0..S).map(|j| d[j] * k[j][i]).sum::<Real>())
+                    }
+                    None => [0.0; N],
+                },
+                next_step: if fabs(next) > max_step {
+                    copysign(max_step, h)
+                } else {
+                    next
+                },
+            });
+        }
+        h *= factor.min(1.0);
+    }
+    Err("Step rejected too many times")
+}
+
+/// Evaluate every stage of the tableau, returning the new state, the error
+/// estimate and the stage derivatives
+fn embedded_step<const S: usize, const N: usize, F>(
+    tableau: &Tableau<S>,
+    f: &F,
+    t: Real,
+    y: &[Real; N],
+    dydt: &[Real; N],
+    h: Real,
+) -> ([Real; N], [Real; N], [[Real; N]; S])
+where
+    F: Fn(Real, &[Real; N]) -> [Real; N],
+{
+    let mut k = [[0.0; N]; S];
+    k[0] = *dydt;
+    for stage in 1..S {
+        let state: [Real; N] = core::array::from_fn(|i| {
+            y[i] + h
+                * (0..stage)
+                    .map(|j| tableau.a[stage][j] * k[j][i])
+                    .sum::<Real>()
+        });
+        k[stage] = f(t + tableau.c[stage] * h, &state);
+    }
+    let y1 =
+        core::array::from_fn(|i| y[i] + h * (0..S).map(|j| tableau.b[j] * k[j][i]).sum::<Real>());
+    let error = core::array::from_fn(|i| h * (0..S).map(|j| tableau.e[j] * k[j][i]).sum::<Real>());
+    (y1, error, k)
+}
+
+/// Root-mean-square of the error scaled by the tolerance of each component
+fn error_norm<const N: usize>(
+    tolerances: Tolerances,
+    y0: &[Real; N],
+    y1: &[Real; N],
+    error: &[Real; N],
+) -> Real {
+    if N == 0 {
+        return 0.0;
+    }
+    let sum: Real = (0..N)
+        .map(|i| {
+            let scale = tolerances.absolute + tolerances.relative * fabs(y0[i]).max(fabs(y1[i]));
+            let ratio = error[i] / scale;
+            ratio * ratio
+        })
+        .sum();
+    sqrt(sum / N as Real)
+}
+
+/// `y + h Σ wⱼ kⱼ`
+fn offset<const N: usize>(y: &[Real; N], h: Real, terms: &[(Real, &[Real; N])]) -> [Real; N] {
+    core::array::from_fn(|i| y[i] + h * terms.iter().map(|(w, k)| w * k[i]).sum::<Real>())
+}
+
+fn validate_step(h: Real) -> Result<(), &'static str> {
+    if h == 0.0 || !h.is_finite() {
+        return Err("Step size must be finite and non-zero");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::kepler::StateVector;
+    use crate::kepler::propagation::propagate_universal;
+    use crate::utils::{Seconds, Vector3};
+    use approx::assert_relative_eq;
+    use core::f64::consts::TAU;
+    use libm::{cos, exp, sin};
+
+    const MU: Real = 3.986004418e14;
+
+    fn two_body(_t: Real, y: &[Real; 6]) -> [Real; 6] {
+        let r = sqrt(y[0] * y[0] + y[1] * y[1] + y[2] * y[2]);
+        let k = -MU / (r * r * r);
+        [y[3], y[4], y[5], k * y[0], k * y[1], k * y[2]]
+    }
+
+    fn oscillator(_t: Real, y: &[Real; 2]) -> [Real; 2] {
+        [y[1], -y[0]]
+    }
+
+    /// An eccentric, inclined LEO-to-MEO orbit and its period
+    fn eccentric_orbit() -> ([Real; 6], Real) {
+        let r = 7_000_000.0;
+        let v = 1.2 * sqrt(MU / r);
+        let a = 1.0 / (2.0 / r - v * v / MU);
+        let y = [r, 0.0, 0.0, 0.0, v * cos(0.5), v * sin(0.5)];
+        (y, TAU * sqrt(a * a * a / MU))
+    }
+
+    fn count_steps<I: Integrator>(integrator: &I, t_end: Real) -> ([Real; 6], usize) {
+        let (y0, _) = eccentric_orbit();
+        let mut steps = 0;
+        let y = integrate(integrator, &two_body, 0.0, y0, t_end, 10.0, |_| steps += 1)
+            .expect("Integrates");
+        (y, steps)
+    }
+
+    #[test]
+    fn test_tableau_rows_sum_to_nodes() {
+        for (row, c) in DORMAND_PRINCE_54.a.iter().zip(DORMAND_PRINCE_54.c) {
+            assert_relative_eq!(row.iter().sum::<Real>(), c, epsilon = 1e-14);
+        }
+        for (row, c) in FEHLBERG_78.a.iter().zip(FEHLBERG_78.c) {
+            assert_relative_eq!(row.iter().sum::<Real>(), c, epsilon = 1e-14);
+        }
+        assert_relative_eq!(
+            DORMAND_PRINCE_54.b.iter().sum::<Real>(),
+            1.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(FEHLBERG_78.b.iter().sum::<Real>(), 1.0, epsilon = 1e-15);
+        assert_relative_eq!(
+            DORMAND_PRINCE_54.e.iter().sum::<Real>(),
+            0.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(FEHLBERG_78.e.iter().sum::<Real>(), 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_rk4_is_fourth_order() {
+        let decay = |_t: Real, y: &[Real; 1]| [-y[0]];
+        let error = |h: Real| {
+            let y = integrate(&Rk4, &decay, 0.0, [1.0], 2.0, h, |_| {}).expect("Integrates");
+            fabs(y[0] - exp(-2.0))
+        };
+        let ratio = error(0.05) / error(0.025);
+        assert_relative_eq!(ratio, 16.0, epsilon = 1.0);
+    }
+
+    #[test]
+    fn test_rk4_takes_fixed_steps() {
+        let mut sizes = [0.0; 4];
+        let mut count = 0;
+        integrate(&Rk4, &oscillator, 0.0, [1.0, 0.0], 1.0, 0.3, |step| {
+            sizes[count] = step.size();
+            count += 1;
+        })
+        .expect("Integrates");
+        assert_eq!(count, 4);
+        assert_relative_eq!(sizes[0], 0.3);
+        assert_relative_eq!(sizes[2], 0.3);
+        // The last step is shortened to land on the end point
+        assert_relative_eq!(sizes[3], 0.1, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_adaptive_oscillator() {
+        let tolerances = Tolerances::new(1e-12, 1e-12);
+        let expected = [cos(20.0), -sin(20.0)];
+
+        let dp = integrate(
+            &DormandPrince54::new(tolerances),
+            &oscillator,
+            0.0,
+            [1.0, 0.0],
+            20.0,
+            0.01,
+            |_| {},
+        )
+        .expect("Integrates");
+        assert_relative_eq!(dp[0], expected[0], epsilon = 1e-9);
+        assert_relative_eq!(dp[1], expected[1], epsilon = 1e-9);
+
+        let rkf = integrate(
+            &RungeKuttaFehlberg78::new(tolerances),
+            &oscillator,
+            0.0,
+            [1.0, 0.0],
+            20.0,
+            0.01,
+            |_| {},
+        )
+        .expect("Integrates");
+        assert_relative_eq!(rkf[0], expected[0], epsilon = 1e-9);
+        assert_relative_eq!(rkf[1], expected[1], epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_cowell_matches_kepler() {
+        let (y0, period) = eccentric_orbit();
+        let t_end = 0.7 * period;
+        let state = StateVector::new(
+            Vector3::new(y0[0], y0[1], y0[2]),
+            Vector3::new(y0[3], y0[4], y0[5]),
+        );
+        let expected = propagate_universal(&state, EARTH.mu, Seconds(t_end)).expect("Converges");
+        let tolerances = Tolerances::new(1e-6, 1e-13);
+
+        let (dp, _) = count_steps(&DormandPrince54::new(tolerances), t_end);
+        let (rkf, _) = count_steps(&RungeKuttaFehlberg78::new(tolerances), t_end);
+        for y in [dp, rkf] {
+            let position = Vector3::new(y[0], y[1], y[2]);
+            let velocity = Vector3::new(y[3], y[4], y[5]);
+            assert!((position - expected.position).norm() < 1e-2);
+            assert!((velocity - expected.velocity).norm() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_higher_order_takes_fewer_steps() {
+        let (_, period) = eccentric_orbit();
+        let tolerances = Tolerances::new(1e-6, 1e-12);
+        let (_, dp) = count_steps(&DormandPrince54::new(tolerances), period);
+        let (_, rkf) = count_steps(&RungeKuttaFehlberg78::new(tolerances), period);
+        assert!(rkf < dp);
+    }
+
+    #[test]
+    fn test_tighter_tolerance_takes_more_steps() {
+        let (_, period) = eccentric_orbit();
+        let (_, loose) = count_steps(&DormandPrince54::new(Tolerances::new(1e-3, 1e-8)), period);
+        let (_, tight) = count_steps(&DormandPrince54::new(Tolerances::new(1e-6, 1e-12)), period);
+        assert!(tight > loose);
+    }
+
+    #[test]
+    fn test_max_step_is_respected() {
+        let (y0, period) = eccentric_orbit();
+        let integrator = RungeKuttaFehlberg78 {
+            max_step: 30.0,
+            ..Default::default()
+        };
+        let mut largest: Real = 0.0;
+        integrate(&integrator, &two_body, 0.0, y0, period, 10.0, |step| {
+            largest = largest.max(fabs(step.size()));
+        })
+        .expect("Integrates");
+        assert!(largest <= 30.0);
+    }
+
+    #[test]
+    fn test_dense_output() {
+        let integrator = DormandPrince54::new(Tolerances::new(1e-12, 1e-12));
+        let mut worst: Real = 0.0;
+        let mut samples = 0;
+        integrate(
+            &integrator,
+            &oscillator,
+            0.0,
+            [1.0, 0.0],
+            10.0,
+            0.1,
+            |step| {
+                let t = 0.5 * (step.t0 + step.t1);
+                let y = step.interpolate(t);
+                worst = worst.max(fabs(y[0] - cos(t))).max(fabs(y[1] + sin(t)));
+                samples += 1;
+
+                // Endpoints are reproduced exactly
+                assert_eq!(step.interpolate(step.t0), step.y0);
+                assert!(step.contains(t));
+            },
+        )
+        .expect("Integrates");
+        assert!(samples > 1);
+        assert!(worst < 1e-6);
+    }
+
+    #[test]
+    fn test_dense_output_order() {
+        // Error at an interior point of a single step, as the step halves
+        let interior_error = |integrator: &dyn Fn(Real) -> Step<2>, h: Real| {
+            let step = integrator(h);
+            let t = 0.4 * h;
+            let y = step.interpolate(t);
+            fabs(y[0] - cos(t)).max(fabs(y[1] + sin(t)))
+        };
+        let ratio = |integrator: &dyn Fn(Real) -> Step<2>| {
+            interior_error(integrator, 0.2) / interior_error(integrator, 0.1)
+        };
+        let loose = Tolerances::new(1.0, 1.0);
+        let y0 = [1.0, 0.0];
+        let dydt0 = oscillator(0.0, &y0);
+
+        // The continuous extension is fifth order in the step...
+        let dormand_prince = |h: Real| {
+            DormandPrince54::new(loose)
+                .step(&oscillator, 0.0, &y0, &dydt0, h)
+                .expect("Steps")
+        };
+        assert_relative_eq!(ratio(&dormand_prince), 32.0, max_relative = 0.1);
+
+        // ...where the Hermite fallback is only fourth
+        let fehlberg = |h: Real| {
+            RungeKuttaFehlberg78::new(loose)
+                .step(&oscillator, 0.0, &y0, &dydt0, h)
+                .expect("Steps")
+        };
+        assert_relative_eq!(ratio(&fehlberg), 16.0, max_relative = 0.1);
+    }
+
+    #[test]
+    fn test_backward_integration_returns_to_start() {
+        let (y0, period) = eccentric_orbit();
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-8, 1e-14));
+        let forward = integrate(&integrator, &two_body, 0.0, y0, 0.3 * period, 10.0, |_| {})
+            .expect("Integrates");
+        let mut backward_steps = true;
+        let back = integrate(
+            &integrator,
+            &two_body,
+            0.3 * period,
+            forward,
+            0.0,
+            10.0,
+            |step| {
+                backward_steps &= step.size() < 0.0;
+            },
+        )
+        .expect("Integrates");
+        assert!(backward_steps);
+        for i in 0..3 {
+            assert_relative_eq!(back[i], y0[i], epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_integrate_until_stops_early() {
+        let (t, y) = integrate_until(&Rk4, &oscillator, 0.0, [1.0, 0.0], 10.0, 0.25, |step| {
+            if step.t1 >= 1.0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .expect("Integrates");
+        assert_relative_eq!(t, 1.0);
+        assert_relative_eq!(y[0], libm::cos(1.0), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let y = [1.0, 0.0];
+        assert!(integrate(&Rk4, &oscillator, 0.0, y, 1.0, 0.0, |_| {}).is_err());
+        assert!(integrate(&Rk4, &oscillator, 0.0, y, Real::NAN, 0.1, |_| {}).is_err());
+        let bad = DormandPrince54::new(Tolerances::new(0.0, 0.0));
+        assert!(integrate(&bad, &oscillator, 0.0, y, 1.0, 0.1, |_| {}).is_err());
+        assert_eq!(
+            integrate(&Rk4, &oscillator, 2.0, y, 2.0, 0.1, |_| {}).expect("Nothing to do"),
+            y
+        );
+    }
+}
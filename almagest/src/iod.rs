@@ -0,0 +1,571 @@
+//! # Initial Orbit Determination
+//!
+//! Classical methods for producing a first element set from a handful of
+//! observations, with no prior knowledge of the orbit. The result is typically
+//! refined afterwards by differential correction against many more
+//! observations, but these methods need no initial guess.
+//!
+//! | Method | Input | Best suited to |
+//! |--------|-------|----------------|
+//! | [`gibbs`] | Three position vectors | Widely spaced observations (> ~5° apart) |
+//! | [`herrick_gibbs`] | Three timed position vectors | Closely spaced observations (< ~5° apart) |
+//! | [`gauss`] | Three timed angles-only observations from known sites | Optical tracking |
+//!
+//! Every method returns [`KeplerianElements`] at the time of the middle
+//! observation, with positions relative to the center of the central body.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::iod::gibbs;
+//! use almagest::utils::Vector3;
+//!
+//! // Curtis, "Orbital Mechanics for Engineering Students", Example 5.1
+//! let positions = [
+//!     Vector3::new(-294_320.0, 4_265_100.0, 5_986_700.0),
+//!     Vector3::new(-1_365_500.0, 3_637_600.0, 6_346_800.0),
+//!     Vector3::new(-2_940_300.0, 2_473_700.0, 6_555_800.0),
+//! ];
+//! let elements = gibbs(&positions, EARTH.mu).expect("Coplanar observations");
+//! println!("Eccentricity: {}", elements.eccentricity().value());
+//! ```
+
+use libm::{asin, fabs, pow, sqrt};
+
+use crate::kepler::propagation::propagate_universal;
+use crate::kepler::{KeplerianElements, StateVector};
+use crate::utils::{MetersCubedPerSecondSquared, Radians, Real, Seconds, Vector3};
+
+/// Largest angle, in radians, between the first position and the plane of the
+/// other two before the three are considered non-coplanar (about 2°)
+const COPLANARITY_TOLERANCE: Real = 0.035;
+/// Relative change in slant range at which Gauss's iteration stops
+const RANGE_TOLERANCE: Real = 1e-10;
+/// Refinement steps allowed for Gauss's method
+const MAX_ITERATIONS: usize = 100;
+/// Samples used to bracket the roots of Gauss's eighth-order polynomial
+const ROOT_SEARCH_SAMPLES: usize = 2_000;
+
+/// A line-of-sight observation from a known site, with no range information.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnglesObservation {
+    /// Time of the observation
+    pub time: Seconds,
+    /// Unit vector from the site toward the object, in the inertial frame
+    pub line_of_sight: Vector3,
+    /// Position of the observing site relative to the central body, in meters
+    pub site: Vector3,
+}
+
+impl AnglesObservation {
+    /// Build an observation from topocentric right ascension and declination.
+    pub fn from_right_ascension_declination(
+        time: Seconds,
+        right_ascension: Radians,
+        declination: Radians,
+        site: Vector3,
+    ) -> Self {
+        let (sin_a, cos_a) = libm::sincos(right_ascension.value());
+        let (sin_d, cos_d) = libm::sincos(declination.value());
+        AnglesObservation {
+            time,
+            line_of_sight: Vector3::new(cos_d * cos_a, cos_d * sin_a, sin_d),
+            site,
+        }
+    }
+}
+
+/// Gibbs's method: an orbit from three coplanar position vectors.
+///
+/// Purely geometric, so the observation times are not needed. Accuracy
+/// degrades as the positions get closer together; prefer
+/// [`herrick_gibbs`] when they are within a few degrees of each other.
+///
+/// # Returns
+/// Elements at the second position, or an error if the positions are not
+/// coplanar or do not define a conic about the central body.
+pub fn gibbs(
+    positions: &[Vector3; 3],
+    mu: MetersCubedPerSecondSquared,
+) -> Result<KeplerianElements, &'static str> {
+    validate_mu(mu)?;
+    let [r1_vec, r2_vec, r3_vec] = *positions;
+    let (r1, r2, r3) = (r1_vec.norm(), r2_vec.norm(), r3_vec.norm());
+    check_coplanar(positions)?;
+
+    let n = r2_vec.cross(&r3_vec) * r1 + r3_vec.cross(&r1_vec) * r2 + r1_vec.cross(&r2_vec) * r3;
+    let d = r1_vec.cross(&r2_vec) + r2_vec.cross(&r3_vec) + r3_vec.cross(&r1_vec);
+    let s = r1_vec * (r2 - r3) + r2_vec * (r3 - r1) + r3_vec * (r1 - r2);
+
+    let nd = n.dot(&d);
+    if nd <= 0.0 || !nd.is_finite() {
+        return Err("Positions do not define an orbit about the central body");
+    }
+    let v2 = (d.cross(&r2_vec) / r2 + s) * sqrt(mu.value() / nd);
+
+    KeplerianElements::from_state_vector(&StateVector::new(r2_vec, v2), mu)
+}
+
+/// Herrick-Gibbs method: an orbit from three closely spaced, timed positions.
+///
+/// Approximates the velocity at the middle position with a Taylor series in
+/// time, which remains accurate where [`gibbs`] loses precision.
+///
+/// # Returns
+/// Elements at the second position, or an error if the observations are not
+/// coplanar or not in strictly increasing time order.
+pub fn herrick_gibbs(
+    positions: &[Vector3; 3],
+    times: &[Seconds; 3],
+    mu: MetersCubedPerSecondSquared,
+) -> Result<KeplerianElements, &'static str> {
+    validate_mu(mu)?;
+    check_coplanar(positions)?;
+    let [r1_vec, r2_vec, r3_vec] = *positions;
+    let [t1, t2, t3] = times.map(|t| t.value());
+    let (dt21, dt32, dt31) = (t2 - t1, t3 - t2, t3 - t1);
+    if dt21 <= 0.0 || dt32 <= 0.0 {
+        return Err("Observations must be in increasing time order");
+    }
+
+    let mu_12 = mu.value() / 12.0;
+    let term = |r: &Vector3| mu_12 / pow(r.norm(), 3.0);
+    let v2 = r1_vec * (-dt32 * (1.0 / (dt21 * dt31) + term(&r1_vec)))
+        + r2_vec * ((dt32 - dt21) * (1.0 / (dt21 * dt32) + term(&r2_vec)))
+        + r3_vec * (dt21 * (1.0 / (dt32 * dt31) + term(&r3_vec)));
+
+    KeplerianElements::from_state_vector(&StateVector::new(r2_vec, v2), mu)
+}
+
+/// Gauss's method: an orbit from three angles-only observations.
+///
+/// Solves the classic eighth-order polynomial for the middle radius, then
+/// refines the slant ranges by iterating with exact Lagrange coefficients
+/// from the universal-variable propagator (Curtis, Algorithms 5.5 and 5.6).
+/// Observations should span no more than a fraction of an orbit.
+///
+/// # Returns
+/// Elements at the time of the second observation, or an error if the
+/// geometry is degenerate or no physically meaningful root exists.
+pub fn gauss(
+    observations: &[AnglesObservation; 3],
+    mu: MetersCubedPerSecondSquared,
+) -> Result<KeplerianElements, &'static str> {
+    validate_mu(mu)?;
+    let [o1, o2, o3] = observations;
+    let tau1 = o1.time.value() - o2.time.value();
+    let tau3 = o3.time.value() - o2.time.value();
+    let tau = tau3 - tau1;
+    if tau1 >= 0.0 || tau3 <= 0.0 {
+        return Err("Observations must be in increasing time order");
+    }
+
+    let (rho1_hat, rho2_hat, rho3_hat) = (o1.line_of_sight, o2.line_of_sight, o3.line_of_sight);
+    let (site1, site2, site3) = (o1.site, o2.site, o3.site);
+    let p1 = rho2_hat.cross(&rho3_hat);
+    let p2 = rho1_hat.cross(&rho3_hat);
+    let p3 = rho1_hat.cross(&rho2_hat);
+    let d0 = rho1_hat.dot(&p1);
+    if fabs(d0) < 1e-15 {
+        return Err("Lines of sight are coplanar; Gauss's method is singular");
+    }
+    let d = [
+        [site1.dot(&p1), site1.dot(&p2), site1.dot(&p3)],
+        [site2.dot(&p1), site2.dot(&p2), site2.dot(&p3)],
+        [site3.dot(&p1), site3.dot(&p2), site3.dot(&p3)],
+    ];
+
+    let mu = mu.value();
+    let a = (-d[0][1] * tau3 / tau + d[1][1] + d[2][1] * tau1 / tau) / d0;
+    let b = (d[0][1] * (tau3 * tau3 - tau * tau) * tau3 / tau
+        + d[2][1] * (tau * tau - tau1 * tau1) * tau1 / tau)
+        / (6.0 * d0);
+    let e = site2.dot(&rho2_hat);
+    let r2_site = site2.dot(&site2);
+
+    let poly_a = -(a * a + 2.0 * a * e + r2_site);
+    let poly_b = -2.0 * mu * b * (a + e);
+    let poly_c = -mu * mu * b * b;
+
+    // Lagrange-series estimate of the slant ranges for a given middle radius
+    let ranges = |r2: Real| {
+        let r2_cubed = r2 * r2 * r2;
+        let rho1 = ((6.0 * (d[2][0] * tau1 / tau3 + d[1][0] * tau / tau3) * r2_cubed
+            + mu * d[2][0] * (tau * tau - tau1 * tau1) * tau1 / tau3)
+            / (6.0 * r2_cubed + mu * (tau * tau - tau3 * tau3))
+            - d[0][0])
+            / d0;
+        let rho2 = a + mu * b / r2_cubed;
+        let rho3 = ((6.0 * (d[0][2] * tau3 / tau1 - d[1][2] * tau / tau1) * r2_cubed
+            + mu * d[0][2] * (tau * tau - tau3 * tau3) * tau3 / tau1)
+            / (6.0 * r2_cubed + mu * (tau * tau - tau1 * tau1))
+            - d[2][2])
+            / d0;
+        [rho1, rho2, rho3]
+    };
+
+    let polynomial = |x: Real| {
+        let x3 = x * x * x;
+        let x6 = x3 * x3;
+        x6 * x * x + poly_a * x6 + poly_b * x3 + poly_c
+    };
+    let r2 = smallest_valid_root(polynomial, (poly_a, poly_b, poly_c), |r| {
+        ranges(r).iter().all(|rho| *rho > 0.0)
+    })
+    .ok_or("No physically meaningful solution for the middle radius")?;
+
+    let r2_cubed = r2 * r2 * r2;
+    let mut rho = ranges(r2);
+    let mut f1 = 1.0 - mu * tau1 * tau1 / (2.0 * r2_cubed);
+    let mut g1 = tau1 - mu * tau1 * tau1 * tau1 / (6.0 * r2_cubed);
+    let mut f3 = 1.0 - mu * tau3 * tau3 / (2.0 * r2_cubed);
+    let mut g3 = tau3 - mu * tau3 * tau3 * tau3 / (6.0 * r2_cubed);
+
+    let positions = |rho: &[Real; 3]| {
+        (
+            site1 + rho1_hat * rho[0],
+            site2 + rho2_hat * rho[1],
+            site3 + rho3_hat * rho[2],
+        )
+    };
+    let velocity = |r1: Vector3, r3: Vector3, f1: Real, g1: Real, f3: Real, g3: Real| {
+        (r1 * -f3 + r3 * f1) / (f1 * g3 - f3 * g1)
+    };
+
+    let (mut r1_vec, mut r2_vec, mut r3_vec) = positions(&rho);
+    let mut v2 = velocity(r1_vec, r3_vec, f1, g1, f3, g3);
+
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+        let state = StateVector::new(r2_vec, v2);
+        let (Some((f1_exact, g1_exact)), Some((f3_exact, g3_exact))) = (
+            lagrange_coefficients(&state, tau1, mu),
+            lagrange_coefficients(&state, tau3, mu),
+        ) else {
+            break;
+        };
+        // Averaging with the previous estimate damps oscillation
+        f1 = (f1 + f1_exact) / 2.0;
+        g1 = (g1 + g1_exact) / 2.0;
+        f3 = (f3 + f3_exact) / 2.0;
+        g3 = (g3 + g3_exact) / 2.0;
+
+        let det = f1 * g3 - f3 * g1;
+        let c1 = g3 / det;
+        let c3 = -g1 / det;
+        let next = [
+            (-d[0][0] + d[1][0] / c1 - c3 / c1 * d[2][0]) / d0,
+            (-c1 * d[0][1] + d[1][1] - c3 * d[2][1]) / d0,
+            (-c1 / c3 * d[0][2] + d[1][2] / c3 - d[2][2]) / d0,
+        ];
+        let change = (0..3)
+            .map(|k| fabs(next[k] - rho[k]) / fabs(next[k]))
+            .fold(0.0, Real::max);
+
+        rho = next;
+        (r1_vec, r2_vec, r3_vec) = positions(&rho);
+        v2 = velocity(r1_vec, r3_vec, f1, g1, f3, g3);
+        if change < RANGE_TOLERANCE {
+            converged = true;
+            break;
+        }
+    }
+
+    if !(converged && v2.norm().is_finite() && r2_vec.norm().is_finite()) {
+        return Err("Gauss's method failed to converge");
+    }
+    KeplerianElements::from_state_vector(
+        &StateVector::new(r2_vec, v2),
+        MetersCubedPerSecondSquared(mu),
+    )
+}
+
+/// Exact f and g such that r(t + dt) = f·r + g·v
+fn lagrange_coefficients(state: &StateVector, dt: Real, mu: Real) -> Option<(Real, Real)> {
+    let later = propagate_universal(state, MetersCubedPerSecondSquared(mu), Seconds(dt))
+        .ok()?
+        .position;
+    let (r, v) = (state.position, state.velocity);
+    let (rr, rv, vv) = (r.dot(&r), r.dot(&v), v.dot(&v));
+    let (pr, pv) = (later.dot(&r), later.dot(&v));
+    let det = rr * vv - rv * rv;
+    if det == 0.0 {
+        return None;
+    }
+    Some(((pr * vv - pv * rv) / det, (pv * rr - pr * rv) / det))
+}
+
+/// Smallest positive root of `x⁸ + a·x⁶ + b·x³ + c` that passes `valid`
+fn smallest_valid_root(
+    polynomial: impl Fn(Real) -> Real,
+    (a, b, c): (Real, Real, Real),
+    valid: impl Fn(Real) -> bool,
+) -> Option<Real> {
+    // Fujiwara's bound on the magnitude of any root
+    let bound = 2.0
+        * sqrt(fabs(a))
+            .max(pow(fabs(b), 0.2))
+            .max(pow(fabs(c), 0.125));
+    let lower = bound * 1e-6;
+    let ratio = pow(bound / lower, 1.0 / ROOT_SEARCH_SAMPLES as Real);
+
+    let mut x0 = lower;
+    let mut f0 = polynomial(x0);
+    for _ in 0..ROOT_SEARCH_SAMPLES {
+        let x1 = x0 * ratio;
+        let f1 = polynomial(x1);
+        if f0.signum() != f1.signum() {
+            let root = bisect(&polynomial, x0, x1);
+
+            if valid(root) {
+                return Some(root);
+            }
+        }
+        x0 = x1;
+        f0 = f1;
+    }
+    None
+}
+
+fn bisect(f: &impl Fn(Real) -> Real, mut lo: Real, mut hi: Real) -> Real {
+    let f_lo = f(lo);
+    for _ in 0..200 {
+        let mid = (lo + hi) / 2.0;
+        if (f(mid) > 0.0) == (f_lo > 0.0) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= Real::EPSILON * mid {
+            break;
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+fn check_coplanar(positions: &[Vector3; 3]) -> Result<(), &'static str> {
+    let [r1, r2, r3] = positions;
+    if r1.norm() == 0.0 || r2.norm() == 0.0 || r3.norm() == 0.0 {
+        return Err("Positions must not coincide with the central body");
+    }
+    let normal = r2.cross(r3);
+    if normal.norm() == 0.0 {
+        return Err("Positions must not be collinear");
+    }
+    let alignment = asin((normal.dot(r1) / (normal.norm() * r1.norm())).clamp(-1.0, 1.0));
+    if fabs(alignment) > COPLANARITY_TOLERANCE {
+        return Err("Positions are not coplanar");
+    }
+    Ok(())
+}
+
+fn validate_mu(mu: MetersCubedPerSecondSquared) -> Result<(), &'static str> {
+    if mu.value() <= 0.0 || !mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kepler::propagation::KeplerPropagator;
+    use crate::utils::{Degrees, Eccentricity, Meters};
+    use approx::assert_relative_eq;
+
+    const KM: Real = 1_000.0;
+    const CURTIS_MU: MetersCubedPerSecondSquared = MetersCubedPerSecondSquared(398_600.0e9);
+
+    fn velocity_at_middle(elements: &KeplerianElements) -> Vector3 {
+        elements.to_state_vector().velocity
+    }
+
+    // Curtis, "Orbital Mechanics for Engineering Students", Example 5.1
+    #[test]
+    fn gibbs_matches_curtis() {
+        let positions = [
+            Vector3::new(-294.32 * KM, 4_265.1 * KM, 5_986.7 * KM),
+            Vector3::new(-1_365.5 * KM, 3_637.6 * KM, 6_346.8 * KM),
+            Vector3::new(-2_940.3 * KM, 2_473.7 * KM, 6_555.8 * KM),
+        ];
+        let elements = gibbs(&positions, CURTIS_MU).unwrap();
+        let v2 = velocity_at_middle(&elements);
+
+        assert_relative_eq!(v2.x, -6_217.4, epsilon = 1.0);
+        assert_relative_eq!(v2.y, -4_012.2, epsilon = 1.0);
+        assert_relative_eq!(v2.z, 1_599.0, epsilon = 1.0);
+        assert_relative_eq!(elements.eccentricity().value(), 0.1, epsilon = 1e-3);
+        assert_relative_eq!(
+            Degrees::from(elements.inclination()).value(),
+            60.0,
+            epsilon = 0.01
+        );
+    }
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 7-4
+    #[test]
+    fn herrick_gibbs_matches_vallado() {
+        let positions = [
+            Vector3::new(3_419.855_64 * KM, 6_019.826_02 * KM, 2_784.600_22 * KM),
+            Vector3::new(2_935.911_95 * KM, 6_326.183_24 * KM, 2_660.595_84 * KM),
+            Vector3::new(2_434.952_02 * KM, 6_597.386_74 * KM, 2_521.523_11 * KM),
+        ];
+        let times = [Seconds(0.0), Seconds(76.48), Seconds(153.04)];
+        let mu = MetersCubedPerSecondSquared(398_600.441_8e9);
+        let v2 = velocity_at_middle(&herrick_gibbs(&positions, &times, mu).unwrap());
+
+        assert_relative_eq!(v2.x, -6_441.557, epsilon = 1.0);
+        assert_relative_eq!(v2.y, 3_777.559, epsilon = 1.0);
+        assert_relative_eq!(v2.z, -1_720.004, epsilon = 1.0);
+    }
+
+    // Curtis, "Orbital Mechanics for Engineering Students", Examples 5.11 and 5.12
+    #[test]
+    fn gauss_matches_curtis() {
+        let site = |lst_degrees: Real| {
+            let (re, f, h) = (6_378.0 * KM, 0.003_353, 1.0 * KM);
+            let phi: Radians = Degrees(40.0).into();
+            let theta: Radians = Degrees(lst_degrees).into();
+            let (sin_phi, cos_phi) = libm::sincos(phi.value());
+            let denom = sqrt(1.0 - (2.0 * f - f * f) * sin_phi * sin_phi);
+            let horizontal = (re / denom + h) * cos_phi;
+            Vector3::new(
+                horizontal * libm::cos(theta.value()),
+                horizontal * libm::sin(theta.value()),
+                (re * (1.0 - f) * (1.0 - f) / denom + h) * sin_phi,
+            )
+        };
+        let observation = |t: Real, ra: Real, dec: Real, lst: Real| {
+            AnglesObservation::from_right_ascension_declination(
+                Seconds(t),
+                Degrees(ra).into(),
+                Degrees(dec).into(),
+                site(lst),
+            )
+        };
+        let observations = [
+            observation(0.0, 43.537, -8.783_3, 44.506),
+            observation(118.10, 54.420, -12.074, 45.000),
+            observation(237.58, 64.318, -15.105, 45.499),
+        ];
+
+        let elements = gauss(&observations, CURTIS_MU).unwrap();
+        let state = elements.to_state_vector();
+
+        assert_relative_eq!(state.position.x, 5_662.1 * KM, epsilon = 2.0 * KM);
+        assert_relative_eq!(state.position.y, 6_538.0 * KM, epsilon = 2.0 * KM);
+        assert_relative_eq!(state.position.z, 3_269.0 * KM, epsilon = 2.0 * KM);
+        assert_relative_eq!(state.velocity.x, -3_885.6, epsilon = 5.0);
+        assert_relative_eq!(state.velocity.y, 5_121.4, epsilon = 5.0);
+        assert_relative_eq!(state.velocity.z, -2_243.3, epsilon = 5.0);
+        assert_relative_eq!(
+            elements.semi_major_axis().value(),
+            10_000.0 * KM,
+            max_relative = 1e-2
+        );
+        assert_relative_eq!(elements.eccentricity().value(), 0.1, epsilon = 2e-3);
+        assert_relative_eq!(
+            Degrees::from(elements.inclination()).value(),
+            30.0,
+            epsilon = 0.1
+        );
+    }
+
+    #[test]
+    fn methods_recover_a_known_orbit() {
+        let truth = KeplerianElements::new(
+            Meters(8_000.0 * KM),
+            Eccentricity::new(0.15).unwrap(),
+            Degrees(63.4).into(),
+            Degrees(200.0).into(),
+            Degrees(270.0).into(),
+            Degrees(10.0).into(),
+            CURTIS_MU,
+        )
+        .unwrap();
+        let propagator = KeplerPropagator::new(truth, Seconds(0.0));
+        let position = |t: Real| propagator.propagate(Seconds(t)).unwrap().position;
+
+        let wide = gibbs(
+            &[position(-1_200.0), position(0.0), position(1_200.0)],
+            CURTIS_MU,
+        )
+        .unwrap();
+        let times = [Seconds(-60.0), Seconds(0.0), Seconds(60.0)];
+        let close = herrick_gibbs(&times.map(|t| position(t.value())), &times, CURTIS_MU).unwrap();
+
+        for elements in [wide, close] {
+            assert_relative_eq!(
+                elements.semi_major_axis().value(),
+                8_000.0 * KM,
+                max_relative = 1e-4
+            );
+            assert_relative_eq!(elements.eccentricity().value(), 0.15, epsilon = 1e-4);
+            assert_relative_eq!(
+                elements.inclination().value(),
+                truth.inclination().value(),
+                epsilon = 1e-6
+            );
+            assert_relative_eq!(
+                elements.raan().value(),
+                truth.raan().value(),
+                epsilon = 1e-6
+            );
+        }
+
+        // Angles-only from a ground site near the middle sub-satellite point,
+        // offset from the orbit plane and rotating with the Earth
+        let normal = truth.to_state_vector().angular_momentum().unit();
+        let site = (position(0.0).unit() + normal * 0.1).unit() * (6_378.0 * KM);
+        let sites: [Vector3; 3] = core::array::from_fn(|k| {
+            let theta = 7.292_115e-5 * (k as Real - 1.0) * 120.0;
+            let (sin_t, cos_t) = libm::sincos(theta);
+            Vector3::new(
+                site.x * cos_t - site.y * sin_t,
+                site.x * sin_t + site.y * cos_t,
+                site.z,
+            )
+        });
+        let observations: [AnglesObservation; 3] = core::array::from_fn(|k| {
+            let t = (k as Real - 1.0) * 120.0;
+            AnglesObservation {
+                time: Seconds(t),
+                line_of_sight: (position(t) - sites[k]).unit(),
+                site: sites[k],
+            }
+        });
+        let angles = gauss(&observations, CURTIS_MU).unwrap();
+        assert_relative_eq!(
+            angles.semi_major_axis().value(),
+            8_000.0 * KM,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(angles.eccentricity().value(), 0.15, epsilon = 1e-6);
+        assert_relative_eq!(
+            angles.true_anomaly().value(),
+            truth.true_anomaly().value(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn invalid_observations_are_rejected() {
+        let r = |x: Real, y: Real, z: Real| Vector3::new(x * KM, y * KM, z * KM);
+        let non_coplanar = [
+            r(7_000.0, 0.0, 0.0),
+            r(0.0, 7_000.0, 0.0),
+            r(0.0, 0.0, 7_000.0),
+        ];
+        assert!(gibbs(&non_coplanar, CURTIS_MU).is_err());
+
+        let positions = [
+            r(7_000.0, 0.0, 0.0),
+            r(6_990.0, 300.0, 0.0),
+            r(6_970.0, 600.0, 0.0),
+        ];
+        let backwards = [Seconds(60.0), Seconds(30.0), Seconds(0.0)];
+        assert!(herrick_gibbs(&positions, &backwards, CURTIS_MU).is_err());
+        assert!(gibbs(&positions, MetersCubedPerSecondSquared(-1.0)).is_err());
+    }
+}
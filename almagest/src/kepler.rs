@@ -9,6 +9,16 @@
 //!
 //! - **Elliptical orbit geometry**: Semi-major axis, eccentricity, foci
 //! - **Orbital parameters**: Periapsis, apoapsis, focal distances
+//! - **Escape trajectories**: Parabolic and hyperbolic conics alongside the ellipse
+//! - **Classical orbital elements**: Full 3D orientation of an orbit in space
+//! - **State vectors**: Conversion between Cartesian position/velocity and elements
+//! - **Anomalies**: Kepler's equation and mean/eccentric/true anomaly conversions, in [`anomaly`]
+//! - **Propagation**: Analytic two-body motion over time, in [`propagation`]
+//! - **Lambert's problem**: Trajectories connecting two positions in a given time, in [`lambert`]
+//! - **Gravity assists**: Turning angle and Δv of unpowered and powered flybys, in [`flyby`]
+//! - **Orbit fitting**: Least-squares orbits from sampled positions, in [`fitting`]
+//! - **Equinoctial elements**: Non-singular element sets for circular and equatorial orbits, in [`equinoctial`]
+//! - **J2 secular drift**: Node and periapsis precession, sun-synchronous and frozen orbit design, in [`secular`]
 //! - **Mathematical relationships**: Standard orbital mechanics formulas
 //!
 //! ## Key Concepts
@@ -19,11 +29,27 @@
 //! - **Eccentricity (e)**: Shape parameter (0 = circle, <1 = ellipse, =1 = parabola)
 //! - **Focal distance (c)**: Distance from center to focus
 //!
+//! ### Conic Sections
+//! Orbits with e ≥ 1 never return. [`Conic`] classifies an orbit by its
+//! eccentricity into an [`Ellipse`], [`Parabola`] or [`Hyperbola`]; the
+//! hyperbola carries the quantities that matter for escape:
+//! - **Hyperbolic excess velocity (v∞)**: Speed remaining far from the body
+//! - **Turning angle (δ)**: Angle between incoming and outgoing asymptotes
+//! - **Asymptote true anomaly (ν∞)**: Limit of the true anomaly, `arccos(-1/e)`
+//!
 //! ### Orbital Points
 //! - **Periapsis**: Closest approach to the central body
 //! - **Apoapsis**: Farthest point from the central body
 //! - **Primary focus**: Location of the central gravitating body
 //!
+//! ### Orientation in Space
+//! A planar [`Ellipse`] says nothing about how the orbit sits in 3D. The
+//! [`KeplerianElements`] type adds the remaining classical elements:
+//! - **Inclination (i)**: Tilt of the orbital plane from the reference plane
+//! - **Right ascension of the ascending node (Ω)**: Where the orbit crosses the reference plane going north
+//! - **Argument of periapsis (ω)**: Angle from the ascending node to periapsis
+//! - **True anomaly (ν)**: Angle from periapsis to the orbiting body
+//!
 //! ## Mathematical Relationships
 //!
 //! The fundamental ellipse relationships implemented here:
@@ -53,7 +79,24 @@
 
 use libm::sqrt;
 
-use crate::utils::{Eccentricity, Meters, Real};
+pub mod anomaly;
+pub mod equinoctial;
+pub mod fitting;
+pub mod flyby;
+pub mod lambert;
+pub mod propagation;
+pub mod secular;
+
+use crate::celestials::CelestialBody;
+use crate::utils::{
+    Eccentricity, JoulesPerKilogram, Meters, MetersCubedPerSecondSquared, MetersPerSecond,
+    MetersSquaredPerSecond, PI, Radians, RadiansPerSecond, Real, Seconds, TAU, Vector3,
+};
+
+/// Below this magnitude an eccentricity is treated as circular, and below
+/// this sine of inclination an orbit is treated as equatorial, when
+/// converting state vectors to elements.
+const SINGULARITY_TOLERANCE: Real = 1e-11;
 
 /// A point in 2D space with type-safe coordinate units.
 ///
@@ -93,6 +136,9 @@ pub struct Point {
 /// - Apoapsis: `rₐ = a(1 + e)`
 /// - Focal distance: `c = ae`
 ///
+/// These formulas only hold for e < 1; use [`Conic`] when the eccentricity
+/// may describe an escape trajectory.
+///
 /// # Examples
 /// ```rust
 /// use almagest::kepler::{Ellipse, Point};
@@ -112,6 +158,7 @@ pub struct Point {
 ///     Point { x: Meters(0.0), y: Meters(0.0) }
 /// );
 /// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Ellipse {
     /// Orbital eccentricity (0 ≤ e < 1 for bound orbits)
     e: Eccentricity,
@@ -152,40 +199,930 @@ impl Ellipse {
         self.r_p
     }
 
-    /// Half of the long axis of the ellipse,
-    /// denoted in formula by `a`
-    pub fn semi_major_axis(&self) -> Meters {
-        self.periapsis() / (1.0 - self.eccentricity().value())
-    }
+    /// Half of the long axis of the ellipse,
+    /// denoted in formula by `a`
+    pub fn semi_major_axis(&self) -> Meters {
+        self.periapsis() / (1.0 - self.eccentricity().value())
+    }
+
+    /// Half of the short axis of the ellipse,
+    /// denoted in formula by `b`
+    pub fn semi_minor_axis(&self) -> Meters {
+        // b = a * sqrt(1 - ecc^2)
+        Meters(
+            self.semi_major_axis().value()
+                * sqrt(1.0 - (self.eccentricity().value() * self.eccentricity().value())),
+        )
+    }
+
+    /// Describe the shape of the ellipse;
+    /// an alternative to using the eccentricity
+    pub fn flattening(&self) -> Real {
+        let a = self.semi_major_axis() - self.semi_minor_axis();
+        a / self.semi_major_axis()
+    }
+
+    /// The distance from the primary focus to the
+    /// far edge of the ellipse, along the major axis
+    pub fn apoapsis(&self) -> Meters {
+        Meters(self.semi_major_axis().value() * (1.0 + self.eccentricity().value()))
+    }
+
+    /// The distance between foci;
+    /// half of the this value is denoted in formula
+    /// by `c`
+    pub fn focal_distance(&self) -> Meters {
+        Meters(self.eccentricity().value() * self.semi_major_axis().value())
+    }
+
+    /// The radius at which the body crosses the latus rectum,
+    /// `p = a(1 - e²)`
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.r_p * (1.0 + self.eccentricity().value())
+    }
+
+    /// Specific orbital energy about `body`, `ε = -μ/2a`
+    pub fn specific_energy(&self, body: &CelestialBody) -> JoulesPerKilogram {
+        JoulesPerKilogram(-body.mu.value() / (2.0 * self.semi_major_axis().value()))
+    }
+
+    /// Specific angular momentum about `body`, `h = √(μp)`
+    pub fn specific_angular_momentum(&self, body: &CelestialBody) -> MetersSquaredPerSecond {
+        MetersSquaredPerSecond(sqrt(body.mu.value() * self.semi_latus_rectum().value()))
+    }
+
+    /// Distance from the primary focus at true anomaly `nu`,
+    /// `r = p / (1 + e·cos ν)`
+    pub fn radius_at(&self, nu: Radians) -> Meters {
+        let e = self.eccentricity().value();
+        Meters(self.semi_latus_rectum().value() / (1.0 + e * libm::cos(nu.value())))
+    }
+
+    /// The point on the ellipse at true anomaly `nu`.
+    ///
+    /// The major axis lies along x with periapsis on the +x side of the
+    /// primary focus, and the body moves counter-clockwise.
+    pub fn point_at(&self, nu: Radians) -> Point {
+        let r = self.radius_at(nu).value();
+        let (sin_nu, cos_nu) = libm::sincos(nu.value());
+        Point {
+            x: Meters(self.f.x.value() + r * cos_nu),
+            y: Meters(self.f.y.value() + r * sin_nu),
+        }
+    }
+
+    /// Angle between the velocity and the local horizontal at true anomaly
+    /// `nu`; positive while climbing from periapsis to apoapsis
+    pub fn flight_path_angle(&self, nu: Radians) -> Radians {
+        let e = self.eccentricity().value();
+        let (sin_nu, cos_nu) = libm::sincos(nu.value());
+        Radians(libm::atan2(e * sin_nu, 1.0 + e * cos_nu))
+    }
+
+    /// Orbital speed at true anomaly `nu` from the vis-viva equation,
+    /// `v = √(μ(2/r - 1/a))`
+    pub fn speed_at(&self, nu: Radians, mu: MetersCubedPerSecondSquared) -> MetersPerSecond {
+        let r = self.radius_at(nu).value();
+        let a = self.semi_major_axis().value();
+        MetersPerSecond(sqrt(mu.value() * (2.0 / r - 1.0 / a)))
+    }
+
+    /// Velocity component along the radius vector at true anomaly `nu`,
+    /// `vᵣ = √(μ/p)·e·sin ν`; positive when moving away from the focus
+    pub fn radial_velocity(&self, nu: Radians, mu: MetersCubedPerSecondSquared) -> MetersPerSecond {
+        let e = self.eccentricity().value();
+        MetersPerSecond(
+            sqrt(mu.value() / self.semi_latus_rectum().value()) * e * libm::sin(nu.value()),
+        )
+    }
+
+    /// Velocity component perpendicular to the radius vector at true anomaly
+    /// `nu`, `vₜ = √(μ/p)·(1 + e·cos ν)`
+    pub fn tangential_velocity(
+        &self,
+        nu: Radians,
+        mu: MetersCubedPerSecondSquared,
+    ) -> MetersPerSecond {
+        let e = self.eccentricity().value();
+        MetersPerSecond(
+            sqrt(mu.value() / self.semi_latus_rectum().value()) * (1.0 + e * libm::cos(nu.value())),
+        )
+    }
+
+    /// Time for one revolution around a central body with parameter `mu`
+    pub fn period(&self, mu: MetersCubedPerSecondSquared) -> Result<Seconds, &'static str> {
+        if self.eccentricity().value() >= 1.0 {
+            return Err("Only bound orbits have a period");
+        }
+        let a = self.semi_major_axis().value();
+        if a <= 0.0 || !a.is_finite() {
+            return Err("Semi-major axis must be positive");
+        }
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        // Kepler's third law, T = 2π√(a³/μ)
+        Ok(Seconds(TAU * sqrt(a * a * a / mu.value())))
+    }
+}
+
+/// A parabolic trajectory (e = 1), the boundary between bound and escape orbits.
+///
+/// A body on a parabola arrives at infinity with exactly zero excess velocity.
+///
+/// # Examples
+/// ```rust
+/// use almagest::celestials::celestial_bodies::EARTH;
+/// use almagest::kepler::{Parabola, Point};
+/// use almagest::utils::Meters;
+///
+/// let escape = Parabola::new(Point { x: Meters(0.0), y: Meters(0.0) }, Meters(6_778_000.0))
+///     .expect("Valid periapsis");
+/// // ~10.8 km/s: escape speed at ISS altitude
+/// assert!((escape.periapsis_speed(EARTH.mu).value() - 10_845.0).abs() < 5.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parabola {
+    /// Location of the focus (central gravitating body)
+    f: Point,
+    /// Distance from the focus to periapsis
+    r_p: Meters,
+}
+
+impl Parabola {
+    pub fn new(f: Point, r_p: Meters) -> Result<Self, &'static str> {
+        if r_p.value() <= 0.0 || !r_p.value().is_finite() {
+            return Err("Periapsis distance must be positive");
+        }
+        Ok(Parabola { f, r_p })
+    }
+
+    pub fn eccentricity(&self) -> Eccentricity {
+        // e = 1 is always a valid eccentricity
+        Eccentricity::new(1.0).unwrap()
+    }
+
+    /// The gravitational center of attraction
+    pub fn primary_focus(&self) -> Point {
+        self.f
+    }
+
+    /// The distance from the focus to the vertex of the parabola
+    pub fn periapsis(&self) -> Meters {
+        self.r_p
+    }
+
+    /// Semi-latus rectum, `p = 2rₚ`
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.r_p * 2.0
+    }
+
+    /// Speed at periapsis, which is exactly the local escape speed `√(2μ/rₚ)`
+    pub fn periapsis_speed(&self, mu: MetersCubedPerSecondSquared) -> MetersPerSecond {
+        MetersPerSecond(sqrt(2.0 * mu.value() / self.r_p.value()))
+    }
+}
+
+/// A hyperbolic escape or flyby trajectory (e > 1).
+///
+/// By convention the semi-major axis of a hyperbola is negative, so that
+/// the familiar relations `rₚ = a(1 - e)` and `ε = -μ/2a` still hold.
+///
+/// Key formulas:
+/// - Hyperbolic excess velocity: `v∞ = √(-μ/a)`
+/// - Turning angle: `δ = 2·arcsin(1/e)`
+/// - Asymptote true anomaly: `ν∞ = arccos(-1/e)`
+///
+/// # Examples
+/// ```rust
+/// use almagest::celestials::celestial_bodies::EARTH;
+/// use almagest::kepler::{Hyperbola, Point};
+/// use almagest::utils::{Meters, MetersPerSecond};
+///
+/// // Leaving Earth from a 300 km periapsis with 3 km/s to spare
+/// let departure = Hyperbola::from_excess_velocity(
+///     MetersPerSecond(3_000.0),
+///     Meters(6_671_000.0),
+///     Point { x: Meters(0.0), y: Meters(0.0) },
+///     EARTH.mu,
+/// )
+/// .expect("Valid departure");
+///
+/// assert!(departure.eccentricity().value() > 1.0);
+/// println!("Turning angle: {} rad", departure.turning_angle().value());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hyperbola {
+    /// Orbital eccentricity (e > 1)
+    e: Eccentricity,
+    /// Location of the focus (central gravitating body)
+    f: Point,
+    /// Distance from the focus to periapsis
+    r_p: Meters,
+}
+
+impl Hyperbola {
+    pub fn new(e: Eccentricity, f: Point, r_p: Meters) -> Result<Self, &'static str> {
+        if e.value() <= 1.0 || !e.value().is_finite() {
+            return Err("Hyperbolic eccentricity must be greater than 1");
+        }
+        if r_p.value() <= 0.0 || !r_p.value().is_finite() {
+            return Err("Periapsis distance must be positive");
+        }
+        Ok(Hyperbola { e, f, r_p })
+    }
+
+    /// Construct the hyperbola that passes periapsis at `r_p` and leaves
+    /// with excess speed `v_inf`: `e = 1 + rₚ·v∞²/μ`.
+    pub fn from_excess_velocity(
+        v_inf: MetersPerSecond,
+        r_p: Meters,
+        f: Point,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        if v_inf.value() <= 0.0 || !v_inf.value().is_finite() {
+            return Err("Hyperbolic excess velocity must be positive");
+        }
+        if mu.value() <= 0.0 {
+            return Err("Gravitational parameter must be positive");
+        }
+        let v = v_inf.value();
+        let e = 1.0 + r_p.value() * v * v / mu.value();
+        Self::new(Eccentricity::new(e)?, f, r_p)
+    }
+
+    pub fn eccentricity(&self) -> Eccentricity {
+        self.e
+    }
+
+    /// The gravitational center of attraction
+    pub fn primary_focus(&self) -> Point {
+        self.f
+    }
+
+    /// The distance from the focus to the closest point of the trajectory
+    pub fn periapsis(&self) -> Meters {
+        self.r_p
+    }
+
+    /// Semi-major axis, `a = rₚ / (1 - e)`; negative for a hyperbola
+    pub fn semi_major_axis(&self) -> Meters {
+        self.r_p / (1.0 - self.e.value())
+    }
+
+    /// Conjugate semi-axis, `b = |a|√(e² - 1)`; also the impact parameter,
+    /// the miss distance of the incoming asymptote from the focus
+    pub fn semi_minor_axis(&self) -> Meters {
+        let e = self.e.value();
+        Meters(libm::fabs(self.semi_major_axis().value()) * sqrt(e * e - 1.0))
+    }
+
+    /// Distance from the center of the hyperbola to the focus, `c = e|a|`
+    pub fn focal_distance(&self) -> Meters {
+        Meters(self.e.value() * libm::fabs(self.semi_major_axis().value()))
+    }
+
+    /// Semi-latus rectum, `p = rₚ(1 + e)`
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.r_p * (1.0 + self.e.value())
+    }
+
+    /// Limiting true anomaly along the asymptotes, `ν∞ = arccos(-1/e)`
+    pub fn asymptote_true_anomaly(&self) -> Radians {
+        Radians(libm::acos(-1.0 / self.e.value()))
+    }
+
+    /// Angle through which the trajectory bends the velocity,
+    /// `δ = 2·arcsin(1/e)`
+    pub fn turning_angle(&self) -> Radians {
+        Radians(2.0 * libm::asin(1.0 / self.e.value()))
+    }
+
+    /// Unit direction of travel on the incoming asymptote, in the plane of
+    /// the conic with periapsis along +x
+    pub fn incoming_asymptote(&self) -> Vector3 {
+        let e = self.e.value();
+        Vector3::new(1.0 / e, sqrt(e * e - 1.0) / e, 0.0)
+    }
+
+    /// Unit direction of travel on the outgoing asymptote, in the plane of
+    /// the conic with periapsis along +x
+    pub fn outgoing_asymptote(&self) -> Vector3 {
+        let e = self.e.value();
+        Vector3::new(-1.0 / e, sqrt(e * e - 1.0) / e, 0.0)
+    }
+
+    /// Speed remaining far from the central body, `v∞ = √(-μ/a)`
+    pub fn hyperbolic_excess_velocity(&self, mu: MetersCubedPerSecondSquared) -> MetersPerSecond {
+        MetersPerSecond(sqrt(-mu.value() / self.semi_major_axis().value()))
+    }
+
+    /// Speed at periapsis, `vₚ = √(v∞² + 2μ/rₚ)`
+    pub fn periapsis_speed(&self, mu: MetersCubedPerSecondSquared) -> MetersPerSecond {
+        let v_inf = self.hyperbolic_excess_velocity(mu).value();
+        MetersPerSecond(sqrt(v_inf * v_inf + 2.0 * mu.value() / self.r_p.value()))
+    }
+}
+
+/// Any Keplerian trajectory, classified by its eccentricity.
+///
+/// [`Ellipse`] on its own silently produces infinite or negative axes when
+/// handed e ≥ 1; `Conic` picks the right shape so each variant only exposes
+/// the quantities that make sense for it.
+///
+/// # Examples
+/// ```rust
+/// use almagest::kepler::{Conic, Point};
+/// use almagest::utils::{Eccentricity, Meters};
+///
+/// let focus = Point { x: Meters(0.0), y: Meters(0.0) };
+/// let conic = Conic::new(Eccentricity::new(1.3).unwrap(), focus, Meters(7_000_000.0))
+///     .expect("Valid conic");
+///
+/// match conic {
+///     Conic::Hyperbola(h) => println!("Escapes, turning {} rad", h.turning_angle().value()),
+///     _ => println!("Does not escape"),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conic {
+    Ellipse(Ellipse),
+    Parabola(Parabola),
+    Hyperbola(Hyperbola),
+}
+
+impl Conic {
+    pub fn new(e: Eccentricity, f: Point, r_p: Meters) -> Result<Self, &'static str> {
+        if r_p.value() <= 0.0 || !r_p.value().is_finite() {
+            return Err("Periapsis distance must be positive");
+        }
+        let ecc = e.value();
+        if ecc < 1.0 {
+            Ok(Conic::Ellipse(Ellipse::new(e, f, r_p)))
+        } else if ecc == 1.0 {
+            Ok(Conic::Parabola(Parabola::new(f, r_p)?))
+        } else {
+            Ok(Conic::Hyperbola(Hyperbola::new(e, f, r_p)?))
+        }
+    }
+
+    pub fn eccentricity(&self) -> Eccentricity {
+        match self {
+            Conic::Ellipse(c) => c.eccentricity(),
+            Conic::Parabola(c) => c.eccentricity(),
+            Conic::Hyperbola(c) => c.eccentricity(),
+        }
+    }
+
+    /// The gravitational center of attraction
+    pub fn primary_focus(&self) -> Point {
+        match self {
+            Conic::Ellipse(c) => c.primary_focus(),
+            Conic::Parabola(c) => c.primary_focus(),
+            Conic::Hyperbola(c) => c.primary_focus(),
+        }
+    }
+
+    /// The distance from the focus to the closest point of the trajectory
+    pub fn periapsis(&self) -> Meters {
+        match self {
+            Conic::Ellipse(c) => c.periapsis(),
+            Conic::Parabola(c) => c.periapsis(),
+            Conic::Hyperbola(c) => c.periapsis(),
+        }
+    }
+
+    /// Semi-latus rectum, `p = rₚ(1 + e)`, valid for every conic
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.periapsis() * (1.0 + self.eccentricity().value())
+    }
+
+    /// Whether the trajectory is closed (e < 1)
+    pub fn is_bound(&self) -> bool {
+        matches!(self, Conic::Ellipse(_))
+    }
+}
+
+/// The six classical orbital elements, plus the gravitational parameter
+/// of the central body they are referenced to.
+///
+/// Where [`Ellipse`] describes only the size and shape of an orbit in its
+/// own plane, `KeplerianElements` also fixes the orientation of that plane
+/// and the position of the orbiting body along it.
+///
+/// Conventions:
+/// - Bound orbits (0 ≤ e < 1) have a positive semi-major axis
+/// - Hyperbolic orbits (e > 1) have a negative semi-major axis
+/// - Parabolic orbits (e = 1) have no finite semi-major axis and are rejected
+/// - Ω, ω and ν are wrapped into [0, 2π); i must lie in [0, π]
+///
+/// # Examples
+/// ```rust
+/// use almagest::celestials::celestial_bodies::EARTH;
+/// use almagest::kepler::KeplerianElements;
+/// use almagest::utils::{Degrees, Eccentricity, Meters, Radians};
+///
+/// let iss = KeplerianElements::new(
+///     Meters(6_778_000.0),
+///     Eccentricity::new(0.0005).unwrap(),
+///     Degrees(51.64).into(),
+///     Degrees(120.0).into(),
+///     Degrees(90.0).into(),
+///     Radians(0.0),
+///     EARTH.mu,
+/// )
+/// .expect("Valid ISS-like elements");
+///
+/// assert!(iss.apoapsis().value() > iss.periapsis().value());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeplerianElements {
+    /// Semi-major axis (negative for hyperbolic orbits)
+    a: Meters,
+    /// Orbital eccentricity
+    e: Eccentricity,
+    /// Inclination
+    i: Radians,
+    /// Right ascension of the ascending node
+    raan: Radians,
+    /// Argument of periapsis
+    argp: Radians,
+    /// True anomaly
+    nu: Radians,
+    /// Standard gravitational parameter of the central body
+    mu: MetersCubedPerSecondSquared,
+}
+
+impl KeplerianElements {
+    pub fn new(
+        a: Meters,
+        e: Eccentricity,
+        i: Radians,
+        raan: Radians,
+        argp: Radians,
+        nu: Radians,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        if !a.value().is_finite() {
+            return Err("Semi-major axis must be finite");
+        }
+        let ecc = e.value();
+        if !ecc.is_finite() {
+            return Err("Eccentricity must be finite");
+        }
+        if ecc == 1.0 {
+            return Err("Parabolic orbits have no finite semi-major axis");
+        }
+        if ecc < 1.0 && a.value() <= 0.0 {
+            return Err("Semi-major axis must be positive for bound orbits");
+        }
+        if ecc > 1.0 && a.value() >= 0.0 {
+            return Err("Semi-major axis must be negative for hyperbolic orbits");
+        }
+        if !i.value().is_finite() || i.value() < 0.0 || i.value() > PI {
+            return Err("Inclination must be between 0 and π");
+        }
+        if !raan.value().is_finite() || !argp.value().is_finite() || !nu.value().is_finite() {
+            return Err("Orbital angles must be finite");
+        }
+        if ecc > 1.0 && 1.0 + ecc * libm::cos(nu.value()) <= 0.0 {
+            return Err("True anomaly lies beyond the hyperbolic asymptotes");
+        }
+
+        Ok(KeplerianElements {
+            a,
+            e,
+            i,
+            raan: raan.normalized(),
+            argp: argp.normalized(),
+            nu: nu.normalized(),
+            mu,
+        })
+    }
+
+    /// Build a full element set from a planar ellipse and its orientation.
+    ///
+    /// The focus location of the ellipse is ignored; the central body is
+    /// always taken to sit at the origin of the reference frame.
+    pub fn from_ellipse(
+        ellipse: &Ellipse,
+        i: Radians,
+        raan: Radians,
+        argp: Radians,
+        nu: Radians,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        Self::new(
+            ellipse.semi_major_axis(),
+            ellipse.eccentricity(),
+            i,
+            raan,
+            argp,
+            nu,
+            mu,
+        )
+    }
+
+    /// The in-plane shape of the orbit, with the central body at the origin
+    pub fn ellipse(&self) -> Ellipse {
+        Ellipse::new(
+            self.e,
+            Point {
+                x: Meters(0.0),
+                y: Meters(0.0),
+            },
+            self.periapsis(),
+        )
+    }
+
+    /// Half of the long axis of the orbit, denoted in formula by `a`;
+    /// negative for hyperbolic orbits
+    pub fn semi_major_axis(&self) -> Meters {
+        self.a
+    }
+
+    pub fn eccentricity(&self) -> Eccentricity {
+        self.e
+    }
+
+    /// Tilt of the orbital plane from the reference plane, denoted by `i`
+    pub fn inclination(&self) -> Radians {
+        self.i
+    }
+
+    /// Right ascension of the ascending node, denoted by `Ω`
+    pub fn raan(&self) -> Radians {
+        self.raan
+    }
+
+    /// Argument of periapsis, denoted by `ω`
+    pub fn argument_of_periapsis(&self) -> Radians {
+        self.argp
+    }
+
+    /// True anomaly, denoted by `ν`
+    pub fn true_anomaly(&self) -> Radians {
+        self.nu
+    }
+
+    /// Standard gravitational parameter of the central body, denoted by `μ`
+    pub fn mu(&self) -> MetersCubedPerSecondSquared {
+        self.mu
+    }
+
+    /// Whether the orbit is closed (e < 1)
+    pub fn is_bound(&self) -> bool {
+        self.e.value() < 1.0
+    }
+
+    /// Half of the short axis of the orbit, denoted in formula by `b`.
+    ///
+    /// For hyperbolic orbits this is the conjugate semi-axis, `|a|√(e² - 1)`.
+    pub fn semi_minor_axis(&self) -> Meters {
+        let e = self.e.value();
+        Meters(libm::fabs(self.a.value()) * sqrt(libm::fabs(1.0 - e * e)))
+    }
+
+    /// The distance from the central body to the closest point of the orbit
+    pub fn periapsis(&self) -> Meters {
+        Meters(self.a.value() * (1.0 - self.e.value()))
+    }
+
+    /// The distance from the central body to the farthest point of the orbit;
+    /// infinite for hyperbolic orbits
+    pub fn apoapsis(&self) -> Meters {
+        if self.is_bound() {
+            Meters(self.a.value() * (1.0 + self.e.value()))
+        } else {
+            Meters(Real::INFINITY)
+        }
+    }
+
+    /// The distance from the center of the conic to its focus, denoted by `c`
+    pub fn focal_distance(&self) -> Meters {
+        Meters(self.e.value() * libm::fabs(self.a.value()))
+    }
+
+    /// Describe the shape of a bound orbit;
+    /// an alternative to using the eccentricity
+    pub fn flattening(&self) -> Real {
+        let a = libm::fabs(self.a.value());
+        (a - self.semi_minor_axis().value()) / a
+    }
+
+    /// Semi-latus rectum, `p = a(1 - e²)`
+    pub fn semi_latus_rectum(&self) -> Meters {
+        let e = self.e.value();
+        Meters(self.a.value() * (1.0 - e * e))
+    }
+
+    /// The in-plane trajectory as the appropriate conic section, with the
+    /// central body at the origin
+    pub fn conic(&self) -> Conic {
+        let origin = Point {
+            x: Meters(0.0),
+            y: Meters(0.0),
+        };
+        // Elements are validated as either bound or hyperbolic on construction
+        Conic::new(self.e, origin, self.periapsis()).unwrap_or(Conic::Ellipse(self.ellipse()))
+    }
+
+    /// Mean anomaly corresponding to the current true anomaly;
+    /// negative before periapsis on hyperbolic orbits
+    pub fn mean_anomaly(&self) -> Result<Radians, &'static str> {
+        anomaly::true_to_mean(self.nu, self.e)
+    }
+
+    /// Rate at which the mean anomaly advances, `n = √(μ/|a|³)`
+    pub fn mean_motion(&self) -> RadiansPerSecond {
+        let a = libm::fabs(self.a.value());
+        RadiansPerSecond(sqrt(self.mu.value() / (a * a * a)))
+    }
+
+    /// Specific orbital energy, `ε = -μ/2a`
+    pub fn specific_energy(&self) -> JoulesPerKilogram {
+        JoulesPerKilogram(-self.mu.value() / (2.0 * self.a.value()))
+    }
+
+    /// Specific angular momentum, `h = √(μp)`
+    pub fn specific_angular_momentum(&self) -> MetersSquaredPerSecond {
+        MetersSquaredPerSecond(sqrt(self.mu.value() * self.semi_latus_rectum().value()))
+    }
+
+    /// Eccentricity vector in the inertial frame, pointing toward periapsis
+    pub fn eccentricity_vector(&self) -> Vector3 {
+        self.perifocal_to_inertial(&Vector3::new(self.e.value(), 0.0, 0.0))
+    }
+
+    /// Time for one revolution; only defined for bound orbits
+    pub fn period(&self) -> Result<Seconds, &'static str> {
+        self.ellipse().period(self.mu)
+    }
+
+    /// The same orbit with the body moved to a different true anomaly
+    pub fn with_true_anomaly(&self, nu: Radians) -> Result<Self, &'static str> {
+        Self::new(self.a, self.e, self.i, self.raan, self.argp, nu, self.mu)
+    }
+}
+
+/// Cartesian position and velocity of a body relative to its central body.
+///
+/// Positions are in meters and velocities in meters per second, expressed in
+/// an inertial frame centered on the central body (e.g. Earth-centered
+/// inertial for Earth orbits).
+///
+/// # Examples
+/// ```rust
+/// use almagest::kepler::StateVector;
+/// use almagest::utils::Vector3;
+///
+/// let state = StateVector {
+///     position: Vector3::new(6_778_000.0, 0.0, 0.0),
+///     velocity: Vector3::new(0.0, 7_668.6, 0.0),
+/// };
+/// assert_eq!(state.radius().value(), 6_778_000.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StateVector {
+    /// Position relative to the central body, in meters
+    pub position: Vector3,
+    /// Velocity relative to the central body, in meters per second
+    pub velocity: Vector3,
+}
+
+impl StateVector {
+    pub fn new(position: Vector3, velocity: Vector3) -> Self {
+        StateVector { position, velocity }
+    }
+
+    /// Distance from the central body
+    pub fn radius(&self) -> Meters {
+        Meters(self.position.norm())
+    }
+
+    /// Magnitude of the velocity
+    pub fn speed(&self) -> MetersPerSecond {
+        MetersPerSecond(self.velocity.norm())
+    }
+
+    /// Specific angular momentum vector, `h = r × v`, normal to the orbit plane
+    pub fn angular_momentum(&self) -> Vector3 {
+        self.position.cross(&self.velocity)
+    }
+
+    /// Magnitude of the specific angular momentum, `|r × v|`
+    pub fn specific_angular_momentum(&self) -> MetersSquaredPerSecond {
+        MetersSquaredPerSecond(self.angular_momentum().norm())
+    }
+
+    /// Specific orbital energy about `body`, `ε = v²/2 - μ/r`
+    pub fn specific_energy(&self, body: &CelestialBody) -> JoulesPerKilogram {
+        let v = self.velocity.norm();
+        JoulesPerKilogram(v * v / 2.0 - body.mu.value() / self.position.norm())
+    }
+
+    /// Semi-latus rectum of the orbit about `body`, `p = h²/μ`
+    pub fn semi_latus_rectum(&self, body: &CelestialBody) -> Meters {
+        let h = self.angular_momentum().norm();
+        Meters(h * h / body.mu.value())
+    }
+
+    /// Eccentricity vector about `body`, pointing toward periapsis with
+    /// magnitude equal to the eccentricity
+    pub fn eccentricity_vector(&self, body: &CelestialBody) -> Vector3 {
+        self.eccentricity_vector_for(body.mu.value())
+    }
+
+    /// `e = ((v² - μ/r)·r - (r·v)·v) / μ`
+    fn eccentricity_vector_for(&self, mu: Real) -> Vector3 {
+        let (r_vec, v_vec) = (self.position, self.velocity);
+        let v = v_vec.norm();
+        (r_vec * (v * v - mu / r_vec.norm()) - v_vec * r_vec.dot(&v_vec)) / mu
+    }
+}
+
+/// Flattens a state to `[x, y, z, vx, vy, vz]` for numerical integration
+impl From<StateVector> for [Real; 6] {
+    fn from(state: StateVector) -> Self {
+        let (r, v) = (state.position, state.velocity);
+        [r.x, r.y, r.z, v.x, v.y, v.z]
+    }
+}
+
+impl From<[Real; 6]> for StateVector {
+    fn from(y: [Real; 6]) -> Self {
+        StateVector::new(
+            Vector3::new(y[0], y[1], y[2]),
+            Vector3::new(y[3], y[4], y[5]),
+        )
+    }
+}
+
+/// Angle between two vectors in [0, π], robust near 0 and π
+fn angle_between(a: &Vector3, b: &Vector3) -> Real {
+    libm::atan2(a.cross(b).norm(), a.dot(b))
+}
+
+impl KeplerianElements {
+    /// Recover the classical orbital elements from a Cartesian state.
+    ///
+    /// Elements that are undefined for singular geometries are fixed by
+    /// convention so that the result never contains NaNs:
+    /// - **Circular** (e ≈ 0): ω = 0 and ν is the argument of latitude
+    /// - **Equatorial** (i ≈ 0 or π): Ω = 0 and ω is the longitude of periapsis
+    /// - **Circular equatorial**: Ω = ω = 0 and ν is the true longitude
+    ///
+    /// Converting the result back with [`KeplerianElements::to_state_vector`]
+    /// reproduces the original state in every case.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use almagest::celestials::celestial_bodies::EARTH;
+    /// use almagest::kepler::{KeplerianElements, StateVector};
+    /// use almagest::utils::Vector3;
+    ///
+    /// let state = StateVector::new(
+    ///     Vector3::new(6_778_000.0, 0.0, 0.0),
+    ///     Vector3::new(0.0, 6_000.0, 4_500.0),
+    /// );
+    /// let elements = KeplerianElements::from_state_vector(&state, EARTH.mu)
+    ///     .expect("Valid state");
+    /// assert!(elements.is_bound());
+    /// ```
+    pub fn from_state_vector(
+        state: &StateVector,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        let r_vec = state.position;
+        let v_vec = state.velocity;
+        let r = r_vec.norm();
+        let v = v_vec.norm();
+        if !(r.is_finite() && v.is_finite()) {
+            return Err("State vector must be finite");
+        }
+        if r == 0.0 {
+            return Err("Position must not coincide with the central body");
+        }
+
+        let mu = mu.value();
+        let h_vec = state.angular_momentum();
+        let h = h_vec.norm();
+        if h <= SINGULARITY_TOLERANCE * r * v {
+            return Err("Rectilinear trajectories have no orbital plane");
+        }
+
+        // Node vector: k × h
+        let n_vec = Vector3::new(-h_vec.y, h_vec.x, 0.0);
+        let n = n_vec.norm();
+
+        let e_vec = state.eccentricity_vector_for(mu);
+        let ecc = e_vec.norm();
+        if libm::fabs(ecc - 1.0) < SINGULARITY_TOLERANCE {
+            return Err("Parabolic orbits have no finite semi-major axis");
+        }
+
+        let energy = v * v / 2.0 - mu / r;
+        let a = -mu / (2.0 * energy);
+        let i = libm::atan2(n, h_vec.z);
+
+        let circular = ecc < SINGULARITY_TOLERANCE;
+        let equatorial = n < SINGULARITY_TOLERANCE * h;
+        let retrograde = h_vec.z < 0.0;
+
+        let raan = if equatorial {
+            0.0
+        } else {
+            libm::atan2(n_vec.y, n_vec.x)
+        };
+
+        let argp = match (circular, equatorial) {
+            (true, _) => 0.0,
+            (false, true) => {
+                // Longitude of periapsis, measured in the direction of motion
+                let lon = libm::atan2(e_vec.y, e_vec.x);
+                if retrograde { -lon } else { lon }
+            }
+            (false, false) => {
+                let w = angle_between(&n_vec, &e_vec);
+                if e_vec.z < 0.0 { TAU - w } else { w }
+            }
+        };
+
+        let nu = match (circular, equatorial) {
+            (false, _) => {
+                let nu = angle_between(&e_vec, &r_vec);
+                if r_vec.dot(&v_vec) < 0.0 {
+                    TAU - nu
+                } else {
+                    nu
+                }
+            }
+            (true, false) => {
+                // Argument of latitude
+                let u = angle_between(&n_vec, &r_vec);
+                if r_vec.z < 0.0 { TAU - u } else { u }
+            }
+            (true, true) => {
+                // True longitude
+                let lon = libm::atan2(r_vec.y, r_vec.x);
+                if retrograde { -lon } else { lon }
+            }
+        };
 
-    /// Half of the short axis of the ellipse,
-    /// denoted in formula by `b`
-    pub fn semi_minor_axis(&self) -> Meters {
-        // b = a * sqrt(1 - ecc^2)
-        Meters(
-            self.semi_major_axis().value()
-                * sqrt(1.0 - (self.eccentricity().value() * self.eccentricity().value())),
+        KeplerianElements::new(
+            Meters(a),
+            Eccentricity::new(ecc)?,
+            Radians(i),
+            Radians(raan),
+            Radians(argp),
+            Radians(nu),
+            MetersCubedPerSecondSquared(mu),
         )
     }
 
-    /// Describe the shape of the ellipse;
-    /// an alternative to using the eccentricity
-    pub fn flattening(&self) -> Real {
-        let a = self.semi_major_axis() - self.semi_minor_axis();
-        a / self.semi_major_axis()
-    }
+    /// Cartesian position and velocity of the orbiting body at its current
+    /// true anomaly.
+    pub fn to_state_vector(&self) -> StateVector {
+        let e = self.e.value();
+        let p = self.semi_latus_rectum().value();
+        let mu = self.mu.value();
+        let (sin_nu, cos_nu) = libm::sincos(self.nu.value());
 
-    /// The distance from the primary focus to the
-    /// far edge of the ellipse, along the major axis
-    pub fn apoapsis(&self) -> Meters {
-        Meters(self.semi_major_axis().value() * (1.0 + self.eccentricity().value()))
+        // Position and velocity in the perifocal frame
+        let r = p / (1.0 + e * cos_nu);
+        let r_pqw = Vector3::new(r * cos_nu, r * sin_nu, 0.0);
+        let v_scale = sqrt(mu / p);
+        let v_pqw = Vector3::new(-v_scale * sin_nu, v_scale * (e + cos_nu), 0.0);
+
+        StateVector {
+            position: self.perifocal_to_inertial(&r_pqw),
+            velocity: self.perifocal_to_inertial(&v_pqw),
+        }
     }
 
-    /// The distance between foci;
-    /// half of the this value is denoted in formula
-    /// by `c`
-    pub fn focal_distance(&self) -> Meters {
-        Meters(self.eccentricity().value() * self.semi_major_axis().value())
+    /// Rotate a perifocal vector into the inertial frame, R3(-Ω)·R1(-i)·R3(-ω)
+    fn perifocal_to_inertial(&self, v: &Vector3) -> Vector3 {
+        let (sin_o, cos_o) = libm::sincos(self.raan.value());
+        let (sin_i, cos_i) = libm::sincos(self.i.value());
+        let (sin_w, cos_w) = libm::sincos(self.argp.value());
+
+        let p_hat = Vector3::new(
+            cos_o * cos_w - sin_o * sin_w * cos_i,
+            sin_o * cos_w + cos_o * sin_w * cos_i,
+            sin_w * sin_i,
+        );
+        let q_hat = Vector3::new(
+            -cos_o * sin_w - sin_o * cos_w * cos_i,
+            -sin_o * sin_w + cos_o * cos_w * cos_i,
+            cos_w * sin_i,
+        );
+        p_hat * v.x + q_hat * v.y
     }
 }
 
@@ -211,6 +1148,8 @@ pub fn calc_2c(r_f: Meters, r_f_p: Meters) -> Meters {
 
 /// Calculate the eccentricity of an orbit from the lengths
 /// of both foci to a single point on the orbit.
+///
+/// To recover an orbit from many noisy samples instead, see [`fitting`].
 // TODO: lots of tests to ensure the returned value is never negative
 pub fn calc_ecc(r_f: Meters, r_f_p: Meters) -> Eccentricity {
     let two_a = calc_2a(r_f, r_f_p);
@@ -223,6 +1162,8 @@ pub fn calc_ecc(r_f: Meters, r_f_p: Meters) -> Eccentricity {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::assert_states_close;
+    use crate::utils::Degrees;
     use approx::assert_relative_eq;
 
     #[test]
@@ -233,7 +1174,7 @@ mod tests {
         };
         let e = Ellipse {
             e: Eccentricity::new(1.0).unwrap(),
-            f: f,
+            f,
             r_p: Meters(1.0),
         };
         assert_eq!(e.f, f);
@@ -247,7 +1188,7 @@ mod tests {
         };
         let e = Ellipse {
             e: Eccentricity::new(0.5).unwrap(),
-            f: f,
+            f,
             r_p: Meters(1.0),
         };
         let expected = Meters(2.0);
@@ -288,8 +1229,8 @@ mod tests {
             r_p: Meters(r_p_val),
         };
 
-        let expected_a = 149_595_240_516.6277;
-        let expected_r_a = 152_093_481_033.25537;
+        let expected_a = 149_595_240_516.627_7;
+        let expected_r_a = 152_093_481_033.255_37;
 
         assert_relative_eq!(ellipse.semi_major_axis().0, expected_a, epsilon = 1e-6);
         assert_relative_eq!(ellipse.apoapsis().0, expected_r_a, epsilon = 1e-6);
@@ -448,6 +1389,655 @@ mod tests {
         assert_relative_eq!(b, a * (1.0 - e * e).sqrt(), epsilon = 1e-10); // b = a√(1-e²)
     }
 
+    fn iss_like() -> KeplerianElements {
+        KeplerianElements::new(
+            Meters(6_778_000.0),
+            Eccentricity::new(0.0005).unwrap(),
+            Radians(0.9013),
+            Radians(2.0),
+            Radians(1.5),
+            Radians(0.25),
+            MetersCubedPerSecondSquared(3.986004418e14),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn elements_match_ellipse_derived_quantities() {
+        let elements = iss_like();
+        let ellipse = elements.ellipse();
+
+        assert_relative_eq!(
+            elements.semi_major_axis().0,
+            ellipse.semi_major_axis().0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            elements.semi_minor_axis().0,
+            ellipse.semi_minor_axis().0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(elements.apoapsis().0, ellipse.apoapsis().0, epsilon = 1e-6);
+        assert_relative_eq!(
+            elements.focal_distance().0,
+            ellipse.focal_distance().0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(elements.flattening(), ellipse.flattening(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn elements_round_trip_through_ellipse() {
+        let ellipse = Ellipse::from_periapsis_apoapsis(
+            Meters(6_578_000.0),
+            Meters(42_164_000.0),
+            Point {
+                x: Meters(0.0),
+                y: Meters(0.0),
+            },
+        );
+        let elements = KeplerianElements::from_ellipse(
+            &ellipse,
+            Radians(0.4974),
+            Radians(0.0),
+            Radians(PI),
+            Radians(0.0),
+            MetersCubedPerSecondSquared(3.986004418e14),
+        )
+        .unwrap();
+
+        assert_relative_eq!(elements.periapsis().0, 6_578_000.0, epsilon = 1e-6);
+        assert_relative_eq!(elements.apoapsis().0, 42_164_000.0, epsilon = 1e-6);
+        assert_eq!(elements.ellipse().periapsis(), elements.periapsis());
+    }
+
+    #[test]
+    fn elements_normalize_angles() {
+        let elements = KeplerianElements::new(
+            Meters(7_000_000.0),
+            Eccentricity::new(0.1).unwrap(),
+            Radians(0.5),
+            Radians(-PI / 2.0),
+            Radians(5.0 * PI),
+            Radians(-0.1),
+            MetersCubedPerSecondSquared(3.986004418e14),
+        )
+        .unwrap();
+
+        assert_relative_eq!(elements.raan().0, 1.5 * PI, epsilon = 1e-12);
+        assert_relative_eq!(elements.argument_of_periapsis().0, PI, epsilon = 1e-12);
+        assert_relative_eq!(elements.true_anomaly().0, 2.0 * PI - 0.1, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn elements_validation() {
+        let mu = MetersCubedPerSecondSquared(3.986004418e14);
+        let e = Eccentricity::new(0.1).unwrap();
+        let zero = Radians(0.0);
+
+        // Bound orbits need a positive semi-major axis
+        assert!(KeplerianElements::new(Meters(-7e6), e, zero, zero, zero, zero, mu).is_err());
+        // Inclination outside [0, π]
+        assert!(
+            KeplerianElements::new(Meters(7e6), e, Radians(-0.1), zero, zero, zero, mu).is_err()
+        );
+        assert!(
+            KeplerianElements::new(Meters(7e6), e, Radians(3.2), zero, zero, zero, mu).is_err()
+        );
+        // Non-positive μ
+        let bad_mu = MetersCubedPerSecondSquared(0.0);
+        assert!(KeplerianElements::new(Meters(7e6), e, zero, zero, zero, zero, bad_mu).is_err());
+        // Parabolic orbits have no finite semi-major axis
+        let parabolic = Eccentricity::new(1.0).unwrap();
+        assert!(
+            KeplerianElements::new(Meters(7e6), parabolic, zero, zero, zero, zero, mu).is_err()
+        );
+        // Hyperbolic orbits need a negative semi-major axis
+        let hyperbolic = Eccentricity::new(1.5).unwrap();
+        assert!(
+            KeplerianElements::new(Meters(7e6), hyperbolic, zero, zero, zero, zero, mu).is_err()
+        );
+        assert!(
+            KeplerianElements::new(Meters(-7e6), hyperbolic, zero, zero, zero, zero, mu).is_ok()
+        );
+        // ...and a true anomaly between the asymptotes
+        assert!(
+            KeplerianElements::new(Meters(-7e6), hyperbolic, zero, zero, zero, Radians(PI), mu)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn hyperbolic_elements_derived_quantities() {
+        let elements = KeplerianElements::new(
+            Meters(-10_000_000.0),
+            Eccentricity::new(1.5).unwrap(),
+            Radians(0.0),
+            Radians(0.0),
+            Radians(0.0),
+            Radians(0.0),
+            MetersCubedPerSecondSquared(3.986004418e14),
+        )
+        .unwrap();
+
+        assert!(!elements.is_bound());
+        assert_relative_eq!(elements.periapsis().0, 5_000_000.0, epsilon = 1e-6);
+        assert!(elements.apoapsis().0.is_infinite());
+        assert_relative_eq!(elements.focal_distance().0, 15_000_000.0, epsilon = 1e-6);
+        // c² = a² + b² for a hyperbola
+        let (a, b, c) = (
+            elements.semi_major_axis().0,
+            elements.semi_minor_axis().0,
+            elements.focal_distance().0,
+        );
+        assert_relative_eq!(c * c, a * a + b * b, epsilon = 1e-3);
+    }
+
+    const EARTH_MU: MetersCubedPerSecondSquared = MetersCubedPerSecondSquared(3.986004418e14);
+
+    fn assert_finite(elements: &KeplerianElements) {
+        assert!(elements.semi_major_axis().0.is_finite());
+        assert!(elements.eccentricity().value().is_finite());
+        assert!(elements.inclination().0.is_finite());
+        assert!(elements.raan().0.is_finite());
+        assert!(elements.argument_of_periapsis().0.is_finite());
+        assert!(elements.true_anomaly().0.is_finite());
+    }
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 2-5
+    #[test]
+    fn state_vector_to_elements_vallado() {
+        let state = StateVector::new(
+            Vector3::new(6_524_834.0, 6_862_875.0, 6_448_296.0),
+            Vector3::new(4_901.327, 5_533.756, -1_976.341),
+        );
+        let elements = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+        let deg = |r: Radians| r.0 * 180.0 / PI;
+
+        assert_relative_eq!(
+            elements.semi_latus_rectum().0,
+            11_067_790.0,
+            epsilon = 100.0
+        );
+        assert_relative_eq!(elements.semi_major_axis().0, 36_127_343.0, epsilon = 500.0);
+        assert_relative_eq!(elements.eccentricity().value(), 0.832853, epsilon = 1e-5);
+        assert_relative_eq!(deg(elements.inclination()), 87.870, epsilon = 1e-3);
+        assert_relative_eq!(deg(elements.raan()), 227.898, epsilon = 1e-3);
+        assert_relative_eq!(deg(elements.argument_of_periapsis()), 53.38, epsilon = 1e-2);
+        assert_relative_eq!(deg(elements.true_anomaly()), 92.335, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn elements_round_trip_through_state_vector() {
+        let elements = iss_like();
+        let state = elements.to_state_vector();
+        let recovered = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+
+        assert_relative_eq!(
+            recovered.semi_major_axis().0,
+            elements.semi_major_axis().0,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            recovered.eccentricity().value(),
+            elements.eccentricity().value(),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            recovered.inclination().0,
+            elements.inclination().0,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(recovered.raan().0, elements.raan().0, epsilon = 1e-10);
+        assert_relative_eq!(
+            recovered.argument_of_periapsis().0,
+            elements.argument_of_periapsis().0,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            recovered.true_anomaly().0,
+            elements.true_anomaly().0,
+            epsilon = 1e-8
+        );
+    }
+
+    #[test]
+    fn state_vector_speed_matches_vis_viva() {
+        let elements = iss_like();
+        let state = elements.to_state_vector();
+        let r = state.radius().0;
+        let a = elements.semi_major_axis().0;
+        let expected = (EARTH_MU.0 * (2.0 / r - 1.0 / a)).sqrt();
+        assert_relative_eq!(state.speed().0, expected, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn circular_inclined_state_has_no_nans() {
+        let r = 7_000_000.0;
+        let v = (EARTH_MU.0 / r).sqrt();
+        let (s, c) = (0.5_f64.sin(), 0.5_f64.cos());
+        let state = StateVector::new(Vector3::new(0.0, r * c, r * s), Vector3::new(-v, 0.0, 0.0));
+        let elements = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+
+        assert_finite(&elements);
+        assert_eq!(elements.argument_of_periapsis().0, 0.0);
+        assert_relative_eq!(elements.semi_major_axis().0, r, epsilon = 1e-3);
+        assert_states_close(&elements.to_state_vector(), &state, 1e-4, 1e-7);
+    }
+
+    #[test]
+    fn elliptic_equatorial_state_has_no_nans() {
+        for sign in [1.0, -1.0] {
+            // sign = -1 flips the orbit to retrograde
+            let state = StateVector::new(
+                Vector3::new(5_000_000.0, 4_000_000.0, 0.0),
+                Vector3::new(-5_000.0 * sign, 6_500.0 * sign, 0.0),
+            );
+            let elements = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+
+            assert_finite(&elements);
+            assert_eq!(elements.raan().0, 0.0);
+            assert!(elements.eccentricity().value() > 1e-3);
+            assert_states_close(&elements.to_state_vector(), &state, 1e-4, 1e-7);
+        }
+    }
+
+    #[test]
+    fn circular_equatorial_state_has_no_nans() {
+        let r = 42_164_000.0;
+        let v = (EARTH_MU.0 / r).sqrt();
+        for sign in [1.0, -1.0] {
+            let state = StateVector::new(
+                Vector3::new(r * 0.6, r * 0.8, 0.0),
+                Vector3::new(-v * 0.8 * sign, v * 0.6 * sign, 0.0),
+            );
+            let elements = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+
+            assert_finite(&elements);
+            assert_eq!(elements.raan().0, 0.0);
+            assert_eq!(elements.argument_of_periapsis().0, 0.0);
+            assert_states_close(&elements.to_state_vector(), &state, 1e-4, 1e-7);
+        }
+    }
+
+    #[test]
+    fn hyperbolic_state_round_trip() {
+        let state = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(1_000.0, 12_000.0, 3_000.0),
+        );
+        let elements = KeplerianElements::from_state_vector(&state, EARTH_MU).unwrap();
+
+        assert!(!elements.is_bound());
+        assert!(elements.semi_major_axis().0 < 0.0);
+        assert_states_close(&elements.to_state_vector(), &state, 1e-4, 1e-7);
+    }
+
+    #[test]
+    fn degenerate_state_vectors_are_rejected() {
+        let at_center = StateVector::new(Vector3::ZERO, Vector3::new(0.0, 7_000.0, 0.0));
+        assert!(KeplerianElements::from_state_vector(&at_center, EARTH_MU).is_err());
+
+        let radial = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(1_000.0, 0.0, 0.0),
+        );
+        assert!(KeplerianElements::from_state_vector(&radial, EARTH_MU).is_err());
+    }
+
+    #[test]
+    fn elements_report_mean_anomaly() {
+        let e = Eccentricity::new(0.4).unwrap();
+        let nu = anomaly::eccentric_to_true(Radians(220.512074 * PI / 180.0), e).unwrap();
+        let elements = KeplerianElements::new(
+            Meters(7_000_000.0),
+            e,
+            Radians(0.1),
+            Radians(0.0),
+            Radians(0.0),
+            nu,
+            EARTH_MU,
+        )
+        .unwrap();
+        assert_relative_eq!(
+            elements.mean_anomaly().unwrap().0 * 180.0 / PI,
+            235.4,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn ellipse_period_matches_elements_period() {
+        let elements = iss_like();
+        let from_ellipse = elements.ellipse().period(EARTH_MU).unwrap();
+        let from_elements = elements.period().unwrap();
+        assert_eq!(from_ellipse, from_elements);
+
+        // ~92.6 minutes for an ISS-like orbit
+        assert_relative_eq!(from_elements.0, 5_553.0, epsilon = 5.0);
+        assert_relative_eq!(
+            elements.mean_motion().0 * from_elements.0,
+            TAU,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn unbound_orbits_have_no_period() {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(1.0).unwrap(),
+            Point {
+                x: Meters(0.0),
+                y: Meters(0.0),
+            },
+            Meters(7e6),
+        );
+        assert!(ellipse.period(EARTH_MU).is_err());
+    }
+
+    #[test]
+    fn ellipse_points_along_orbit() {
+        let f = Point {
+            x: Meters(1_000.0),
+            y: Meters(-2_000.0),
+        };
+        let ellipse =
+            Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(21_000_000.0), f);
+
+        assert_relative_eq!(
+            ellipse.radius_at(Radians::ZERO).0,
+            7_000_000.0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            ellipse.radius_at(Radians(PI)).0,
+            21_000_000.0,
+            epsilon = 1e-6
+        );
+
+        let periapsis = ellipse.point_at(Radians::ZERO);
+        assert_relative_eq!(periapsis.x.0, 7_001_000.0, epsilon = 1e-6);
+        assert_relative_eq!(periapsis.y.0, -2_000.0, epsilon = 1e-6);
+
+        // The latus rectum passes through the focus, perpendicular to the major axis
+        let latus = ellipse.point_at(Radians(PI / 2.0));
+        assert_relative_eq!(latus.x.0, f.x.0, epsilon = 1e-6);
+        assert_relative_eq!(
+            latus.y.0,
+            f.y.0 + ellipse.semi_latus_rectum().0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(ellipse.semi_latus_rectum().0, 10_500_000.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn ellipse_velocity_components_match_state_vector() {
+        let ellipse = iss_like().ellipse();
+        for degrees in [0.0, 45.0, 135.0, 180.0, 270.0] {
+            let nu: Radians = Degrees(degrees).into();
+            let state = KeplerianElements::from_ellipse(
+                &ellipse,
+                Radians::ZERO,
+                Radians::ZERO,
+                Radians::ZERO,
+                nu,
+                EARTH_MU,
+            )
+            .unwrap()
+            .to_state_vector();
+
+            let radial = ellipse.radial_velocity(nu, EARTH_MU).0;
+            let tangential = ellipse.tangential_velocity(nu, EARTH_MU).0;
+            let speed = ellipse.speed_at(nu, EARTH_MU).0;
+
+            assert_relative_eq!(state.radius().0, ellipse.radius_at(nu).0, epsilon = 1e-6);
+            assert_relative_eq!(state.speed().0, speed, epsilon = 1e-8);
+            assert_relative_eq!(
+                radial * radial + tangential * tangential,
+                speed * speed,
+                epsilon = 1e-6
+            );
+            assert_relative_eq!(
+                state.position.dot(&state.velocity) / state.radius().0,
+                radial,
+                epsilon = 1e-8
+            );
+            assert_relative_eq!(
+                ellipse.flight_path_angle(nu).0,
+                libm::atan2(radial, tangential),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn flight_path_angle_is_zero_at_apsides() {
+        let ellipse =
+            Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(42_000_000.0), origin());
+        assert_relative_eq!(ellipse.flight_path_angle(Radians::ZERO).0, 0.0);
+        assert_relative_eq!(
+            ellipse.flight_path_angle(Radians(PI)).0,
+            0.0,
+            epsilon = 1e-15
+        );
+        assert!(ellipse.flight_path_angle(Radians(PI / 2.0)).0 > 0.0);
+        assert!(ellipse.flight_path_angle(Radians(3.0 * PI / 2.0)).0 < 0.0);
+    }
+
+    #[test]
+    fn invariants_agree_across_representations() {
+        use crate::celestials::celestial_bodies::EARTH;
+
+        let elements = iss_like();
+        let ellipse = elements.ellipse();
+        let state = elements.to_state_vector();
+
+        let energy = elements.specific_energy().value();
+        assert_relative_eq!(
+            ellipse.specific_energy(&EARTH).value(),
+            energy,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            state.specific_energy(&EARTH).value(),
+            energy,
+            max_relative = 1e-10
+        );
+
+        let h = elements.specific_angular_momentum().value();
+        assert_relative_eq!(
+            ellipse.specific_angular_momentum(&EARTH).value(),
+            h,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            state.specific_angular_momentum().value(),
+            h,
+            max_relative = 1e-10
+        );
+
+        assert_relative_eq!(
+            state.semi_latus_rectum(&EARTH).value(),
+            elements.semi_latus_rectum().value(),
+            max_relative = 1e-10
+        );
+
+        let e_vec = state.eccentricity_vector(&EARTH);
+        let expected = elements.eccentricity_vector();
+        assert_relative_eq!(e_vec.x, expected.x, epsilon = 1e-9);
+        assert_relative_eq!(e_vec.y, expected.y, epsilon = 1e-9);
+        assert_relative_eq!(e_vec.z, expected.z, epsilon = 1e-9);
+
+        // h is normal to the orbit plane, so e lies within it
+        assert_relative_eq!(state.angular_momentum().dot(&e_vec), 0.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn energy_sign_follows_conic_type() {
+        use crate::celestials::celestial_bodies::EARTH;
+
+        let r = 7_000_000.0;
+        let v_circular = sqrt(EARTH.mu.value() / r);
+        let state =
+            |v: Real| StateVector::new(Vector3::new(r, 0.0, 0.0), Vector3::new(0.0, v, 0.0));
+
+        assert!(state(v_circular).specific_energy(&EARTH).value() < 0.0);
+        assert_relative_eq!(
+            state(v_circular * libm::sqrt(2.0))
+                .specific_energy(&EARTH)
+                .value(),
+            0.0,
+            epsilon = 1e-6
+        );
+        assert!(state(v_circular * 1.5).specific_energy(&EARTH).value() > 0.0);
+        assert!(state(v_circular).eccentricity_vector(&EARTH).norm() < 1e-12);
+    }
+
+    fn origin() -> Point {
+        Point {
+            x: Meters(0.0),
+            y: Meters(0.0),
+        }
+    }
+
+    #[test]
+    fn conic_classifies_by_eccentricity() {
+        let e = |v| Eccentricity::new(v).unwrap();
+        assert!(matches!(
+            Conic::new(e(0.0), origin(), Meters(7e6)).unwrap(),
+            Conic::Ellipse(_)
+        ));
+        assert!(matches!(
+            Conic::new(e(0.99), origin(), Meters(7e6)).unwrap(),
+            Conic::Ellipse(_)
+        ));
+        assert!(matches!(
+            Conic::new(e(1.0), origin(), Meters(7e6)).unwrap(),
+            Conic::Parabola(_)
+        ));
+        assert!(matches!(
+            Conic::new(e(1.01), origin(), Meters(7e6)).unwrap(),
+            Conic::Hyperbola(_)
+        ));
+        assert!(Conic::new(e(0.5), origin(), Meters(0.0)).is_err());
+        assert!(
+            Conic::new(e(1.0), origin(), Meters(7e6))
+                .unwrap()
+                .semi_latus_rectum()
+                == Meters(14e6)
+        );
+    }
+
+    #[test]
+    fn hyperbola_geometry() {
+        let hyperbola = Hyperbola::new(
+            Eccentricity::new(2.0).unwrap(),
+            origin(),
+            Meters(7_000_000.0),
+        )
+        .unwrap();
+
+        // a = r_p / (1 - e) = -7000 km
+        assert_relative_eq!(hyperbola.semi_major_axis().0, -7_000_000.0, epsilon = 1e-6);
+        // c² = a² + b² for a hyperbola
+        let (a, b, c) = (
+            hyperbola.semi_major_axis().0,
+            hyperbola.semi_minor_axis().0,
+            hyperbola.focal_distance().0,
+        );
+        assert_relative_eq!(c * c, a * a + b * b, max_relative = 1e-12);
+        // e = 2 gives ν∞ = 120° and δ = 60°
+        assert_relative_eq!(
+            hyperbola.asymptote_true_anomaly().0,
+            2.0 * PI / 3.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(hyperbola.turning_angle().0, PI / 3.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn hyperbola_asymptotes_span_turning_angle() {
+        for e_val in [1.01, 1.5, 3.0, 20.0] {
+            let hyperbola =
+                Hyperbola::new(Eccentricity::new(e_val).unwrap(), origin(), Meters(7e6)).unwrap();
+            let incoming = hyperbola.incoming_asymptote();
+            let outgoing = hyperbola.outgoing_asymptote();
+
+            assert_relative_eq!(incoming.norm(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(outgoing.norm(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(
+                libm::acos(incoming.dot(&outgoing)),
+                hyperbola.turning_angle().0,
+                epsilon = 1e-7
+            );
+            // Outgoing asymptote points along ν∞
+            let nu_inf = hyperbola.asymptote_true_anomaly().0;
+            assert_relative_eq!(outgoing.x, libm::cos(nu_inf), epsilon = 1e-12);
+            assert_relative_eq!(outgoing.y, libm::sin(nu_inf), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn hyperbola_excess_velocity_round_trip() {
+        let v_inf = MetersPerSecond(2_945.0);
+        let r_p = Meters(6_678_000.0);
+        let hyperbola = Hyperbola::from_excess_velocity(v_inf, r_p, origin(), EARTH_MU).unwrap();
+
+        assert_relative_eq!(
+            hyperbola.hyperbolic_excess_velocity(EARTH_MU).0,
+            v_inf.0,
+            epsilon = 1e-6
+        );
+        assert_eq!(hyperbola.periapsis(), r_p);
+
+        // Vis-viva at periapsis: v² = v∞² + v_esc²
+        let v_esc = Parabola::new(origin(), r_p)
+            .unwrap()
+            .periapsis_speed(EARTH_MU)
+            .0;
+        assert_relative_eq!(
+            hyperbola.periapsis_speed(EARTH_MU).0,
+            (v_inf.0 * v_inf.0 + v_esc * v_esc).sqrt(),
+            epsilon = 1e-6
+        );
+
+        assert!(
+            Hyperbola::from_excess_velocity(MetersPerSecond(0.0), r_p, origin(), EARTH_MU).is_err()
+        );
+    }
+
+    #[test]
+    fn parabola_and_hyperbola_validation() {
+        assert!(Parabola::new(origin(), Meters(-1.0)).is_err());
+        assert!(Hyperbola::new(Eccentricity::new(1.0).unwrap(), origin(), Meters(7e6)).is_err());
+        assert!(Hyperbola::new(Eccentricity::new(0.5).unwrap(), origin(), Meters(7e6)).is_err());
+        assert!(Hyperbola::new(Eccentricity::new(1.5).unwrap(), origin(), Meters(0.0)).is_err());
+    }
+
+    #[test]
+    fn elements_expose_conic() {
+        assert!(matches!(iss_like().conic(), Conic::Ellipse(_)));
+
+        let escape = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(0.0, 12_000.0, 0.0),
+        );
+        let elements = KeplerianElements::from_state_vector(&escape, EARTH_MU).unwrap();
+        match elements.conic() {
+            Conic::Hyperbola(h) => {
+                assert_relative_eq!(h.periapsis().0, 7_000_000.0, epsilon = 1e-3);
+                // Energy: v∞² = v² - 2μ/r
+                let v_inf_sq = 12_000.0 * 12_000.0 - 2.0 * EARTH_MU.0 / 7_000_000.0;
+                assert_relative_eq!(
+                    h.hyperbolic_excess_velocity(EARTH_MU).0,
+                    v_inf_sq.sqrt(),
+                    epsilon = 1e-6
+                );
+            }
+            other => panic!("Expected a hyperbola, got {other:?}"),
+        }
+    }
+
     // Property-based test helper
     #[test]
     fn test_eccentricity_bounds() {
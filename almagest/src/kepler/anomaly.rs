@@ -0,0 +1,339 @@
+//! # Anomaly Conversions and Kepler's Equation
+//!
+//! The position of a body along its orbit can be described by three angles,
+//! each measured from periapsis:
+//!
+//! - **True anomaly (ν)**: The geometric angle seen from the central body
+//! - **Eccentric anomaly (E)**, or **hyperbolic anomaly (H)**: An auxiliary
+//!   angle measured on the circle (or hyperbola) circumscribing the orbit
+//! - **Mean anomaly (M)**: An angle that grows uniformly with time, `M = n·(t - tₚ)`
+//!
+//! Mean anomaly is what a clock gives you; true anomaly is what a position
+//! gives you. Getting from one to the other means solving Kepler's equation,
+//! which has no closed-form solution:
+//!
+//! - Elliptic: `M = E - e·sin(E)`
+//! - Hyperbolic: `M = e·sinh(H) - H`
+//!
+//! The solvers here use Newton-Raphson iteration with starting guesses that
+//! converge for every eccentricity, and rely only on `libm` so they work in
+//! `no_std` environments.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::kepler::anomaly::{mean_to_true, true_to_mean};
+//! use almagest::utils::{Eccentricity, Radians};
+//!
+//! let e = Eccentricity::new(0.3).unwrap();
+//! let nu = mean_to_true(Radians(1.0), e).expect("Converges");
+//! let m = true_to_mean(nu, e).expect("Valid anomaly");
+//! assert!((m.value() - 1.0).abs() < 1e-12);
+//! ```
+
+use libm::{asinh, atan2, cbrt, cos, cosh, fabs, fmin, sin, sinh, sqrt};
+
+use crate::utils::{Eccentricity, PI, Radians, Real};
+
+/// Convergence tolerance on the anomaly, in radians
+const TOLERANCE: Real = 1e-14;
+/// Newton iterations allowed before giving up
+const MAX_ITERATIONS: usize = 50;
+
+/// Solve Kepler's equation `M = E - e·sin(E)` for the eccentric anomaly.
+///
+/// Valid for bound orbits (0 ≤ e < 1). The result is wrapped into [0, 2π).
+///
+/// # Example
+/// ```
+/// use almagest::kepler::anomaly::mean_to_eccentric;
+/// use almagest::utils::{Eccentricity, Radians};
+///
+/// let e = Eccentricity::new(0.1).unwrap();
+/// let ecc_anomaly = mean_to_eccentric(Radians(0.5), e).expect("Converges");
+/// assert!((ecc_anomaly.value() - 0.5525).abs() < 1e-4);
+/// ```
+pub fn mean_to_eccentric(m: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = elliptic(e)?;
+    if !m.value().is_finite() {
+        return Err("Mean anomaly must be finite");
+    }
+
+    // Solve on [-π, π) where the starting guess is well behaved
+    let m = m.normalized().value();
+    let m = if m >= PI { m - 2.0 * PI } else { m };
+
+    // Danby's starting guess converges for all 0 ≤ e < 1
+    let mut ecc_anomaly = m + 0.85 * e * sign(sin(m));
+    for _ in 0..MAX_ITERATIONS {
+        let f = ecc_anomaly - e * sin(ecc_anomaly) - m;
+        let f_prime = 1.0 - e * cos(ecc_anomaly);
+        let step = f / f_prime;
+        ecc_anomaly -= step;
+        if fabs(step) < TOLERANCE {
+            return Ok(Radians(ecc_anomaly).normalized());
+        }
+    }
+    Err("Kepler's equation failed to converge")
+}
+
+/// Mean anomaly from eccentric anomaly, `M = E - e·sin(E)`.
+pub fn eccentric_to_mean(ecc_anomaly: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = elliptic(e)?;
+    let ecc_anomaly = ecc_anomaly.value();
+    Ok(Radians(ecc_anomaly - e * sin(ecc_anomaly)).normalized())
+}
+
+/// True anomaly from eccentric anomaly.
+///
+/// `tan(ν/2) = √((1 + e)/(1 - e))·tan(E/2)`
+pub fn eccentric_to_true(ecc_anomaly: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = elliptic(e)?;
+    let half = ecc_anomaly.value() / 2.0;
+    let nu = 2.0 * atan2(sqrt(1.0 + e) * sin(half), sqrt(1.0 - e) * cos(half));
+    Ok(Radians(nu).normalized())
+}
+
+/// Eccentric anomaly from true anomaly.
+///
+/// `tan(E/2) = √((1 - e)/(1 + e))·tan(ν/2)`
+pub fn true_to_eccentric(nu: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = elliptic(e)?;
+    let half = nu.value() / 2.0;
+    let ecc_anomaly = 2.0 * atan2(sqrt(1.0 - e) * sin(half), sqrt(1.0 + e) * cos(half));
+    Ok(Radians(ecc_anomaly).normalized())
+}
+
+/// Solve the hyperbolic Kepler equation `M = e·sinh(H) - H` for the
+/// hyperbolic anomaly.
+///
+/// Valid for escape trajectories (e > 1). Unlike the elliptic case the mean
+/// anomaly is not periodic, so no wrapping is applied.
+///
+/// # Example
+/// ```
+/// use almagest::kepler::anomaly::{hyperbolic_to_mean, mean_to_hyperbolic};
+/// use almagest::utils::{Eccentricity, Radians};
+///
+/// let e = Eccentricity::new(2.0).unwrap();
+/// let h = mean_to_hyperbolic(Radians(10.0), e).expect("Converges");
+/// let m = hyperbolic_to_mean(h, e).expect("Valid anomaly");
+/// assert!((m.value() - 10.0).abs() < 1e-10);
+/// ```
+pub fn mean_to_hyperbolic(m: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = hyperbolic(e)?;
+    let m = m.value();
+    if !m.is_finite() {
+        return Err("Mean anomaly must be finite");
+    }
+
+    // Both candidates bound the root from above, and f(H) is convex on that
+    // side, so Newton converges monotonically without overshooting:
+    // e·sinh(H) - H ≥ e·H³/6 and e·sinh(H) - H ≥ (e - 1)·sinh(H)
+    let m_abs = fabs(m);
+    let mut h = sign(m) * fmin(cbrt(6.0 * m_abs / e), asinh(m_abs / (e - 1.0)));
+
+    for _ in 0..MAX_ITERATIONS {
+        let f = e * sinh(h) - h - m;
+        let f_prime = e * cosh(h) - 1.0;
+        let step = f / f_prime;
+        h -= step;
+        if fabs(step) < TOLERANCE * (1.0 + fabs(h)) {
+            return Ok(Radians(h));
+        }
+    }
+    Err("Hyperbolic Kepler's equation failed to converge")
+}
+
+/// Mean anomaly from hyperbolic anomaly, `M = e·sinh(H) - H`.
+pub fn hyperbolic_to_mean(h: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = hyperbolic(e)?;
+    Ok(Radians(e * sinh(h.value()) - h.value()))
+}
+
+/// True anomaly from hyperbolic anomaly.
+///
+/// `tan(ν/2) = √((e + 1)/(e - 1))·tanh(H/2)`
+pub fn hyperbolic_to_true(h: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = hyperbolic(e)?;
+    let half = h.value() / 2.0;
+    // tanh(H/2) = sinh(H/2) / cosh(H/2), kept as a ratio for atan2
+    let nu = 2.0 * atan2(sqrt(e + 1.0) * sinh(half), sqrt(e - 1.0) * cosh(half));
+    Ok(Radians(nu))
+}
+
+/// Hyperbolic anomaly from true anomaly.
+///
+/// The true anomaly must lie strictly between the asymptotes,
+/// `|ν| < arccos(-1/e)`.
+pub fn true_to_hyperbolic(nu: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    let e = hyperbolic(e)?;
+    let nu = signed(nu.value());
+    if 1.0 + e * cos(nu) <= 0.0 {
+        return Err("True anomaly lies beyond the hyperbolic asymptotes");
+    }
+    let half_tan = sqrt((e - 1.0) / (e + 1.0)) * libm::tan(nu / 2.0);
+    Ok(Radians(2.0 * libm::atanh(half_tan)))
+}
+
+/// True anomaly from mean anomaly for elliptic or hyperbolic orbits.
+///
+/// Dispatches to the elliptic or hyperbolic form of Kepler's equation
+/// depending on the eccentricity. Parabolic orbits are not supported.
+pub fn mean_to_true(m: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    if e.value() < 1.0 {
+        eccentric_to_true(mean_to_eccentric(m, e)?, e)
+    } else {
+        hyperbolic_to_true(mean_to_hyperbolic(m, e)?, e)
+    }
+}
+
+/// Mean anomaly from true anomaly for elliptic or hyperbolic orbits.
+///
+/// For hyperbolic orbits the result is negative before periapsis passage.
+pub fn true_to_mean(nu: Radians, e: Eccentricity) -> Result<Radians, &'static str> {
+    if e.value() < 1.0 {
+        eccentric_to_mean(true_to_eccentric(nu, e)?, e)
+    } else {
+        hyperbolic_to_mean(true_to_hyperbolic(nu, e)?, e)
+    }
+}
+
+fn elliptic(e: Eccentricity) -> Result<Real, &'static str> {
+    let e = e.value();
+    if e < 1.0 {
+        Ok(e)
+    } else {
+        Err("Eccentric anomaly is only defined for elliptic orbits (e < 1)")
+    }
+}
+
+fn hyperbolic(e: Eccentricity) -> Result<Real, &'static str> {
+    let e = e.value();
+    if e > 1.0 && e.is_finite() {
+        Ok(e)
+    } else {
+        Err("Hyperbolic anomaly is only defined for hyperbolic orbits (e > 1)")
+    }
+}
+
+/// Wrap an angle into [-π, π)
+fn signed(angle: Real) -> Real {
+    let wrapped = Radians(angle).normalized().value();
+    if wrapped >= PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+fn sign(x: Real) -> Real {
+    if x < 0.0 { -1.0 } else { 1.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn ecc(e: Real) -> Eccentricity {
+        Eccentricity::new(e).unwrap()
+    }
+
+    // Vallado, Example 2-1: M = 235.4°, e = 0.4 gives E = 220.512074°
+    #[test]
+    fn solves_vallado_example() {
+        let m = Radians(235.4 * PI / 180.0);
+        let ecc_anomaly = mean_to_eccentric(m, ecc(0.4)).unwrap();
+        assert_relative_eq!(ecc_anomaly.0 * 180.0 / PI, 220.512074, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn circular_orbit_anomalies_coincide() {
+        let e = ecc(0.0);
+        for m in [0.0, 0.5, 2.0, 4.0, 6.0] {
+            let nu = mean_to_true(Radians(m), e).unwrap();
+            assert_relative_eq!(nu.0, m, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn elliptic_round_trip_across_eccentricities() {
+        for e_val in [0.0, 0.01, 0.3, 0.7, 0.9, 0.99, 0.999_999] {
+            let e = ecc(e_val);
+            for i in 0..36 {
+                let m = Radians(i as Real * PI / 18.0);
+                let ecc_anomaly = mean_to_eccentric(m, e).unwrap();
+                // Kepler's equation is satisfied
+                let residual = ecc_anomaly.0 - e_val * sin(ecc_anomaly.0) - m.0;
+                assert!(fabs(signed(residual)) < 1e-12, "e = {e_val}, M = {}", m.0);
+
+                let nu = eccentric_to_true(ecc_anomaly, e).unwrap();
+                let back = true_to_mean(nu, e).unwrap();
+                assert!(
+                    fabs(signed(back.0 - m.0)) < 1e-9,
+                    "e = {e_val}, M = {}",
+                    m.0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mean_anomaly_wraps_revolutions() {
+        let e = ecc(0.5);
+        let one = mean_to_eccentric(Radians(1.0), e).unwrap();
+        let three_revs = mean_to_eccentric(Radians(1.0 + 6.0 * PI), e).unwrap();
+        let negative = mean_to_eccentric(Radians(1.0 - 4.0 * PI), e).unwrap();
+        assert_relative_eq!(one.0, three_revs.0, epsilon = 1e-10);
+        assert_relative_eq!(one.0, negative.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn periapsis_and_apoapsis_are_fixed_points() {
+        let e = ecc(0.6);
+        assert_relative_eq!(
+            mean_to_true(Radians(0.0), e).unwrap().0,
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(mean_to_true(Radians(PI), e).unwrap().0, PI, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn hyperbolic_round_trip() {
+        for e_val in [1.000_1, 1.1, 1.5, 2.0, 5.0, 50.0] {
+            let e = ecc(e_val);
+            for m in [-100.0, -10.0, -1.0, -0.01, 0.0, 0.01, 1.0, 10.0, 100.0] {
+                let h = mean_to_hyperbolic(Radians(m), e).unwrap();
+                let residual = e_val * sinh(h.0) - h.0 - m;
+                assert!(
+                    fabs(residual) < 1e-9 * (1.0 + fabs(m)),
+                    "e = {e_val}, M = {m}"
+                );
+
+                let nu = hyperbolic_to_true(h, e).unwrap();
+                let back = true_to_mean(nu, e).unwrap();
+                assert_relative_eq!(back.0, m, epsilon = 1e-8 * (1.0 + fabs(m)));
+            }
+        }
+    }
+
+    #[test]
+    fn hyperbolic_true_anomaly_stays_within_asymptotes() {
+        let e = ecc(1.5);
+        let limit = libm::acos(-1.0 / 1.5);
+        let nu = mean_to_true(Radians(1.0e6), e).unwrap();
+        assert!(nu.0 < limit && nu.0 > 0.0);
+        assert!(true_to_hyperbolic(Radians(limit + 0.01), e).is_err());
+    }
+
+    #[test]
+    fn wrong_conic_is_rejected() {
+        assert!(mean_to_eccentric(Radians(1.0), ecc(1.0)).is_err());
+        assert!(mean_to_eccentric(Radians(1.0), ecc(1.5)).is_err());
+        assert!(mean_to_hyperbolic(Radians(1.0), ecc(0.5)).is_err());
+        assert!(mean_to_hyperbolic(Radians(1.0), ecc(1.0)).is_err());
+        assert!(mean_to_eccentric(Radians(Real::NAN), ecc(0.5)).is_err());
+    }
+}
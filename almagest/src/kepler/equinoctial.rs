@@ -0,0 +1,706 @@
+//! # Equinoctial Elements
+//!
+//! Element sets that stay well defined for circular and equatorial orbits,
+//! where the classical argument of periapsis and ascending node lose their
+//! meaning. Rather than measuring angles from the node and the periapsis,
+//! both sets combine them into components of the eccentricity and node
+//! vectors and measure the body's position from a fixed reference direction.
+//!
+//! - [`EquinoctialElements`] (Broucke & Cefola): `a, h, k, p, q, λ`, with the
+//!   mean longitude `λ = M + ω + Ω`. Suited to averaged, secular theories;
+//!   bound orbits only.
+//! - [`ModifiedEquinoctialElements`] (Walker, Ireland & Owens):
+//!   `p, f, g, h, k, L`, with the true longitude `L = ν + ω + Ω`. Also valid
+//!   for parabolic and hyperbolic orbits, and the usual choice for
+//!   integrating perturbed motion.
+//!
+//! Both sets use the prograde formulation and are singular only for
+//! retrograde equatorial orbits (i = 180°).
+//!
+//! | Symbol | Equinoctial | Modified equinoctial |
+//! |--------|-------------|----------------------|
+//! | `e·cos(ω + Ω)` | `k` | `f` |
+//! | `e·sin(ω + Ω)` | `h` | `g` |
+//! | `tan(i/2)·cos Ω` | `q` | `h` |
+//! | `tan(i/2)·sin Ω` | `p` | `k` |
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::MOON;
+//! use almagest::kepler::KeplerianElements;
+//! use almagest::kepler::equinoctial::ModifiedEquinoctialElements;
+//! use almagest::utils::{Eccentricity, Meters, Radians};
+//!
+//! // A circular, equatorial lunar orbit: ω and Ω are meaningless here
+//! let orbit = KeplerianElements::new(
+//!     Meters(1_837_500.0),
+//!     Eccentricity::new(0.0).unwrap(),
+//!     Radians(0.0),
+//!     Radians(0.0),
+//!     Radians(0.0),
+//!     Radians(1.0),
+//!     MOON.mu,
+//! )
+//! .unwrap();
+//!
+//! let mee = ModifiedEquinoctialElements::from_keplerian(&orbit).unwrap();
+//! assert_eq!(mee.f(), 0.0);
+//! assert_eq!(mee.h(), 0.0);
+//! ```
+
+use libm::{atan, atan2, sincos, sqrt, tan};
+
+use super::anomaly::{mean_to_true, true_to_mean};
+use super::{KeplerianElements, SINGULARITY_TOLERANCE, StateVector};
+use crate::utils::{Eccentricity, Meters, MetersCubedPerSecondSquared, PI, Radians, Real, Vector3};
+
+/// Equinoctial elements with the mean longitude as the fast variable.
+///
+/// Defined for bound orbits only, since the mean longitude of an unbound
+/// orbit does not wrap around; use [`ModifiedEquinoctialElements`] for
+/// escape trajectories.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EquinoctialElements {
+    /// Semi-major axis
+    a: Meters,
+    /// `e·sin(ω + Ω)`
+    h: Real,
+    /// `e·cos(ω + Ω)`
+    k: Real,
+    /// `tan(i/2)·sin Ω`
+    p: Real,
+    /// `tan(i/2)·cos Ω`
+    q: Real,
+    /// Mean longitude, `λ = M + ω + Ω`
+    mean_longitude: Radians,
+    /// Standard gravitational parameter of the central body
+    mu: MetersCubedPerSecondSquared,
+}
+
+/// Modified equinoctial elements with the true longitude as the fast variable.
+///
+/// Built on the semi-latus rectum instead of the semi-major axis, so they
+/// remain finite for parabolic orbits as well.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModifiedEquinoctialElements {
+    /// Semi-latus rectum, `p = a(1 - e²)`
+    p: Meters,
+    /// `e·cos(ω + Ω)`
+    f: Real,
+    /// `e·sin(ω + Ω)`
+    g: Real,
+    /// `tan(i/2)·cos Ω`
+    h: Real,
+    /// `tan(i/2)·sin Ω`
+    k: Real,
+    /// True longitude, `L = ν + ω + Ω`
+    true_longitude: Radians,
+    /// Standard gravitational parameter of the central body
+    mu: MetersCubedPerSecondSquared,
+}
+
+impl EquinoctialElements {
+    pub fn new(
+        a: Meters,
+        h: Real,
+        k: Real,
+        p: Real,
+        q: Real,
+        mean_longitude: Radians,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        validate_mu(mu)?;
+        if ![a.value(), h, k, p, q, mean_longitude.value()]
+            .iter()
+            .all(|x| x.is_finite())
+        {
+            return Err("Equinoctial elements must be finite");
+        }
+        if h * h + k * k >= 1.0 {
+            return Err("Equinoctial elements are only defined for bound orbits");
+        }
+        if a.value() <= 0.0 {
+            return Err("Semi-major axis must be positive for bound orbits");
+        }
+        Ok(EquinoctialElements {
+            a,
+            h,
+            k,
+            p,
+            q,
+            mean_longitude: mean_longitude.normalized(),
+            mu,
+        })
+    }
+
+    /// Convert from classical elements.
+    ///
+    /// Fails only for retrograde equatorial orbits, where `tan(i/2)` is infinite.
+    pub fn from_keplerian(elements: &KeplerianElements) -> Result<Self, &'static str> {
+        let (e, tan_half_i) = (
+            elements.eccentricity().value(),
+            tan_half_inclination(elements)?,
+        );
+        let raan = elements.raan().value();
+        let lon_periapsis = raan + elements.argument_of_periapsis().value();
+        let (sin_w, cos_w) = sincos(lon_periapsis);
+        let (sin_o, cos_o) = sincos(raan);
+        let m = elements.mean_anomaly()?;
+        Self::new(
+            elements.semi_major_axis(),
+            e * sin_w,
+            e * cos_w,
+            tan_half_i * sin_o,
+            tan_half_i * cos_o,
+            Radians(m.value() + lon_periapsis),
+            elements.mu(),
+        )
+    }
+
+    /// Convert to classical elements, following the same conventions as
+    /// [`KeplerianElements::from_state_vector`] for circular and equatorial orbits.
+    pub fn to_keplerian(&self) -> Result<KeplerianElements, &'static str> {
+        let e = Eccentricity::new(self.eccentricity())?;
+        let (raan, argp) = node_and_periapsis(self.k, self.h, self.q, self.p);
+        let m = Radians(self.mean_longitude.value() - raan - argp);
+        let nu = mean_to_true(m, e)?;
+        KeplerianElements::new(
+            self.a,
+            e,
+            self.inclination(),
+            Radians(raan),
+            Radians(argp),
+            nu,
+            self.mu,
+        )
+    }
+
+    /// Convert from a Cartesian state about a body with parameter `mu`.
+    pub fn from_state_vector(
+        state: &StateVector,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        ModifiedEquinoctialElements::from_state_vector(state, mu)?.to_equinoctial()
+    }
+
+    /// Position and velocity in the inertial frame
+    pub fn to_state_vector(&self) -> Result<StateVector, &'static str> {
+        Ok(self.to_modified()?.to_state_vector())
+    }
+
+    /// The equivalent modified equinoctial set
+    pub fn to_modified(&self) -> Result<ModifiedEquinoctialElements, &'static str> {
+        let e2 = self.h * self.h + self.k * self.k;
+        let e = Eccentricity::new(sqrt(e2))?;
+        let lon_periapsis = atan2(self.h, self.k);
+        let nu = mean_to_true(Radians(self.mean_longitude.value() - lon_periapsis), e)?;
+        ModifiedEquinoctialElements::new(
+            Meters(self.a.value() * (1.0 - e2)),
+            self.k,
+            self.h,
+            self.q,
+            self.p,
+            Radians(nu.value() + lon_periapsis),
+            self.mu,
+        )
+    }
+
+    pub fn semi_major_axis(&self) -> Meters {
+        self.a
+    }
+
+    /// `e·sin(ω + Ω)`
+    pub fn h(&self) -> Real {
+        self.h
+    }
+
+    /// `e·cos(ω + Ω)`
+    pub fn k(&self) -> Real {
+        self.k
+    }
+
+    /// `tan(i/2)·sin Ω`
+    pub fn p(&self) -> Real {
+        self.p
+    }
+
+    /// `tan(i/2)·cos Ω`
+    pub fn q(&self) -> Real {
+        self.q
+    }
+
+    /// Mean longitude, `λ = M + ω + Ω`
+    pub fn mean_longitude(&self) -> Radians {
+        self.mean_longitude
+    }
+
+    pub fn mu(&self) -> MetersCubedPerSecondSquared {
+        self.mu
+    }
+
+    pub fn eccentricity(&self) -> Real {
+        sqrt(self.h * self.h + self.k * self.k)
+    }
+
+    pub fn inclination(&self) -> Radians {
+        Radians(2.0 * atan(sqrt(self.p * self.p + self.q * self.q)))
+    }
+}
+
+impl ModifiedEquinoctialElements {
+    pub fn new(
+        p: Meters,
+        f: Real,
+        g: Real,
+        h: Real,
+        k: Real,
+        true_longitude: Radians,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        validate_mu(mu)?;
+        if ![p.value(), f, g, h, k, true_longitude.value()]
+            .iter()
+            .all(|x| x.is_finite())
+        {
+            return Err("Equinoctial elements must be finite");
+        }
+        if p.value() <= 0.0 {
+            return Err("Semi-latus rectum must be positive");
+        }
+        let (sin_l, cos_l) = sincos(true_longitude.value());
+        if 1.0 + f * cos_l + g * sin_l <= 0.0 {
+            return Err("True longitude lies beyond the hyperbolic asymptotes");
+        }
+        Ok(ModifiedEquinoctialElements {
+            p,
+            f,
+            g,
+            h,
+            k,
+            true_longitude: true_longitude.normalized(),
+            mu,
+        })
+    }
+
+    /// Convert from classical elements.
+    ///
+    /// Fails only for retrograde equatorial orbits, where `tan(i/2)` is infinite.
+    pub fn from_keplerian(elements: &KeplerianElements) -> Result<Self, &'static str> {
+        let (e, tan_half_i) = (
+            elements.eccentricity().value(),
+            tan_half_inclination(elements)?,
+        );
+        let raan = elements.raan().value();
+        let lon_periapsis = raan + elements.argument_of_periapsis().value();
+        let (sin_w, cos_w) = sincos(lon_periapsis);
+        let (sin_o, cos_o) = sincos(raan);
+        Self::new(
+            elements.semi_latus_rectum(),
+            e * cos_w,
+            e * sin_w,
+            tan_half_i * cos_o,
+            tan_half_i * sin_o,
+            Radians(elements.true_anomaly().value() + lon_periapsis),
+            elements.mu(),
+        )
+    }
+
+    /// Convert to classical elements, following the same conventions as
+    /// [`KeplerianElements::from_state_vector`] for circular and equatorial
+    /// orbits. Parabolic orbits cannot be represented and are rejected.
+    pub fn to_keplerian(&self) -> Result<KeplerianElements, &'static str> {
+        let e2 = self.f * self.f + self.g * self.g;
+        let e = Eccentricity::new(sqrt(e2))?;
+        if libm::fabs(e.value() - 1.0) < SINGULARITY_TOLERANCE {
+            return Err("Parabolic orbits have no finite semi-major axis");
+        }
+        let (raan, argp) = node_and_periapsis(self.f, self.g, self.h, self.k);
+        KeplerianElements::new(
+            Meters(self.p.value() / (1.0 - e2)),
+            e,
+            self.inclination(),
+            Radians(raan),
+            Radians(argp),
+            Radians(self.true_longitude.value() - raan - argp),
+            self.mu,
+        )
+    }
+
+    /// Convert from a Cartesian state about a body with parameter `mu`.
+    ///
+    /// Works directly in the equinoctial frame, so no intermediate classical
+    /// angles are formed and circular or equatorial states lose no precision.
+    pub fn from_state_vector(
+        state: &StateVector,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<Self, &'static str> {
+        validate_mu(mu)?;
+        let r_vec = state.position;
+        let r = r_vec.norm();
+        if r == 0.0 || !r.is_finite() || !state.velocity.norm().is_finite() {
+            return Err("State vector must be finite and away from the central body");
+        }
+        let h_vec = state.angular_momentum();
+        let h_mag = h_vec.norm();
+        if h_mag <= SINGULARITY_TOLERANCE * r * state.velocity.norm() {
+            return Err("Rectilinear trajectories have no orbital plane");
+        }
+        let w_hat = h_vec / h_mag;
+        if 1.0 + w_hat.z <= SINGULARITY_TOLERANCE {
+            return Err("Retrograde equatorial orbits are singular in equinoctial elements");
+        }
+
+        let h = -w_hat.y / (1.0 + w_hat.z);
+        let k = w_hat.x / (1.0 + w_hat.z);
+        let (f_hat, g_hat) = equinoctial_frame(h, k);
+
+        let e_vec = state.eccentricity_vector_for(mu.value());
+        Self::new(
+            Meters(h_mag * h_mag / mu.value()),
+            e_vec.dot(&f_hat),
+            e_vec.dot(&g_hat),
+            h,
+            k,
+            Radians(atan2(r_vec.dot(&g_hat), r_vec.dot(&f_hat))),
+            mu,
+        )
+    }
+
+    /// Position and velocity in the inertial frame
+    pub fn to_state_vector(&self) -> StateVector {
+        let (f_hat, g_hat) = equinoctial_frame(self.h, self.k);
+        let (sin_l, cos_l) = sincos(self.true_longitude.value());
+        let p = self.p.value();
+        let r = p / (1.0 + self.f * cos_l + self.g * sin_l);
+        let scale = sqrt(self.mu.value() / p);
+
+        StateVector::new(
+            (f_hat * cos_l + g_hat * sin_l) * r,
+            (f_hat * -(sin_l + self.g) + g_hat * (cos_l + self.f)) * scale,
+        )
+    }
+
+    /// The equivalent equinoctial set; only defined for bound orbits
+    pub fn to_equinoctial(&self) -> Result<EquinoctialElements, &'static str> {
+        let e2 = self.f * self.f + self.g * self.g;
+        if e2 >= 1.0 {
+            return Err("Equinoctial elements are only defined for bound orbits");
+        }
+        let e = Eccentricity::new(sqrt(e2))?;
+        let lon_periapsis = atan2(self.g, self.f);
+        let m = true_to_mean(Radians(self.true_longitude.value() - lon_periapsis), e)?;
+        EquinoctialElements::new(
+            Meters(self.p.value() / (1.0 - e2)),
+            self.g,
+            self.f,
+            self.k,
+            self.h,
+            Radians(m.value() + lon_periapsis),
+            self.mu,
+        )
+    }
+
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.p
+    }
+
+    /// `e·cos(ω + Ω)`
+    pub fn f(&self) -> Real {
+        self.f
+    }
+
+    /// `e·sin(ω + Ω)`
+    pub fn g(&self) -> Real {
+        self.g
+    }
+
+    /// `tan(i/2)·cos Ω`
+    pub fn h(&self) -> Real {
+        self.h
+    }
+
+    /// `tan(i/2)·sin Ω`
+    pub fn k(&self) -> Real {
+        self.k
+    }
+
+    /// True longitude, `L = ν + ω + Ω`
+    pub fn true_longitude(&self) -> Radians {
+        self.true_longitude
+    }
+
+    pub fn mu(&self) -> MetersCubedPerSecondSquared {
+        self.mu
+    }
+
+    pub fn eccentricity(&self) -> Real {
+        sqrt(self.f * self.f + self.g * self.g)
+    }
+
+    pub fn inclination(&self) -> Radians {
+        Radians(2.0 * atan(sqrt(self.h * self.h + self.k * self.k)))
+    }
+}
+
+/// Unit vectors f̂ and ĝ spanning the orbit plane, for node components
+/// `h = tan(i/2)·cos Ω` and `k = tan(i/2)·sin Ω`
+fn equinoctial_frame(h: Real, k: Real) -> (Vector3, Vector3) {
+    let s2 = 1.0 + h * h + k * k;
+    let f_hat = Vector3::new(1.0 - k * k + h * h, 2.0 * h * k, -2.0 * k) / s2;
+    let g_hat = Vector3::new(2.0 * h * k, 1.0 + k * k - h * h, 2.0 * h) / s2;
+    (f_hat, g_hat)
+}
+
+/// Ω and ω from the eccentricity components (e·cos ϖ, e·sin ϖ) and node
+/// components (tan(i/2)·cos Ω, tan(i/2)·sin Ω), with the circular and
+/// equatorial conventions of [`KeplerianElements::from_state_vector`]
+fn node_and_periapsis(e_cos: Real, e_sin: Real, n_cos: Real, n_sin: Real) -> (Real, Real) {
+    let equatorial = sqrt(n_cos * n_cos + n_sin * n_sin) < SINGULARITY_TOLERANCE;
+    let circular = sqrt(e_cos * e_cos + e_sin * e_sin) < SINGULARITY_TOLERANCE;
+    let raan = if equatorial { 0.0 } else { atan2(n_sin, n_cos) };
+    let argp = if circular {
+        0.0
+    } else {
+        atan2(e_sin, e_cos) - raan
+    };
+    (raan, argp)
+}
+
+fn tan_half_inclination(elements: &KeplerianElements) -> Result<Real, &'static str> {
+    let i = elements.inclination().value();
+    if PI - i < SINGULARITY_TOLERANCE {
+        return Err("Retrograde equatorial orbits are singular in equinoctial elements");
+    }
+    Ok(tan(i / 2.0))
+}
+
+fn validate_mu(mu: MetersCubedPerSecondSquared) -> Result<(), &'static str> {
+    if mu.value() <= 0.0 || !mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MOON};
+    use crate::test_helpers::assert_states_close;
+    use crate::utils::Degrees;
+    use approx::assert_relative_eq;
+
+    fn elements(a: Real, e: Real, i: Real, raan: Real, argp: Real, nu: Real) -> KeplerianElements {
+        KeplerianElements::new(
+            Meters(a),
+            Eccentricity::new(e).unwrap(),
+            Degrees(i).into(),
+            Degrees(raan).into(),
+            Degrees(argp).into(),
+            Degrees(nu).into(),
+            EARTH.mu,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn matches_definitions() {
+        let kep = elements(7_000_000.0, 0.1, 30.0, 40.0, 60.0, 20.0);
+        let mee = ModifiedEquinoctialElements::from_keplerian(&kep).unwrap();
+        let eq = EquinoctialElements::from_keplerian(&kep).unwrap();
+        let (lon_peri, raan): (Radians, Radians) = (Degrees(100.0).into(), Degrees(40.0).into());
+        let tan_half_i = libm::tan(Radians::from(Degrees(15.0)).value());
+
+        assert_relative_eq!(
+            mee.semi_latus_rectum().value(),
+            7_000_000.0 * 0.99,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(mee.f(), 0.1 * libm::cos(lon_peri.value()), epsilon = 1e-15);
+        assert_relative_eq!(mee.g(), 0.1 * libm::sin(lon_peri.value()), epsilon = 1e-15);
+        assert_relative_eq!(
+            mee.h(),
+            tan_half_i * libm::cos(raan.value()),
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            mee.k(),
+            tan_half_i * libm::sin(raan.value()),
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            mee.true_longitude().value(),
+            Radians::from(Degrees(120.0)).value(),
+            epsilon = 1e-12
+        );
+
+        assert_eq!((eq.h(), eq.k()), (mee.g(), mee.f()));
+        assert_eq!((eq.p(), eq.q()), (mee.k(), mee.h()));
+        assert_relative_eq!(
+            eq.mean_longitude().value(),
+            kep.mean_anomaly().unwrap().value() + lon_peri.value(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            eq.inclination().value(),
+            kep.inclination().value(),
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn state_vectors_agree_with_keplerian() {
+        let cases = [
+            elements(7_000_000.0, 0.1, 30.0, 40.0, 60.0, 20.0),
+            elements(26_600_000.0, 0.74, 63.4, 250.0, 270.0, 190.0),
+            elements(42_164_000.0, 0.0, 0.0, 0.0, 0.0, 75.0),
+            elements(-20_000_000.0, 1.5, 120.0, 10.0, 300.0, 30.0),
+        ];
+        for kep in cases {
+            let expected = kep.to_state_vector();
+            let dr = 1e-7 * expected.radius().value();
+            let dv = 1e-10 * expected.speed().value();
+            let mee = ModifiedEquinoctialElements::from_keplerian(&kep).unwrap();
+            assert_states_close(&mee.to_state_vector(), &expected, dr, dv);
+
+            let from_state =
+                ModifiedEquinoctialElements::from_state_vector(&expected, EARTH.mu).unwrap();
+            assert_relative_eq!(from_state.f(), mee.f(), epsilon = 1e-9);
+            assert_relative_eq!(from_state.g(), mee.g(), epsilon = 1e-9);
+            assert_relative_eq!(from_state.h(), mee.h(), epsilon = 1e-9);
+            assert_relative_eq!(from_state.k(), mee.k(), epsilon = 1e-9);
+            assert_relative_eq!(
+                from_state.true_longitude().value(),
+                mee.true_longitude().value(),
+                epsilon = 1e-9
+            );
+            assert_states_close(
+                &from_state.to_keplerian().unwrap().to_state_vector(),
+                &expected,
+                dr,
+                dv,
+            );
+
+            if kep.is_bound() {
+                let eq = EquinoctialElements::from_state_vector(&expected, EARTH.mu).unwrap();
+                assert_states_close(&eq.to_state_vector().unwrap(), &expected, dr, dv);
+                assert_states_close(
+                    &eq.to_keplerian().unwrap().to_state_vector(),
+                    &expected,
+                    dr,
+                    dv,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn near_circular_near_equatorial_lunar_orbit_is_smooth() {
+        // A lunar tether facility orbit: e and i both tiny
+        let state = |nu: Real| {
+            KeplerianElements::new(
+                Meters(1_837_500.0),
+                Eccentricity::new(1e-9).unwrap(),
+                Radians(1e-10),
+                Radians(2.0),
+                Radians(1.0),
+                Radians(nu),
+                MOON.mu,
+            )
+            .unwrap()
+            .to_state_vector()
+        };
+        let before = ModifiedEquinoctialElements::from_state_vector(&state(0.5), MOON.mu).unwrap();
+        let after =
+            ModifiedEquinoctialElements::from_state_vector(&state(0.5001), MOON.mu).unwrap();
+
+        // Only the fast variable changes, and by exactly the anomaly step
+        assert_relative_eq!(
+            after.true_longitude().value() - before.true_longitude().value(),
+            1e-4,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            after.semi_latus_rectum().value(),
+            before.semi_latus_rectum().value(),
+            max_relative = 1e-9
+        );
+        assert!(before.eccentricity() < 1e-8);
+        assert!(before.inclination().value() < 1e-8);
+    }
+
+    #[test]
+    fn equatorial_circular_orbits_follow_keplerian_conventions() {
+        let kep = elements(42_164_000.0, 0.0, 0.0, 0.0, 0.0, 75.0);
+        let round_trip = ModifiedEquinoctialElements::from_keplerian(&kep)
+            .unwrap()
+            .to_keplerian()
+            .unwrap();
+        assert_eq!(round_trip.raan(), Radians::ZERO);
+        assert_eq!(round_trip.argument_of_periapsis(), Radians::ZERO);
+        assert_relative_eq!(
+            round_trip.true_anomaly().value(),
+            kep.true_anomaly().value(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn singular_and_invalid_cases_are_rejected() {
+        let retrograde_equatorial = elements(7_000_000.0, 0.1, 180.0, 0.0, 0.0, 0.0);
+        assert!(ModifiedEquinoctialElements::from_keplerian(&retrograde_equatorial).is_err());
+        assert!(EquinoctialElements::from_keplerian(&retrograde_equatorial).is_err());
+
+        let hyperbolic = elements(-20_000_000.0, 1.5, 30.0, 0.0, 0.0, 0.0);
+        assert!(EquinoctialElements::from_keplerian(&hyperbolic).is_err());
+        assert!(
+            ModifiedEquinoctialElements::from_keplerian(&hyperbolic)
+                .unwrap()
+                .to_equinoctial()
+                .is_err()
+        );
+
+        assert!(
+            ModifiedEquinoctialElements::new(
+                Meters(-1.0),
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                Radians::ZERO,
+                EARTH.mu
+            )
+            .is_err()
+        );
+        assert!(
+            EquinoctialElements::new(
+                Meters(7e6),
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                Radians::ZERO,
+                MetersCubedPerSecondSquared(0.0)
+            )
+            .is_err()
+        );
+
+        // A parabola is fine in modified elements but has no semi-major axis
+        let parabola = ModifiedEquinoctialElements::new(
+            Meters(7e6),
+            1.0,
+            0.0,
+            0.0,
+            0.0,
+            Radians(1.0),
+            EARTH.mu,
+        )
+        .unwrap();
+        assert!(parabola.to_keplerian().is_err());
+        assert_relative_eq!(
+            parabola.to_state_vector().speed().value(),
+            sqrt(2.0 * EARTH.mu.value() / parabola.to_state_vector().radius().value()),
+            max_relative = 1e-12
+        );
+    }
+}
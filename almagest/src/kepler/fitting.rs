@@ -0,0 +1,446 @@
+//! # Orbit Fitting
+//!
+//! Reconstruct an orbit from a set of sampled positions, such as simulation
+//! output or tracking data. Where [`calc_ecc`](super::calc_ecc) recovers an
+//! ellipse exactly from a single point and both foci, the fits here take any
+//! number of noisy samples around a known primary focus and find the ellipse
+//! that best explains them in a least-squares sense.
+//!
+//! Every point on a conic satisfies the focus-directrix relation
+//! `r = p - e·r⃗`, where `p` is the semi-latus rectum and `e` the
+//! eccentricity vector. That relation is linear in `p` and the two in-plane
+//! components of `e`, so the fit reduces to a 3×3 linear system with no
+//! iteration and no initial guess. Samples in 3D are first projected onto
+//! the best-fit plane through the focus.
+//!
+//! Samples should be in time order; the order decides the direction of
+//! motion, and with it the sign of the orbit normal.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::kepler::Point;
+//! use almagest::kepler::fitting::fit_orbit_2d;
+//! use almagest::utils::Meters;
+//!
+//! let samples = [
+//!     Point { x: Meters(7_000_000.0), y: Meters(0.0) },
+//!     Point { x: Meters(0.0), y: Meters(9_333_333.3) },
+//!     Point { x: Meters(-14_000_000.0), y: Meters(0.0) },
+//!     Point { x: Meters(0.0), y: Meters(-9_333_333.3) },
+//! ];
+//! let origin = Point { x: Meters(0.0), y: Meters(0.0) };
+//! let fit = fit_orbit_2d(&samples, origin).expect("Enough samples");
+//! println!("e = {}, rms residual = {} m", fit.ellipse.eccentricity().value(), fit.rms_residual.value());
+//! ```
+
+use libm::{atan2, cos, fabs, sqrt};
+
+use crate::kepler::{Ellipse, KeplerianElements, Point, StateVector};
+use crate::utils::{Eccentricity, Meters, MetersCubedPerSecondSquared, Radians, Real, Vector3};
+
+/// Below this eccentricity the periapsis direction is undefined and the
+/// first sample's direction is used instead
+const CIRCULAR_TOLERANCE: Real = 1e-12;
+
+/// The ellipse that best fits a set of position samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitFit {
+    /// Fitted ellipse, with its primary focus at the focus used for the fit
+    pub ellipse: Ellipse,
+    /// Unit vector from the focus toward periapsis
+    pub periapsis_direction: Vector3,
+    /// Unit normal to the orbit plane, along the angular momentum
+    pub normal: Vector3,
+    /// Root-mean-square residual over all samples
+    pub rms_residual: Meters,
+    /// Largest residual of any sample
+    pub max_residual: Meters,
+}
+
+impl OrbitFit {
+    /// Distance from a 3D sample to the fitted orbit.
+    ///
+    /// Combines the out-of-plane offset with the in-plane radial miss, which
+    /// closely approximates the true geometric distance for small residuals.
+    pub fn residual(&self, sample: &Vector3) -> Meters {
+        let f = self.ellipse.primary_focus();
+        let r_vec = *sample - Vector3::new(f.x.value(), f.y.value(), 0.0);
+        let out_of_plane = r_vec.dot(&self.normal);
+        let in_plane = r_vec - self.normal * out_of_plane;
+
+        let q_hat = self.normal.cross(&self.periapsis_direction);
+        let nu = atan2(
+            in_plane.dot(&q_hat),
+            in_plane.dot(&self.periapsis_direction),
+        );
+        let e = self.ellipse.eccentricity().value();
+        let r_model = self.ellipse.semi_latus_rectum().value() / (1.0 + e * cos(nu));
+        let radial = in_plane.norm() - r_model;
+
+        Meters(sqrt(radial * radial + out_of_plane * out_of_plane))
+    }
+
+    /// Distance from a 2D sample to the fitted orbit
+    pub fn residual_2d(&self, sample: &Point) -> Meters {
+        self.residual(&Vector3::new(sample.x.value(), sample.y.value(), 0.0))
+    }
+
+    /// Classical elements of the fitted orbit about a body with parameter
+    /// `mu`, with the body placed at periapsis.
+    ///
+    /// The orientation angles are measured from the x-y plane, so this is
+    /// only meaningful for fits whose focus is at the origin.
+    pub fn elements(
+        &self,
+        mu: MetersCubedPerSecondSquared,
+    ) -> Result<KeplerianElements, &'static str> {
+        let r_p = self.ellipse.periapsis();
+        let speed = self.ellipse.speed_at(Radians::ZERO, mu);
+        let state = StateVector::new(
+            self.periapsis_direction * r_p.value(),
+            self.normal.cross(&self.periapsis_direction) * speed.value(),
+        );
+        KeplerianElements::from_state_vector(&state, mu)
+    }
+}
+
+/// Fit an orbit to 2D position samples around a known focus.
+///
+/// # Arguments
+/// * `samples` - At least three positions, in time order
+/// * `focus` - Location of the central body
+///
+/// # Returns
+/// The best-fit ellipse and its residuals, or an error if there are too few
+/// samples, they are degenerate (e.g. collinear), or they describe an
+/// unbound trajectory.
+pub fn fit_orbit_2d(samples: &[Point], focus: Point) -> Result<OrbitFit, &'static str> {
+    let relative = samples.iter().map(|s| {
+        Vector3::new(
+            s.x.value() - focus.x.value(),
+            s.y.value() - focus.y.value(),
+            0.0,
+        )
+    });
+    let normal = if motion_direction(relative.clone()).z < 0.0 {
+        Vector3::new(0.0, 0.0, -1.0)
+    } else {
+        Vector3::new(0.0, 0.0, 1.0)
+    };
+    let fit = fit_in_plane(relative, normal, focus)?;
+
+    let mut sum = 0.0;
+    let mut max: Real = 0.0;
+    for sample in samples {
+        let residual = fit.residual_2d(sample).value();
+        sum += residual * residual;
+        max = max.max(residual);
+    }
+    Ok(with_residuals(fit, sum, max, samples.len()))
+}
+
+/// Fit an orbit to 3D position samples around a central body at the origin.
+///
+/// The orbit plane is the least-squares plane through the origin; the
+/// ellipse is then fitted to the samples projected onto it.
+///
+/// # Arguments
+/// * `samples` - At least three positions relative to the central body, in time order
+///
+/// # Returns
+/// The best-fit ellipse and its residuals, or an error if there are too few
+/// samples, they are degenerate, or they describe an unbound trajectory.
+pub fn fit_orbit_3d(samples: &[Vector3]) -> Result<OrbitFit, &'static str> {
+    if samples.len() < 3 {
+        return Err("At least three samples are needed to fit an orbit");
+    }
+    let mut normal = plane_normal(samples)?;
+    if normal.dot(&motion_direction(samples.iter().copied())) < 0.0 {
+        normal = -normal;
+    }
+    let origin = Point {
+        x: Meters(0.0),
+        y: Meters(0.0),
+    };
+    let fit = fit_in_plane(samples.iter().copied(), normal, origin)?;
+
+    let mut sum = 0.0;
+    let mut max: Real = 0.0;
+    for sample in samples {
+        let residual = fit.residual(sample).value();
+        sum += residual * residual;
+        max = max.max(residual);
+    }
+    Ok(with_residuals(fit, sum, max, samples.len()))
+}
+
+/// Solve `r = p - e·r⃗` in the plane normal to `normal` by linear least squares
+fn fit_in_plane<I>(samples: I, normal: Vector3, focus: Point) -> Result<OrbitFit, &'static str>
+where
+    I: Iterator<Item = Vector3> + Clone,
+{
+    // In-plane basis, anchored on the first sample that is away from the focus
+    let u = samples
+        .clone()
+        .map(|s| s - normal * s.dot(&normal))
+        .find(|s| s.norm() > 0.0)
+        .ok_or("Samples must not all coincide with the focus")?
+        .unit();
+    let w = normal.cross(&u);
+
+    // Normal equations for the unknowns (p, eᵤ, e_w) with rows [1, -x, -y]
+    let mut ata = [[0.0; 3]; 3];
+    let mut atb = [0.0; 3];
+    let mut count = 0;
+    for s in samples {
+        let (x, y) = (s.dot(&u), s.dot(&w));
+        let row = [1.0, -x, -y];
+        let r = sqrt(x * x + y * y);
+        for i in 0..3 {
+            for j in 0..3 {
+                ata[i][j] += row[i] * row[j];
+            }
+            atb[i] += row[i] * r;
+        }
+        count += 1;
+    }
+    if count < 3 {
+        return Err("At least three samples are needed to fit an orbit");
+    }
+
+    let [p, e_u, e_w] =
+        solve_3x3(&ata, &atb).ok_or("Samples are too degenerate to fit an orbit")?;
+    let e_vec = u * e_u + w * e_w;
+    let e = e_vec.norm();
+    if e >= 1.0 || p <= 0.0 {
+        return Err("Samples do not describe a closed orbit");
+    }
+    let periapsis_direction = if e < CIRCULAR_TOLERANCE { u } else { e_vec / e };
+
+    Ok(OrbitFit {
+        ellipse: Ellipse::new(Eccentricity::new(e)?, focus, Meters(p / (1.0 + e))),
+        periapsis_direction,
+        normal,
+        rms_residual: Meters(0.0),
+        max_residual: Meters(0.0),
+    })
+}
+
+fn with_residuals(fit: OrbitFit, sum_of_squares: Real, max: Real, n: usize) -> OrbitFit {
+    OrbitFit {
+        rms_residual: Meters(sqrt(sum_of_squares / n as Real)),
+        max_residual: Meters(max),
+        ..fit
+    }
+}
+
+/// Sum of successive cross products; points along the angular momentum
+/// when samples are in time order
+fn motion_direction<I: Iterator<Item = Vector3>>(mut samples: I) -> Vector3 {
+    let Some(mut previous) = samples.next() else {
+        return Vector3::ZERO;
+    };
+    let mut direction = Vector3::ZERO;
+    for s in samples {
+        direction = direction + previous.cross(&s);
+        previous = s;
+    }
+    direction
+}
+
+/// Unit normal of the least-squares plane through the origin.
+///
+/// Each row of the scatter matrix's adjugate is parallel to the eigenvector
+/// of the smallest eigenvalue; the largest row is the best conditioned.
+fn plane_normal(samples: &[Vector3]) -> Result<Vector3, &'static str> {
+    let (mut xx, mut xy, mut xz, mut yy, mut yz, mut zz) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for s in samples {
+        xx += s.x * s.x;
+        xy += s.x * s.y;
+        xz += s.x * s.z;
+        yy += s.y * s.y;
+        yz += s.y * s.z;
+        zz += s.z * s.z;
+    }
+    let candidates = [
+        Vector3::new(yy * zz - yz * yz, xz * yz - xy * zz, xy * yz - xz * yy),
+        Vector3::new(xz * yz - xy * zz, xx * zz - xz * xz, xy * xz - yz * xx),
+        Vector3::new(xy * yz - xz * yy, xy * xz - yz * xx, xx * yy - xy * xy),
+    ];
+    let best = candidates
+        .into_iter()
+        .max_by(|a, b| a.norm().total_cmp(&b.norm()))
+        .unwrap_or(Vector3::ZERO);
+
+    let scale = xx + yy + zz;
+    if best.norm() <= 1e-12 * scale * scale {
+        return Err("Samples must span a plane through the focus");
+    }
+    Ok(best.unit())
+}
+
+/// Cramer's rule for a symmetric 3×3 system
+fn solve_3x3(a: &[[Real; 3]; 3], b: &[Real; 3]) -> Option<[Real; 3]> {
+    let det = |m: &[[Real; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(a);
+    let scale = a[0][0] * a[1][1] * a[2][2];
+    if !d.is_finite() || fabs(d) <= 1e-12 * fabs(scale) {
+        return None;
+    }
+
+    let mut solution = [0.0; 3];
+    for (k, value) in solution.iter_mut().enumerate() {
+        let mut m = *a;
+        for i in 0..3 {
+            m[i][k] = b[i];
+        }
+        *value = det(&m) / d;
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::utils::{Degrees, TAU};
+    use approx::assert_relative_eq;
+
+    /// Deterministic noise in [-1, 1]
+    fn noise(k: usize) -> Real {
+        libm::sin(k as Real * 12.9898) * 0.5 + libm::sin(k as Real * 78.233) * 0.5
+    }
+
+    fn reference_orbit() -> KeplerianElements {
+        KeplerianElements::new(
+            Meters(12_000_000.0),
+            Eccentricity::new(0.3).unwrap(),
+            Degrees(40.0).into(),
+            Degrees(75.0).into(),
+            Degrees(130.0).into(),
+            Radians::ZERO,
+            EARTH.mu,
+        )
+        .unwrap()
+    }
+
+    fn samples_3d<const N: usize>(elements: &KeplerianElements, noise_m: Real) -> [Vector3; N] {
+        core::array::from_fn(|k| {
+            let nu = Radians(TAU * k as Real / N as Real);
+            let r = elements
+                .with_true_anomaly(nu)
+                .unwrap()
+                .to_state_vector()
+                .position;
+            r + Vector3::new(noise(3 * k), noise(3 * k + 1), noise(3 * k + 2)) * noise_m
+        })
+    }
+
+    #[test]
+    fn recovers_exact_2d_ellipse_around_offset_focus() {
+        let focus = Point {
+            x: Meters(500.0),
+            y: Meters(-250.0),
+        };
+        let truth =
+            Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(20_000_000.0), focus);
+        let samples: [Point; 12] =
+            core::array::from_fn(|k| truth.point_at(Radians(TAU * k as Real / 12.0)));
+
+        let fit = fit_orbit_2d(&samples, focus).unwrap();
+        assert_relative_eq!(
+            fit.ellipse.eccentricity().value(),
+            truth.eccentricity().value(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            fit.ellipse.periapsis().value(),
+            7_000_000.0,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(fit.periapsis_direction.x, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(fit.normal.z, 1.0);
+        assert!(fit.max_residual.value() < 1e-6);
+    }
+
+    #[test]
+    fn clockwise_samples_flip_the_normal() {
+        let origin = Point {
+            x: Meters(0.0),
+            y: Meters(0.0),
+        };
+        let truth =
+            Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(9_000_000.0), origin);
+        let samples: [Point; 8] =
+            core::array::from_fn(|k| truth.point_at(Radians(-TAU * k as Real / 8.0)));
+
+        let fit = fit_orbit_2d(&samples, origin).unwrap();
+        assert_relative_eq!(fit.normal.z, -1.0);
+        assert_relative_eq!(fit.periapsis_direction.x, 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn recovers_3d_orbit_from_noisy_samples() {
+        let truth = reference_orbit();
+        let samples: [Vector3; 60] = samples_3d(&truth, 1_000.0);
+
+        let fit = fit_orbit_3d(&samples).unwrap();
+        let elements = fit.elements(EARTH.mu).unwrap();
+
+        assert_relative_eq!(
+            elements.semi_major_axis().value(),
+            12_000_000.0,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(elements.eccentricity().value(), 0.3, epsilon = 1e-3);
+        assert_relative_eq!(
+            elements.inclination().value(),
+            truth.inclination().value(),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            elements.raan().value(),
+            truth.raan().value(),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            elements.argument_of_periapsis().value(),
+            truth.argument_of_periapsis().value(),
+            epsilon = 1e-3
+        );
+        // Residuals are on the order of the injected noise
+        assert!(fit.rms_residual.value() > 100.0);
+        assert!(fit.rms_residual.value() < 2_000.0);
+        assert!(fit.max_residual >= fit.rms_residual);
+    }
+
+    #[test]
+    fn exact_3d_samples_have_no_residual() {
+        let samples: [Vector3; 5] = samples_3d(&reference_orbit(), 0.0);
+        let fit = fit_orbit_3d(&samples).unwrap();
+        assert!(fit.max_residual.value() < 1e-4);
+        assert!(fit.residual(&(samples[0] * 1.01)).value() > 1.0);
+    }
+
+    #[test]
+    fn degenerate_samples_are_rejected() {
+        let origin = Point {
+            x: Meters(0.0),
+            y: Meters(0.0),
+        };
+        let point = |x: Real, y: Real| Point {
+            x: Meters(x),
+            y: Meters(y),
+        };
+        assert!(fit_orbit_2d(&[point(1.0, 0.0), point(0.0, 1.0)], origin).is_err());
+        assert!(
+            fit_orbit_2d(&[point(1.0, 1.0), point(2.0, 2.0), point(3.0, 3.0)], origin).is_err()
+        );
+        assert!(fit_orbit_3d(&[Vector3::new(1.0, 0.0, 0.0); 4]).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! # Gravity Assists
+//!
+//! A flyby of a planet leaves the spacecraft's speed relative to that
+//! planet unchanged but turns its direction. Far from the planet the
+//! trajectory is a [`Hyperbola`] whose asymptotes meet at the turning angle
+//!
+//! `δ = 2·arcsin(1/e)`, with `e = 1 + rₚ v∞² / μ`
+//!
+//! so a closer pass or a slower approach bends the path further. Because
+//! the planet itself is moving, the turned excess velocity adds to the
+//! planet's velocity differently than the incoming one did, and the
+//! spacecraft's velocity relative to the Sun changes by `v∞,out - v∞,in`,
+//! of magnitude `2 v∞ sin(δ/2)`, without any propellant.
+//!
+//! A powered flyby adds a tangential burn at periapsis. The burn changes
+//! the excess speed by more than its own size, since it is made deep in the
+//! planet's gravity well (the Oberth effect), and the trajectory follows
+//! different hyperbolas in and out, turning by `arcsin(1/e_in) +
+//! arcsin(1/e_out)`.
+//!
+//! The flyby plane is fixed by a `pole`, the direction of the
+//! spacecraft's angular momentum about the planet. Passing behind a planet
+//! (relative to its motion) turns the velocity toward the planet's motion
+//! and speeds the spacecraft up; passing in front slows it down.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::kepler::flyby::flyby;
+//! use almagest::utils::{Kilometers, Vector3};
+//!
+//! // Approach at 5 km/s, passing 1,000 km above the surface
+//! let v_in = Vector3::new(5_000.0, 0.0, 0.0);
+//! let pass = flyby(&EARTH, &v_in, Kilometers(1_000.0), &Vector3::new(0.0, 0.0, 1.0))
+//!     .expect("Valid flyby");
+//! println!(
+//!     "Turned {:.1}°, Δv {:.0} m/s",
+//!     pass.turning_angle.value().to_degrees(),
+//!     pass.delta_v().norm(),
+//! );
+//! ```
+
+use libm::{asin, cos, sin, sqrt};
+
+use crate::celestials::CelestialBody;
+use crate::kepler::{Hyperbola, Point};
+use crate::utils::{Kilometers, Meters, MetersPerSecond, Radians, Real, Vector3};
+
+/// Relative tolerance on the turning angle when solving for a periapsis
+const TURN_TOLERANCE: Real = 1e-13;
+
+/// Bisection steps allowed when solving for a periapsis
+const MAX_ITERATIONS: usize = 200;
+
+/// A hyperbolic pass of a body, possibly with a burn at periapsis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Flyby {
+    /// Approach trajectory, with periapsis along +x in its own plane
+    pub incoming: Hyperbola,
+    /// Departure trajectory; the same as `incoming` without a burn
+    pub outgoing: Hyperbola,
+    /// Distance from the body's center at closest approach
+    pub periapsis_radius: Meters,
+    /// Angle between the incoming and outgoing excess velocities
+    pub turning_angle: Radians,
+    /// Excess velocity relative to the body on approach
+    pub incoming_v_infinity: Vector3,
+    /// Excess velocity relative to the body on departure
+    pub outgoing_v_infinity: Vector3,
+    /// Tangential burn at periapsis, negative when braking; zero for an
+    /// unpowered flyby
+    pub periapsis_delta_v: MetersPerSecond,
+}
+
+impl Flyby {
+    /// Change in velocity relative to the central body (the Sun, for a
+    /// planetary flyby), `v∞,out - v∞,in`
+    pub fn delta_v(&self) -> Vector3 {
+        self.outgoing_v_infinity - self.incoming_v_infinity
+    }
+
+    /// Change in speed relative to the central body when the flyby body
+    /// moves with `body_velocity`
+    pub fn speed_change(&self, body_velocity: &Vector3) -> MetersPerSecond {
+        let before = (*body_velocity + self.incoming_v_infinity).norm();
+        let after = (*body_velocity + self.outgoing_v_infinity).norm();
+        MetersPerSecond(after - before)
+    }
+}
+
+/// Unpowered flyby of `body` approaching with excess velocity
+/// `v_infinity`, passing `periapsis_altitude` above its mean radius.
+///
+/// Only the part of `pole` perpendicular to `v_infinity` is used, so any
+/// vector that is not parallel to the approach will do.
+pub fn flyby(
+    body: &CelestialBody,
+    v_infinity: &Vector3,
+    periapsis_altitude: Kilometers,
+    pole: &Vector3,
+) -> Result<Flyby, &'static str> {
+    powered_flyby(
+        body,
+        v_infinity,
+        periapsis_altitude,
+        pole,
+        MetersPerSecond(0.0),
+    )
+}
+
+/// Flyby with a tangential burn of `periapsis_delta_v` at closest approach;
+/// negative values brake.
+pub fn powered_flyby(
+    body: &CelestialBody,
+    v_infinity: &Vector3,
+    periapsis_altitude: Kilometers,
+    pole: &Vector3,
+    periapsis_delta_v: MetersPerSecond,
+) -> Result<Flyby, &'static str> {
+    let mu = validate_mu(body)?;
+    if periapsis_altitude.value() < 0.0 || !periapsis_altitude.value().is_finite() {
+        return Err("Periapsis must not be below the surface");
+    }
+    let r_p = Meters::from(Kilometers(body.radius.value() + periapsis_altitude.value()));
+    let v_in = v_infinity.norm();
+    if v_in <= 0.0 || !v_in.is_finite() {
+        return Err("Hyperbolic excess velocity must be positive");
+    }
+    let burn = periapsis_delta_v.value();
+    if !burn.is_finite() {
+        return Err("Periapsis burn must be finite");
+    }
+    let escape = 2.0 * mu / r_p.value();
+    let v_p = sqrt(v_in * v_in + escape) + burn;
+    let v_out_squared = v_p * v_p - escape;
+    if v_p <= 0.0 || v_out_squared <= 0.0 {
+        return Err("Periapsis burn captures the spacecraft");
+    }
+    let v_out = sqrt(v_out_squared);
+
+    let incoming = Hyperbola::from_excess_velocity(MetersPerSecond(v_in), r_p, origin(), body.mu)?;
+    let outgoing = Hyperbola::from_excess_velocity(MetersPerSecond(v_out), r_p, origin(), body.mu)?;
+    let turning_angle = half_turn(&incoming) + half_turn(&outgoing);
+
+    // Rotate the approach direction by δ about the pole
+    let direction = v_infinity.unit();
+    let normal = *pole - direction * pole.dot(&direction);
+    if normal.norm() <= 1e-12 * pole.norm() || !normal.norm().is_finite() {
+        return Err("Pole must not be parallel to the excess velocity");
+    }
+    let normal = normal.unit();
+    let turned = direction * cos(turning_angle) + normal.cross(&direction) * sin(turning_angle);
+
+    Ok(Flyby {
+        incoming,
+        outgoing,
+        periapsis_radius: r_p,
+        turning_angle: Radians(turning_angle),
+        incoming_v_infinity: *v_infinity,
+        outgoing_v_infinity: turned * v_out,
+        periapsis_delta_v,
+    })
+}
+
+/// Powered flyby that turns `v_infinity_in` into `v_infinity_out`, such as
+/// the excess velocities of two heliocentric legs meeting at `body`.
+///
+/// Solves for the periapsis that gives the required turn and the
+/// periapsis burn that gives the required change of excess speed. Fails if
+/// the turn would need a periapsis below the surface.
+pub fn powered_flyby_between(
+    body: &CelestialBody,
+    v_infinity_in: &Vector3,
+    v_infinity_out: &Vector3,
+) -> Result<Flyby, &'static str> {
+    let mu = validate_mu(body)?;
+    let (v_in, v_out) = (v_infinity_in.norm(), v_infinity_out.norm());
+    if v_in <= 0.0 || v_out <= 0.0 || !v_in.is_finite() || !v_out.is_finite() {
+        return Err("Hyperbolic excess velocities must be positive");
+    }
+    let pole = v_infinity_in.cross(v_infinity_out);
+    if pole.norm() <= 1e-12 * v_in * v_out {
+        return Err("Excess velocities must not be parallel");
+    }
+    let required = libm::atan2(pole.norm(), v_infinity_in.dot(v_infinity_out));
+
+    // The turn shrinks monotonically as the periapsis rises
+    let turn = |r_p: Real| {
+        let e_in = 1.0 + r_p * v_in * v_in / mu;
+        let e_out = 1.0 + r_p * v_out * v_out / mu;
+        asin(1.0 / e_in) + asin(1.0 / e_out)
+    };
+    let surface = Meters::from(body.radius).value();
+    if turn(surface) < required {
+        return Err("Turn requires a periapsis below the surface");
+    }
+    let mut lower = surface;
+    let mut upper = 2.0 * surface;
+    while turn(upper) > required {
+        upper *= 2.0;
+    }
+    for _ in 0..MAX_ITERATIONS {
+        let middle = 0.5 * (lower + upper);
+        if turn(middle) > required {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+        if upper - lower <= TURN_TOLERANCE * upper {
+            break;
+        }
+    }
+    let r_p = 0.5 * (lower + upper);
+
+    let escape = 2.0 * mu / r_p;
+    let burn = sqrt(v_out * v_out + escape) - sqrt(v_in * v_in + escape);
+    let altitude = Kilometers::from(Meters(r_p - surface));
+    let mut pass = powered_flyby(body, v_infinity_in, altitude, &pole, MetersPerSecond(burn))?;
+    // Report the requested direction exactly, rather than the rotated one
+    pass.outgoing_v_infinity = *v_infinity_out;
+    Ok(pass)
+}
+
+/// Half of the turning angle, `arcsin(1/e)`
+fn half_turn(hyperbola: &Hyperbola) -> Real {
+    0.5 * hyperbola.turning_angle().value()
+}
+
+fn validate_mu(body: &CelestialBody) -> Result<Real, &'static str> {
+    let mu = body.mu.value();
+    if mu <= 0.0 || !mu.is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    Ok(mu)
+}
+
+fn origin() -> Point {
+    Point {
+        x: Meters(0.0),
+        y: Meters(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS};
+    use approx::assert_relative_eq;
+
+    const POLE: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
+    #[test]
+    fn test_unpowered_turn() {
+        let v_in = Vector3::new(5_000.0, 0.0, 0.0);
+        let pass = flyby(&EARTH, &v_in, Kilometers(1_000.0), &POLE).unwrap();
+
+        let r_p = 7_371_000.0;
+        let e = 1.0 + r_p * 25e6 / EARTH.mu.value();
+        assert_relative_eq!(
+            pass.turning_angle.value(),
+            2.0 * asin(1.0 / e),
+            max_relative = 1e-12
+        );
+        assert_eq!(pass.incoming, pass.outgoing);
+
+        // Speed is conserved and the velocity turns counterclockwise about +z
+        let out = pass.outgoing_v_infinity;
+        assert_relative_eq!(out.norm(), 5_000.0, max_relative = 1e-12);
+        assert!(out.y > 0.0);
+        assert_relative_eq!(out.z, 0.0);
+        assert_relative_eq!(
+            libm::acos(out.dot(&v_in) / 25e6),
+            pass.turning_angle.value(),
+            max_relative = 1e-9
+        );
+        let half = 0.5 * pass.turning_angle.value();
+        assert_relative_eq!(
+            pass.delta_v().norm(),
+            2.0 * 5_000.0 * sin(half),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_closer_passes_turn_further() {
+        let v_in = Vector3::new(0.0, 4_000.0, 0.0);
+        let close = flyby(&MARS, &v_in, Kilometers(200.0), &POLE).unwrap();
+        let far = flyby(&MARS, &v_in, Kilometers(5_000.0), &POLE).unwrap();
+        assert!(close.turning_angle.value() > far.turning_angle.value());
+    }
+
+    #[test]
+    fn test_speed_change_depends_on_side() {
+        // Mars moving along +y; the spacecraft overtakes it from behind
+        let mars_velocity = Vector3::new(0.0, 24_000.0, 0.0);
+        let v_in = Vector3::new(3_000.0, 0.0, 0.0);
+        let toward_motion = flyby(&MARS, &v_in, Kilometers(300.0), &POLE).unwrap();
+        let against_motion = flyby(&MARS, &v_in, Kilometers(300.0), &-POLE).unwrap();
+        assert!(toward_motion.speed_change(&mars_velocity).value() > 0.0);
+        assert!(against_motion.speed_change(&mars_velocity).value() < 0.0);
+    }
+
+    #[test]
+    fn test_powered_flyby_oberth_effect() {
+        let v_in = Vector3::new(3_000.0, 0.0, 0.0);
+        let burn = MetersPerSecond(500.0);
+        let pass = powered_flyby(&EARTH, &v_in, Kilometers(500.0), &POLE, burn).unwrap();
+
+        let r_p = 6_871_000.0;
+        let escape = 2.0 * EARTH.mu.value() / r_p;
+        let v_p = sqrt(9e6 + escape) + 500.0;
+        let v_out = pass.outgoing_v_infinity.norm();
+        assert_relative_eq!(v_out, sqrt(v_p * v_p - escape), max_relative = 1e-12);
+        // Deep in the well the burn is worth more than its own size
+        assert!(v_out - 3_000.0 > burn.value());
+        // Less eccentric on the way in, so it turns further in than out
+        let expected = half_turn(&pass.incoming) + half_turn(&pass.outgoing);
+        assert_relative_eq!(pass.turning_angle.value(), expected);
+
+        let coasting = powered_flyby(
+            &EARTH,
+            &v_in,
+            Kilometers(500.0),
+            &POLE,
+            MetersPerSecond(0.0),
+        )
+        .unwrap();
+        assert_eq!(
+            coasting,
+            flyby(&EARTH, &v_in, Kilometers(500.0), &POLE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_powered_flyby_between() {
+        let v_in = Vector3::new(3_000.0, 500.0, -200.0);
+        let pole = Vector3::new(0.3, -0.2, 1.0);
+        let burn = MetersPerSecond(-250.0);
+        let pass = powered_flyby(&EARTH, &v_in, Kilometers(2_000.0), &pole, burn).unwrap();
+
+        let solved = powered_flyby_between(&EARTH, &v_in, &pass.outgoing_v_infinity).unwrap();
+        assert_relative_eq!(
+            solved.periapsis_radius.value(),
+            pass.periapsis_radius.value(),
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            solved.periapsis_delta_v.value(),
+            burn.value(),
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_invalid_flybys() {
+        let v_in = Vector3::new(3_000.0, 0.0, 0.0);
+        assert!(flyby(&EARTH, &Vector3::ZERO, Kilometers(500.0), &POLE).is_err());
+        assert!(flyby(&EARTH, &v_in, Kilometers(-10.0), &POLE).is_err());
+        assert!(
+            flyby(
+                &EARTH,
+                &v_in,
+                Kilometers(500.0),
+                &Vector3::new(1.0, 0.0, 0.0)
+            )
+            .is_err()
+        );
+        // Braking below escape speed
+        let brake = MetersPerSecond(-5_000.0);
+        assert!(powered_flyby(&EARTH, &v_in, Kilometers(500.0), &POLE, brake).is_err());
+        // A reversal cannot be done above the surface
+        let back = Vector3::new(-3_000.0, 1.0, 0.0);
+        assert!(powered_flyby_between(&MARS, &v_in, &back).is_err());
+    }
+}
@@ -0,0 +1,511 @@
+//! # Lambert's Problem
+//!
+//! Given two positions and the time allowed to travel between them, find the
+//! Keplerian trajectory that connects them. This is the boundary-value
+//! counterpart to propagation: instead of asking "where will it be?", it asks
+//! "how fast must it leave to arrive there on time?".
+//!
+//! The solver uses the universal-variable formulation (Bate, Mueller & White;
+//! Vallado, Algorithm 58). The time of flight is a function of a single
+//! parameter `ψ = χ²/a`:
+//!
+//! - `ψ < 0` — hyperbolic transfer
+//! - `ψ = 0` — parabolic transfer
+//! - `0 < ψ < 4π²` — elliptic transfer of less than one revolution
+//! - `(2πN)² < ψ < (2π(N+1))²` — elliptic transfer completing N full revolutions
+//!
+//! Within each interval the time of flight is found by bisection, which is
+//! slower than Newton's method but cannot diverge. For N ≥ 1 the time of flight
+//! has a minimum inside the interval, so every feasible multi-revolution
+//! transfer has two solutions.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::kepler::lambert::{TransferDirection, solve_lambert};
+//! use almagest::utils::{Seconds, Vector3};
+//!
+//! let r1 = Vector3::new(7_000_000.0, 0.0, 0.0);
+//! let r2 = Vector3::new(0.0, 12_000_000.0, 0.0);
+//!
+//! let transfer = solve_lambert(&r1, &r2, Seconds(3_000.0), EARTH.mu, TransferDirection::Prograde)
+//!     .expect("Feasible transfer");
+//! println!("Departure speed: {} m/s", transfer.departure_velocity.norm());
+//! ```
+
+use libm::{fabs, sqrt};
+
+use crate::kepler::propagation::{stumpff_c2, stumpff_c3};
+use crate::utils::{
+    Meters, MetersCubedPerSecondSquared, PI, Real, Seconds, TAU, Vector3, golden_section_minimum,
+};
+
+/// Relative tolerance on the time of flight
+const TOF_TOLERANCE: Real = 1e-12;
+/// Bisection steps allowed before giving up
+const MAX_ITERATIONS: usize = 500;
+/// Samples used to locate the minimum time of flight on a multi-revolution interval
+const MINIMUM_SEARCH_SAMPLES: usize = 400;
+
+/// Sense of travel around the central body, relative to the +z axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferDirection {
+    /// Counter-clockwise seen from +z; angular momentum along +z
+    Prograde,
+    /// Clockwise seen from +z; angular momentum along -z
+    Retrograde,
+}
+
+/// A trajectory connecting two positions in a given time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LambertSolution {
+    /// Velocity required at the first position, in m/s
+    pub departure_velocity: Vector3,
+    /// Velocity on arrival at the second position, in m/s
+    pub arrival_velocity: Vector3,
+    /// Semi-major axis of the transfer; negative for hyperbolic transfers
+    pub semi_major_axis: Meters,
+    /// Number of complete revolutions made before arrival
+    pub revolutions: u32,
+}
+
+/// Solve Lambert's problem for a transfer of less than one revolution.
+///
+/// # Arguments
+/// * `r1` - Departure position relative to the central body, in meters
+/// * `r2` - Arrival position relative to the central body, in meters
+/// * `tof` - Time of flight
+/// * `mu` - Standard gravitational parameter of the central body
+/// * `direction` - Whether the transfer is prograde or retrograde about +z
+///
+/// # Returns
+/// Departure and arrival velocities, or an error when the geometry is
+/// degenerate (positions 0° or 180° apart) or the inputs are invalid.
+pub fn solve_lambert(
+    r1: &Vector3,
+    r2: &Vector3,
+    tof: Seconds,
+    mu: MetersCubedPerSecondSquared,
+    direction: TransferDirection,
+) -> Result<LambertSolution, &'static str> {
+    let geometry = Geometry::new(r1, r2, tof, mu, direction)?;
+
+    // Time of flight increases monotonically with ψ on (-∞, 4π²)
+    let upper = TAU * TAU;
+    let mut lower = -TAU * TAU;
+    while geometry
+        .time_of_flight(lower)
+        .is_some_and(|t| t > geometry.tof)
+    {
+        lower *= 2.0;
+        if lower < -1e12 {
+            return Err("Time of flight is too short for any trajectory");
+        }
+    }
+
+    let psi = geometry.bisect(lower, upper, true)?;
+    geometry.solution(psi, 0)
+}
+
+/// Solve Lambert's problem for a transfer making `revolutions` complete
+/// revolutions before arrival.
+///
+/// With zero revolutions this is equivalent to [`solve_lambert`], and both
+/// returned solutions are identical. Otherwise there are two distinct
+/// trajectories, returned in order of increasing semi-major axis.
+///
+/// # Returns
+/// An error if the time of flight is shorter than the minimum possible for
+/// the requested number of revolutions.
+pub fn solve_lambert_multi_rev(
+    r1: &Vector3,
+    r2: &Vector3,
+    tof: Seconds,
+    mu: MetersCubedPerSecondSquared,
+    direction: TransferDirection,
+    revolutions: u32,
+) -> Result<[LambertSolution; 2], &'static str> {
+    if revolutions == 0 {
+        let solution = solve_lambert(r1, r2, tof, mu, direction)?;
+        return Ok([solution; 2]);
+    }
+    let geometry = Geometry::new(r1, r2, tof, mu, direction)?;
+
+    let n = revolutions as Real;
+    let lower = (TAU * n) * (TAU * n);
+    let upper = (TAU * (n + 1.0)) * (TAU * (n + 1.0));
+    let (psi_min, t_min) = geometry.minimum_time_of_flight(lower, upper)?;
+    if t_min > geometry.tof {
+        return Err("Time of flight is too short for the requested revolutions");
+    }
+
+    // Left of the minimum the time of flight decreases with ψ; right of it, increases
+    let left = geometry.solution(geometry.bisect(lower, psi_min, false)?, revolutions)?;
+    let right = geometry.solution(geometry.bisect(psi_min, upper, true)?, revolutions)?;
+
+    if left.semi_major_axis.value() <= right.semi_major_axis.value() {
+        Ok([left, right])
+    } else {
+        Ok([right, left])
+    }
+}
+
+/// Quantities fixed by the boundary conditions
+struct Geometry {
+    r1_vec: Vector3,
+    r2_vec: Vector3,
+    r1: Real,
+    r2: Real,
+    /// Geometry constant `A = sin(Δν)·√(r₁r₂/(1 - cos Δν))`
+    a: Real,
+    sqrt_mu: Real,
+    tof: Real,
+}
+
+impl Geometry {
+    fn new(
+        r1_vec: &Vector3,
+        r2_vec: &Vector3,
+        tof: Seconds,
+        mu: MetersCubedPerSecondSquared,
+        direction: TransferDirection,
+    ) -> Result<Self, &'static str> {
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        if tof.value() <= 0.0 || !tof.value().is_finite() {
+            return Err("Time of flight must be positive");
+        }
+        let r1 = r1_vec.norm();
+        let r2 = r2_vec.norm();
+        if r1 == 0.0 || r2 == 0.0 || !r1.is_finite() || !r2.is_finite() {
+            return Err("Positions must be finite and away from the central body");
+        }
+
+        let cos_dnu = (r1_vec.dot(r2_vec) / (r1 * r2)).clamp(-1.0, 1.0);
+        let dnu = transfer_angle(r1_vec, r2_vec, direction);
+        if fabs(libm::sin(dnu)) < 1e-10 {
+            return Err("Positions 0° or 180° apart leave the transfer plane undefined");
+        }
+
+        let a = libm::sin(dnu) * sqrt(r1 * r2 / (1.0 - cos_dnu));
+        Ok(Geometry {
+            r1_vec: *r1_vec,
+            r2_vec: *r2_vec,
+            r1,
+            r2,
+            a,
+            sqrt_mu: sqrt(mu.value()),
+            tof: tof.value(),
+        })
+    }
+
+    /// The auxiliary variable `y(ψ)`; negative where ψ is infeasible
+    fn y(&self, psi: Real) -> Real {
+        let c2 = stumpff_c2(psi);
+        let c3 = stumpff_c3(psi);
+        self.r1 + self.r2 + self.a * (psi * c3 - 1.0) / sqrt(c2)
+    }
+
+    /// Time of flight for a given ψ, or `None` if ψ is infeasible
+    fn time_of_flight(&self, psi: Real) -> Option<Real> {
+        let c2 = stumpff_c2(psi);
+        if c2 <= 0.0 {
+            return None;
+        }
+        let y = self.y(psi);
+        if y <= 0.0 || !y.is_finite() {
+            return None;
+        }
+        let chi = sqrt(y / c2);
+        let t = (chi * chi * chi * stumpff_c3(psi) + self.a * sqrt(y)) / self.sqrt_mu;
+        if t.is_finite() { Some(t) } else { None }
+    }
+
+    /// Bisect on [lower, upper] for the ψ whose time of flight matches.
+    ///
+    /// `increasing` gives the sense of t(ψ) on the interval; infeasible
+    /// values of ψ are treated as lying on the too-short side when the
+    /// function is increasing and on the too-long side when decreasing.
+    fn bisect(
+        &self,
+        mut lower: Real,
+        mut upper: Real,
+        increasing: bool,
+    ) -> Result<Real, &'static str> {
+        for _ in 0..MAX_ITERATIONS {
+            let psi = (lower + upper) / 2.0;
+            let too_long = match self.time_of_flight(psi) {
+                Some(t) => {
+                    if fabs(t - self.tof) <= TOF_TOLERANCE * self.tof {
+                        return Ok(psi);
+                    }
+                    t > self.tof
+                }
+                None => !increasing,
+            };
+            // Move toward shorter times when too long, longer when too short
+            if too_long == increasing {
+                upper = psi;
+            } else {
+                lower = psi;
+            }
+            if upper - lower <= Real::EPSILON * fabs(psi) {
+                return Ok(psi);
+            }
+        }
+        Err("Lambert solver failed to converge")
+    }
+
+    /// Locate the minimum time of flight on an open multi-revolution interval
+    fn minimum_time_of_flight(
+        &self,
+        lower: Real,
+        upper: Real,
+    ) -> Result<(Real, Real), &'static str> {
+        let step = (upper - lower) / MINIMUM_SEARCH_SAMPLES as Real;
+        let mut best: Option<(usize, Real)> = None;
+        for k in 1..MINIMUM_SEARCH_SAMPLES {
+            if let Some(t) = self.time_of_flight(lower + step * k as Real)
+                && best.is_none_or(|(_, t_best)| t < t_best)
+            {
+                best = Some((k, t));
+            }
+        }
+        let (k, _) = best.ok_or("No feasible multi-revolution transfer exists")?;
+
+        // Golden-section refinement around the best sample
+        let t = |psi: Real| self.time_of_flight(psi).unwrap_or(Real::INFINITY);
+        let psi = golden_section_minimum(
+            t,
+            lower + step * (k as Real - 1.0),
+            lower + step * (k as Real + 1.0),
+            100,
+        );
+        Ok((psi, t(psi)))
+    }
+
+    /// Velocities from the Lagrange coefficients at the converged ψ
+    fn solution(&self, psi: Real, revolutions: u32) -> Result<LambertSolution, &'static str> {
+        let y = self.y(psi);
+        if y <= 0.0 || !y.is_finite() {
+            return Err("Lambert solver failed to converge");
+        }
+        let f = 1.0 - y / self.r1;
+        let g = self.a * sqrt(y) / self.sqrt_mu;
+        let g_dot = 1.0 - y / self.r2;
+
+        let v1 = (self.r2_vec - self.r1_vec * f) / g;
+        let v2 = (self.r2_vec * g_dot - self.r1_vec) / g;
+
+        let mu = self.sqrt_mu * self.sqrt_mu;
+        let energy = v1.dot(&v1) / 2.0 - mu / self.r1;
+        Ok(LambertSolution {
+            departure_velocity: v1,
+            arrival_velocity: v2,
+            semi_major_axis: Meters(-mu / (2.0 * energy)),
+            revolutions,
+        })
+    }
+}
+
+/// Transfer angle swept from `r1` to `r2` in the given direction, in [0, 2π)
+pub fn transfer_angle(r1: &Vector3, r2: &Vector3, direction: TransferDirection) -> Real {
+    let cos_dnu = (r1.dot(r2) / (r1.norm() * r2.norm())).clamp(-1.0, 1.0);
+    let short = libm::acos(cos_dnu);
+    // Short way when the natural sense of r₁ × r₂ matches the requested direction
+    let normal_z = r1.cross(r2).z;
+    match direction {
+        TransferDirection::Prograde if normal_z >= 0.0 => short,
+        TransferDirection::Retrograde if normal_z < 0.0 => short,
+        _ => (TAU - short) % TAU,
+    }
+}
+
+/// Whether the transfer passes through more than half a revolution
+pub fn is_long_way(r1: &Vector3, r2: &Vector3, direction: TransferDirection) -> bool {
+    transfer_angle(r1, r2, direction) > PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::kepler::StateVector;
+    use crate::kepler::propagation::propagate_universal;
+    use approx::assert_relative_eq;
+
+    const KM: Real = 1_000.0;
+
+    fn assert_reaches(r1: &Vector3, r2: &Vector3, tof: Real, solution: &LambertSolution) {
+        let state = StateVector::new(*r1, solution.departure_velocity);
+        let arrival = propagate_universal(&state, EARTH.mu, Seconds(tof)).unwrap();
+        let miss = (arrival.position - *r2).norm();
+        assert!(miss < 1e-6 * r2.norm(), "Missed target by {miss} m");
+        let dv = (arrival.velocity - solution.arrival_velocity).norm();
+        assert!(
+            dv < 1e-6 * solution.arrival_velocity.norm(),
+            "Arrival velocity off by {dv} m/s"
+        );
+    }
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 7-5
+    #[test]
+    fn solves_vallado_example() {
+        let r1 = Vector3::new(15_945.34 * KM, 0.0, 0.0);
+        let r2 = Vector3::new(12_214.838_99 * KM, 10_249.467_31 * KM, 0.0);
+        let tof = 76.0 * 60.0;
+        let solution = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+
+        assert_relative_eq!(solution.departure_velocity.x, 2_058.913, epsilon = 0.5);
+        assert_relative_eq!(solution.departure_velocity.y, 2_915.965, epsilon = 0.5);
+        assert_relative_eq!(solution.arrival_velocity.x, -3_451.565, epsilon = 0.5);
+        assert_relative_eq!(solution.arrival_velocity.y, 910.315, epsilon = 0.5);
+        assert_reaches(&r1, &r2, tof, &solution);
+    }
+
+    // Curtis, "Orbital Mechanics for Engineering Students", Example 5.2
+    #[test]
+    fn solves_curtis_example() {
+        let r1 = Vector3::new(5_000.0 * KM, 10_000.0 * KM, 2_100.0 * KM);
+        let r2 = Vector3::new(-14_600.0 * KM, 2_500.0 * KM, 7_000.0 * KM);
+        let tof = 3_600.0;
+        let solution = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+
+        assert_relative_eq!(solution.departure_velocity.x, -5_992.5, epsilon = 1.0);
+        assert_relative_eq!(solution.departure_velocity.y, 1_925.4, epsilon = 1.0);
+        assert_relative_eq!(solution.departure_velocity.z, 3_245.6, epsilon = 1.0);
+        assert_relative_eq!(solution.arrival_velocity.x, -3_312.5, epsilon = 1.0);
+        assert_relative_eq!(solution.arrival_velocity.y, -4_196.6, epsilon = 1.0);
+        assert_relative_eq!(solution.arrival_velocity.z, -385.29, epsilon = 1.0);
+        assert_reaches(&r1, &r2, tof, &solution);
+    }
+
+    #[test]
+    fn retrograde_transfer_goes_the_other_way() {
+        let r1 = Vector3::new(7_000.0 * KM, 0.0, 0.0);
+        let r2 = Vector3::new(0.0, 9_000.0 * KM, 0.0);
+        let tof = 5_000.0;
+
+        let prograde = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+        let retrograde = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Retrograde,
+        )
+        .unwrap();
+
+        assert!(r1.cross(&prograde.departure_velocity).z > 0.0);
+        assert!(r1.cross(&retrograde.departure_velocity).z < 0.0);
+        assert!(is_long_way(&r1, &r2, TransferDirection::Retrograde));
+        assert!(!is_long_way(&r1, &r2, TransferDirection::Prograde));
+        assert_reaches(&r1, &r2, tof, &prograde);
+        assert_reaches(&r1, &r2, tof, &retrograde);
+    }
+
+    #[test]
+    fn short_flight_gives_hyperbolic_transfer() {
+        let r1 = Vector3::new(7_000.0 * KM, 0.0, 0.0);
+        let r2 = Vector3::new(-20_000.0 * KM, 30_000.0 * KM, 1_000.0 * KM);
+        let tof = 1_800.0;
+        let solution = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+
+        assert!(solution.semi_major_axis.value() < 0.0);
+        assert_reaches(&r1, &r2, tof, &solution);
+    }
+
+    #[test]
+    fn multi_revolution_has_two_solutions() {
+        let r1 = Vector3::new(7_000.0 * KM, 0.0, 0.0);
+        let r2 = Vector3::new(-3_000.0 * KM, 8_000.0 * KM, 500.0 * KM);
+        // Long enough for a full extra lap on a mid-altitude ellipse
+        let tof = 20_000.0;
+
+        let [low, high] = solve_lambert_multi_rev(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(low.revolutions, 1);
+        assert!(low.semi_major_axis.value() < high.semi_major_axis.value());
+        assert_reaches(&r1, &r2, tof, &low);
+        assert_reaches(&r1, &r2, tof, &high);
+
+        let single = solve_lambert(
+            &r1,
+            &r2,
+            Seconds(tof),
+            EARTH.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+        assert!((single.departure_velocity - low.departure_velocity).norm() > 1.0);
+        assert!((single.departure_velocity - high.departure_velocity).norm() > 1.0);
+    }
+
+    #[test]
+    fn multi_revolution_needs_enough_time() {
+        let r1 = Vector3::new(7_000.0 * KM, 0.0, 0.0);
+        let r2 = Vector3::new(0.0, 7_000.0 * KM, 0.0);
+        // Shorter than one period of the smallest orbit through both points
+        let result = solve_lambert_multi_rev(
+            &r1,
+            &r2,
+            Seconds(3_000.0),
+            EARTH.mu,
+            TransferDirection::Prograde,
+            1,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn degenerate_inputs_are_rejected() {
+        let r1 = Vector3::new(7_000.0 * KM, 0.0, 0.0);
+        let opposite = Vector3::new(-8_000.0 * KM, 0.0, 0.0);
+        let mu = EARTH.mu;
+        let prograde = TransferDirection::Prograde;
+
+        assert!(solve_lambert(&r1, &opposite, Seconds(3_000.0), mu, prograde).is_err());
+        assert!(solve_lambert(&r1, &(r1 * 2.0), Seconds(3_000.0), mu, prograde).is_err());
+        let r2 = Vector3::new(0.0, 8_000.0 * KM, 0.0);
+        assert!(solve_lambert(&r1, &r2, Seconds(0.0), mu, prograde).is_err());
+        assert!(solve_lambert(&Vector3::ZERO, &r2, Seconds(3_000.0), mu, prograde).is_err());
+    }
+}
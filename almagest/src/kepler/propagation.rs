@@ -0,0 +1,596 @@
+//! # Analytic Two-Body Propagation
+//!
+//! In the two-body problem the shape and orientation of an orbit never
+//! change; only the mean anomaly advances, at the constant rate `n`:
+//!
+//! `M(t) = M₀ + n·(t - t₀)`
+//!
+//! Propagating an orbit therefore reduces to advancing the mean anomaly,
+//! solving Kepler's equation for the new true anomaly, and converting the
+//! elements back to a state vector. This is exact for an unperturbed point
+//! mass, and fast enough to call once per simulation step.
+//!
+//! [`KeplerPropagator`] follows that recipe, which means choosing between the
+//! elliptic and hyperbolic forms of Kepler's equation and failing for exactly
+//! parabolic orbits. [`UniversalPropagator`] instead works directly on the
+//! state vector with the universal anomaly χ and the Stumpff functions
+//! `c₂(ψ)` and `c₃(ψ)`, which remain smooth as ψ = χ²/a passes through zero.
+//! One code path then covers ellipses, parabolas and hyperbolas alike.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::MOON;
+//! use almagest::kepler::{Ellipse, Point, propagation::KeplerPropagator};
+//! use almagest::utils::{Meters, Seconds};
+//!
+//! // A 100 km × 300 km lunar orbit, starting at periapsis at t = 0
+//! let orbit = Ellipse::from_periapsis_apoapsis(
+//!     Meters(1_837_480.0),
+//!     Meters(2_037_480.0),
+//!     Point { x: Meters(0.0), y: Meters(0.0) },
+//! );
+//! let propagator = KeplerPropagator::from_ellipse(&orbit, MOON.mu, Seconds(0.0))
+//!     .expect("Valid orbit");
+//!
+//! let later = propagator.propagate(Seconds(1_800.0)).expect("Converges");
+//! println!("Distance after 30 minutes: {} m", later.radius().value());
+//! ```
+
+use libm::{cos, cosh, fabs, sin, sinh, sqrt};
+
+use crate::kepler::{Ellipse, KeplerianElements, StateVector, anomaly};
+use crate::utils::{MetersCubedPerSecondSquared, Radians, Real, Seconds, TAU};
+
+/// Below this |ψ| the Stumpff functions switch to their series expansions
+const STUMPFF_SERIES_LIMIT: Real = 1e-6;
+/// Relative convergence tolerance on the universal anomaly
+const UNIVERSAL_TOLERANCE: Real = 1e-13;
+/// Newton iterations allowed when solving for the universal anomaly
+const UNIVERSAL_MAX_ITERATIONS: usize = 100;
+
+/// Propagates a Keplerian orbit analytically from a reference epoch.
+///
+/// The elements describe the orbit at `epoch`; any later (or earlier) state
+/// is obtained in closed form by advancing the mean anomaly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeplerPropagator {
+    /// Orbital elements at the reference epoch
+    elements: KeplerianElements,
+    /// Time at which `elements` are valid
+    epoch: Seconds,
+}
+
+impl KeplerPropagator {
+    pub fn new(elements: KeplerianElements, epoch: Seconds) -> Self {
+        KeplerPropagator { elements, epoch }
+    }
+
+    /// Propagate a planar ellipse, with the body at periapsis at `epoch`.
+    ///
+    /// The orbit is placed in the reference plane with periapsis along the
+    /// x-axis, matching the 2D frame in which [`Ellipse`] is defined.
+    pub fn from_ellipse(
+        ellipse: &Ellipse,
+        mu: MetersCubedPerSecondSquared,
+        epoch: Seconds,
+    ) -> Result<Self, &'static str> {
+        let elements = KeplerianElements::from_ellipse(
+            ellipse,
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            mu,
+        )?;
+        Ok(Self::new(elements, epoch))
+    }
+
+    /// Propagate from a Cartesian state observed at `epoch`.
+    pub fn from_state_vector(
+        state: &StateVector,
+        mu: MetersCubedPerSecondSquared,
+        epoch: Seconds,
+    ) -> Result<Self, &'static str> {
+        let elements = KeplerianElements::from_state_vector(state, mu)?;
+        Ok(Self::new(elements, epoch))
+    }
+
+    /// Orbital elements at the reference epoch
+    pub fn elements(&self) -> KeplerianElements {
+        self.elements
+    }
+
+    /// Time at which the reference elements are valid
+    pub fn epoch(&self) -> Seconds {
+        self.epoch
+    }
+
+    /// Time for one revolution; only defined for bound orbits
+    pub fn period(&self) -> Result<Seconds, &'static str> {
+        self.elements.period()
+    }
+
+    /// Orbital elements `dt` seconds after the reference epoch.
+    ///
+    /// Only the true anomaly differs from the reference elements.
+    pub fn elements_after(&self, dt: Seconds) -> Result<KeplerianElements, &'static str> {
+        if !dt.value().is_finite() {
+            return Err("Time step must be finite");
+        }
+        let e = self.elements.eccentricity();
+        let m0 = self.elements.mean_anomaly()?;
+        let m = Radians(m0.value() + self.elements.mean_motion().value() * dt.value());
+        let nu = anomaly::mean_to_true(m, e)?;
+        self.elements.with_true_anomaly(nu)
+    }
+
+    /// Position and velocity `dt` seconds after the reference epoch.
+    ///
+    /// Negative `dt` propagates backwards in time.
+    pub fn propagate(&self, dt: Seconds) -> Result<StateVector, &'static str> {
+        Ok(self.elements_after(dt)?.to_state_vector())
+    }
+
+    /// Position and velocity at the absolute time `t`.
+    pub fn state_at(&self, t: Seconds) -> Result<StateVector, &'static str> {
+        self.propagate(Seconds(t.value() - self.epoch.value()))
+    }
+}
+
+/// Stumpff function `c₂(ψ) = (1 - cos√ψ)/ψ`, continued smoothly to ψ ≤ 0.
+pub fn stumpff_c2(psi: Real) -> Real {
+    if psi > STUMPFF_SERIES_LIMIT {
+        (1.0 - cos(sqrt(psi))) / psi
+    } else if psi < -STUMPFF_SERIES_LIMIT {
+        (1.0 - cosh(sqrt(-psi))) / psi
+    } else {
+        1.0 / 2.0 - psi / 24.0 + psi * psi / 720.0
+    }
+}
+
+/// Stumpff function `c₃(ψ) = (√ψ - sin√ψ)/√ψ³`, continued smoothly to ψ ≤ 0.
+pub fn stumpff_c3(psi: Real) -> Real {
+    if psi > STUMPFF_SERIES_LIMIT {
+        let root = sqrt(psi);
+        (root - sin(root)) / (root * psi)
+    } else if psi < -STUMPFF_SERIES_LIMIT {
+        let root = sqrt(-psi);
+        (sinh(root) - root) / (root * -psi)
+    } else {
+        1.0 / 6.0 - psi / 120.0 + psi * psi / 5040.0
+    }
+}
+
+/// Propagate a state vector by `dt` using universal variables.
+///
+/// Valid for every conic, including exactly parabolic and rectilinear
+/// trajectories, without converting to orbital elements.
+///
+/// # Example
+/// ```
+/// use almagest::celestials::celestial_bodies::EARTH;
+/// use almagest::kepler::{StateVector, propagation::propagate_universal};
+/// use almagest::utils::{Seconds, Vector3};
+///
+/// // Exactly escape speed: a parabola, which KeplerPropagator cannot handle
+/// let r = 7_000_000.0;
+/// let v_esc = (2.0 * EARTH.mu.value() / r).sqrt();
+/// let state = StateVector::new(Vector3::new(r, 0.0, 0.0), Vector3::new(0.0, v_esc, 0.0));
+///
+/// let later = propagate_universal(&state, EARTH.mu, Seconds(3_600.0)).expect("Converges");
+/// assert!(later.radius().value() > r);
+/// ```
+pub fn propagate_universal(
+    state: &StateVector,
+    mu: MetersCubedPerSecondSquared,
+    dt: Seconds,
+) -> Result<StateVector, &'static str> {
+    if mu.value() <= 0.0 || !mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    if !dt.value().is_finite() {
+        return Err("Time step must be finite");
+    }
+    let r0_vec = state.position;
+    let v0_vec = state.velocity;
+    let r0 = r0_vec.norm();
+    let v0 = v0_vec.norm();
+    if r0 == 0.0 || !r0.is_finite() || !v0.is_finite() {
+        return Err("Position must be finite and away from the central body");
+    }
+
+    let mu = mu.value();
+    let sqrt_mu = sqrt(mu);
+    let rv = r0_vec.dot(&v0_vec) / sqrt_mu;
+    // Reciprocal semi-major axis: positive, zero or negative by conic type
+    let alpha = 2.0 / r0 - v0 * v0 / mu;
+
+    let mut dt = dt.value();
+    if dt == 0.0 {
+        return Ok(*state);
+    }
+    // Whole revolutions change nothing on a closed orbit
+    if alpha > 0.0 {
+        let period = TAU / (sqrt_mu * alpha * sqrt(alpha));
+        dt = libm::fmod(dt, period);
+    }
+
+    let h = r0_vec.cross(&v0_vec).norm();
+    let mut chi = universal_anomaly_guess(r0, rv, alpha, sqrt_mu, dt, h);
+    let mut converged = false;
+    for _ in 0..UNIVERSAL_MAX_ITERATIONS {
+        let psi = chi * chi * alpha;
+        let c2 = stumpff_c2(psi);
+        let c3 = stumpff_c3(psi);
+        let chi2 = chi * chi;
+        let r = chi2 * c2 + rv * chi * (1.0 - psi * c3) + r0 * (1.0 - psi * c2);
+        let t = chi2 * chi * c3 + rv * chi2 * c2 + r0 * chi * (1.0 - psi * c3);
+        let step = (sqrt_mu * dt - t) / r;
+        chi += step;
+        if !chi.is_finite() {
+            break;
+        }
+        if fabs(step) <= UNIVERSAL_TOLERANCE * (1.0 + fabs(chi)) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err("Universal Kepler's equation failed to converge");
+    }
+
+    // Lagrange coefficients
+    let psi = chi * chi * alpha;
+    let c2 = stumpff_c2(psi);
+    let c3 = stumpff_c3(psi);
+    let chi2 = chi * chi;
+    let f = 1.0 - chi2 / r0 * c2;
+    let g = dt - chi2 * chi / sqrt_mu * c3;
+    let r_vec = r0_vec * f + v0_vec * g;
+    let r = r_vec.norm();
+    let g_dot = 1.0 - chi2 / r * c2;
+    let f_dot = sqrt_mu / (r * r0) * chi * (psi * c3 - 1.0);
+    let v_vec = r0_vec * f_dot + v0_vec * g_dot;
+
+    Ok(StateVector::new(r_vec, v_vec))
+}
+
+/// Starting guess for the universal anomaly, after Vallado, Algorithm 8
+fn universal_anomaly_guess(
+    r0: Real,
+    rv: Real,
+    alpha: Real,
+    sqrt_mu: Real,
+    dt: Real,
+    h: Real,
+) -> Real {
+    let mu = sqrt_mu * sqrt_mu;
+    if alpha * r0 > STUMPFF_SERIES_LIMIT {
+        // Elliptic
+        sqrt_mu * dt * alpha
+    } else if alpha * r0 < -STUMPFF_SERIES_LIMIT {
+        // Hyperbolic
+        let a = 1.0 / alpha;
+        let sign = if dt < 0.0 { -1.0 } else { 1.0 };
+        let denominator = rv * sqrt_mu + sign * sqrt(-mu * a) * (1.0 - r0 * alpha);
+        let guess = sign * sqrt(-a) * libm::log(-2.0 * mu * alpha * dt / denominator);
+        if guess.is_finite() {
+            guess
+        } else {
+            sqrt_mu * dt / r0
+        }
+    } else {
+        // Near-parabolic: Barker's equation
+        let p = h * h / mu;
+        if p <= 0.0 {
+            return sqrt_mu * dt / r0;
+        }
+        let s = 0.5 * libm::atan(1.0 / (3.0 * sqrt(mu / (p * p * p)) * dt));
+        let w = libm::atan(libm::cbrt(libm::tan(s)));
+        sqrt(p) * 2.0 / libm::tan(2.0 * w)
+    }
+}
+
+/// Propagates any Keplerian trajectory from a reference state using
+/// universal variables.
+///
+/// Unlike [`KeplerPropagator`] this never converts to orbital elements, so it
+/// handles bound, parabolic and escape trajectories in a single code path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniversalPropagator {
+    /// Cartesian state at the reference epoch
+    state: StateVector,
+    /// Standard gravitational parameter of the central body
+    mu: MetersCubedPerSecondSquared,
+    /// Time at which `state` is valid
+    epoch: Seconds,
+}
+
+impl UniversalPropagator {
+    pub fn new(
+        state: StateVector,
+        mu: MetersCubedPerSecondSquared,
+        epoch: Seconds,
+    ) -> Result<Self, &'static str> {
+        if mu.value() <= 0.0 || !mu.value().is_finite() {
+            return Err("Gravitational parameter must be positive");
+        }
+        if state.radius().value() == 0.0 {
+            return Err("Position must not coincide with the central body");
+        }
+        Ok(UniversalPropagator { state, mu, epoch })
+    }
+
+    /// Cartesian state at the reference epoch
+    pub fn state(&self) -> StateVector {
+        self.state
+    }
+
+    /// Time at which the reference state is valid
+    pub fn epoch(&self) -> Seconds {
+        self.epoch
+    }
+
+    /// Position and velocity `dt` seconds after the reference epoch.
+    pub fn propagate(&self, dt: Seconds) -> Result<StateVector, &'static str> {
+        propagate_universal(&self.state, self.mu, dt)
+    }
+
+    /// Position and velocity at the absolute time `t`.
+    pub fn state_at(&self, t: Seconds) -> Result<StateVector, &'static str> {
+        self.propagate(Seconds(t.value() - self.epoch.value()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::kepler::Point;
+    use crate::test_helpers::assert_vectors_close;
+    use crate::utils::{Eccentricity, Meters, PI, Real, Vector3};
+    use approx::assert_relative_eq;
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 2-4
+    #[test]
+    fn propagates_vallado_example() {
+        let state = StateVector::new(
+            Vector3::new(1_131_340.0, -2_282_343.0, 6_672_423.0),
+            Vector3::new(-5_643.05, 4_303.33, 2_428.79),
+        );
+        let propagator =
+            KeplerPropagator::from_state_vector(&state, EARTH.mu, Seconds(0.0)).unwrap();
+        let later = propagator.propagate(Seconds(40.0 * 60.0)).unwrap();
+
+        assert_vectors_close(
+            later.position,
+            Vector3::new(-4_219_752.7, 4_363_029.2, -3_958_766.6),
+            50.0,
+        );
+        assert_vectors_close(
+            later.velocity,
+            Vector3::new(3_689.866, -1_916.735, -6_112.511),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn full_period_returns_to_start() {
+        let orbit = Ellipse::from_periapsis_apoapsis(
+            Meters(6_578_000.0),
+            Meters(42_164_000.0),
+            Point {
+                x: Meters(0.0),
+                y: Meters(0.0),
+            },
+        );
+        let propagator = KeplerPropagator::from_ellipse(&orbit, EARTH.mu, Seconds(100.0)).unwrap();
+        let period = propagator.period().unwrap();
+
+        let start = propagator.propagate(Seconds(0.0)).unwrap();
+        let one_rev = propagator.propagate(period).unwrap();
+        let three_revs_back = propagator.propagate(Seconds(-3.0 * period.0)).unwrap();
+
+        assert_vectors_close(start.position, one_rev.position, 1e-2);
+        assert_vectors_close(start.velocity, one_rev.velocity, 1e-5);
+        assert_vectors_close(start.position, three_revs_back.position, 1e-2);
+    }
+
+    #[test]
+    fn half_period_reaches_apoapsis() {
+        let orbit = Ellipse::from_periapsis_apoapsis(
+            Meters(6_578_000.0),
+            Meters(42_164_000.0),
+            Point {
+                x: Meters(0.0),
+                y: Meters(0.0),
+            },
+        );
+        let propagator = KeplerPropagator::from_ellipse(&orbit, EARTH.mu, Seconds(0.0)).unwrap();
+        let half = Seconds(propagator.period().unwrap().0 / 2.0);
+
+        let state = propagator.state_at(half).unwrap();
+        assert_relative_eq!(state.radius().0, 42_164_000.0, epsilon = 1e-3);
+        // Periapsis lies along +x, so apoapsis lies along -x
+        assert!(state.position.x < 0.0);
+    }
+
+    #[test]
+    fn epoch_offsets_absolute_time() {
+        let elements = KeplerianElements::new(
+            Meters(7_000_000.0),
+            Eccentricity::new(0.05).unwrap(),
+            Radians(0.7),
+            Radians(1.0),
+            Radians(2.0),
+            Radians(0.3),
+            EARTH.mu,
+        )
+        .unwrap();
+        let propagator = KeplerPropagator::new(elements, Seconds(500.0));
+
+        assert_eq!(
+            propagator.state_at(Seconds(1_500.0)).unwrap(),
+            propagator.propagate(Seconds(1_000.0)).unwrap()
+        );
+        let at_epoch = propagator.state_at(Seconds(500.0)).unwrap();
+        assert_vectors_close(at_epoch.position, elements.to_state_vector().position, 1e-6);
+    }
+
+    #[test]
+    fn conserves_energy_and_angular_momentum() {
+        let elements = KeplerianElements::new(
+            Meters(9_000_000.0),
+            Eccentricity::new(0.3).unwrap(),
+            Radians(1.1),
+            Radians(0.4),
+            Radians(PI),
+            Radians(0.0),
+            EARTH.mu,
+        )
+        .unwrap();
+        let propagator = KeplerPropagator::new(elements, Seconds(0.0));
+        let mu = EARTH.mu.value();
+
+        let invariants = |s: &StateVector| {
+            let energy = s.velocity.dot(&s.velocity) / 2.0 - mu / s.radius().0;
+            (energy, s.position.cross(&s.velocity))
+        };
+        let (energy0, h0) = invariants(&propagator.propagate(Seconds(0.0)).unwrap());
+
+        for step in 1..20 {
+            let state = propagator.propagate(Seconds(step as Real * 731.0)).unwrap();
+            let (energy, h) = invariants(&state);
+            assert_relative_eq!(energy, energy0, max_relative = 1e-10);
+            assert_vectors_close(h, h0, 1e-3);
+        }
+    }
+
+    #[test]
+    fn propagates_hyperbolic_escape() {
+        let state = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(0.0, 12_000.0, 0.0),
+        );
+        let propagator =
+            KeplerPropagator::from_state_vector(&state, EARTH.mu, Seconds(0.0)).unwrap();
+        assert!(propagator.period().is_err());
+
+        let mut previous = state.radius().0;
+        for hours in 1..10 {
+            let later = propagator
+                .propagate(Seconds(hours as Real * 3_600.0))
+                .unwrap();
+            assert!(later.radius().0 > previous);
+            previous = later.radius().0;
+        }
+
+        // Backwards in time the body approaches from the other asymptote
+        let earlier = propagator.propagate(Seconds(-3_600.0)).unwrap();
+        assert!(earlier.position.y < 0.0);
+    }
+
+    #[test]
+    fn stumpff_functions_are_continuous_at_zero() {
+        for psi in [1e-6, 1.000_001e-6, -1e-6, -1.000_001e-6] {
+            assert_relative_eq!(stumpff_c2(psi), 0.5 - psi / 24.0, epsilon = 1e-9);
+            assert_relative_eq!(stumpff_c3(psi), 1.0 / 6.0 - psi / 120.0, epsilon = 1e-9);
+        }
+        assert_eq!(stumpff_c2(0.0), 0.5);
+        assert_eq!(stumpff_c3(0.0), 1.0 / 6.0);
+        // c₂(4π²) = 0 since cos(2π) = 1
+        assert_relative_eq!(stumpff_c2(4.0 * PI * PI), 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn universal_matches_vallado_example() {
+        let state = StateVector::new(
+            Vector3::new(1_131_340.0, -2_282_343.0, 6_672_423.0),
+            Vector3::new(-5_643.05, 4_303.33, 2_428.79),
+        );
+        let later = propagate_universal(&state, EARTH.mu, Seconds(40.0 * 60.0)).unwrap();
+
+        assert_vectors_close(
+            later.position,
+            Vector3::new(-4_219_752.7, 4_363_029.2, -3_958_766.6),
+            50.0,
+        );
+        assert_vectors_close(
+            later.velocity,
+            Vector3::new(3_689.866, -1_916.735, -6_112.511),
+            0.05,
+        );
+    }
+
+    #[test]
+    fn universal_agrees_with_kepler_for_bound_and_escape_orbits() {
+        for speed in [6_000.0, 7_546.0, 9_000.0, 10_600.0, 11_000.0, 15_000.0] {
+            let state = StateVector::new(
+                Vector3::new(7_000_000.0, 0.0, 0.0),
+                Vector3::new(500.0, speed * 0.8, speed * 0.6),
+            );
+            let kepler =
+                KeplerPropagator::from_state_vector(&state, EARTH.mu, Seconds(0.0)).unwrap();
+            let universal = UniversalPropagator::new(state, EARTH.mu, Seconds(0.0)).unwrap();
+
+            for dt in [-5_000.0, -60.0, 1.0, 600.0, 4_000.0, 20_000.0] {
+                let expected = kepler.propagate(Seconds(dt)).unwrap();
+                let actual = universal.propagate(Seconds(dt)).unwrap();
+                let scale = expected.radius().0;
+                assert_vectors_close(actual.position, expected.position, 1e-7 * scale);
+                assert_vectors_close(actual.velocity, expected.velocity, 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn universal_handles_exact_parabola() {
+        let r0 = 7_000_000.0;
+        let v_esc = (2.0 * EARTH.mu.0 / r0).sqrt();
+        let state = StateVector::new(Vector3::new(r0, 0.0, 0.0), Vector3::new(0.0, v_esc, 0.0));
+        let propagator = UniversalPropagator::new(state, EARTH.mu, Seconds(10.0)).unwrap();
+
+        // Barker's equation: t = √(p³/μ)/2 · (D + D³/3), D = tan(ν/2), p = 2rₚ
+        let p = 2.0 * r0;
+        for nu in [0.5, 1.5, 2.5] {
+            let d: Real = libm::tan(nu / 2.0);
+            let t = 0.5 * (p * p * p / EARTH.mu.0).sqrt() * (d + d * d * d / 3.0);
+            let state = propagator.state_at(Seconds(10.0 + t)).unwrap();
+            let expected_r = p / (1.0 + libm::cos(nu));
+
+            assert_relative_eq!(state.radius().0, expected_r, max_relative = 1e-9);
+            // Parabolic: speed is always the local escape speed
+            let v_local_esc = (2.0 * EARTH.mu.0 / state.radius().0).sqrt();
+            assert_relative_eq!(state.speed().0, v_local_esc, max_relative = 1e-9);
+        }
+    }
+
+    #[test]
+    fn universal_zero_step_is_identity() {
+        let state = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(0.0, 7_500.0, 0.0),
+        );
+        assert_eq!(
+            propagate_universal(&state, EARTH.mu, Seconds(0.0)).unwrap(),
+            state
+        );
+        assert!(propagate_universal(&state, EARTH.mu, Seconds(Real::INFINITY)).is_err());
+        assert!(
+            UniversalPropagator::new(state, MetersCubedPerSecondSquared(0.0), Seconds(0.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_non_finite_time() {
+        let state = StateVector::new(
+            Vector3::new(7_000_000.0, 0.0, 0.0),
+            Vector3::new(0.0, 7_500.0, 0.0),
+        );
+        let propagator =
+            KeplerPropagator::from_state_vector(&state, EARTH.mu, Seconds(0.0)).unwrap();
+        assert!(propagator.propagate(Seconds(Real::NAN)).is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! # J2 Secular Drift
+//!
+//! An oblate central body does not let an orbit stay fixed in space. Averaged
+//! over one revolution, the J2 term of the gravity field leaves the size and
+//! shape of the orbit unchanged but turns it steadily:
+//!
+//! - `Ω̇ = -³⁄₂ n J₂ (R/p)² cos i` - the node regresses for prograde orbits
+//! - `ω̇ = ³⁄₄ n J₂ (R/p)² (4 - 5 sin² i)` - periapsis rotates within the plane
+//! - `Ṁ = n + ³⁄₄ n J₂ (R/p)² √(1 - e²) (2 - 3 sin² i)` - the mean motion shifts
+//!
+//! where `n` is the Keplerian mean motion, `p = a(1 - e²)` the semi-latus
+//! rectum and `R` the body's equatorial radius. These first-order rates are
+//! the basis of two classic design choices:
+//!
+//! - **Sun-synchronous orbits**: choose the inclination so that the node
+//!   turns once per year, keeping the orbit plane fixed relative to the Sun.
+//! - **Frozen orbits**: choose the eccentricity so that J3 balances J2 and
+//!   the periapsis stops rotating, holding the altitude profile over each
+//!   ground location constant.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::kepler::secular::{EARTH_SIDEREAL_YEAR, sun_synchronous_inclination};
+//! use almagest::utils::{Degrees, Eccentricity, Meters};
+//!
+//! // 800 km circular sun-synchronous orbit
+//! let a = Meters(6_378_137.0 + 800_000.0);
+//! let i = sun_synchronous_inclination(a, Eccentricity::new(0.0).unwrap(), &EARTH, EARTH_SIDEREAL_YEAR)
+//!     .expect("Reachable at this altitude");
+//! println!("Inclination: {:.2}°", Degrees::from(i).value());
+//! ```
+
+use libm::{acos, sin, sqrt};
+
+use crate::celestials::CelestialBody;
+use crate::kepler::{KeplerianElements, anomaly};
+use crate::utils::{Eccentricity, Meters, PI, Radians, RadiansPerSecond, Real, Seconds, TAU};
+
+/// Inclination at which J2 leaves the argument of periapsis fixed,
+/// `arcsin(2/√5)` ≈ 63.43°; its supplement works for retrograde orbits
+pub const CRITICAL_INCLINATION: Radians = Radians(1.107_148_717_794_090_4);
+
+/// Earth's orbital period relative to the fixed stars, the nodal period of a
+/// sun-synchronous Earth orbit
+pub const EARTH_SIDEREAL_YEAR: Seconds = Seconds(31_558_149.8);
+
+/// Iterations allowed when solving for the frozen eccentricity
+const FROZEN_MAX_ITERATIONS: usize = 50;
+
+/// Orbit-averaged angular rates caused by J2.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SecularRates {
+    /// Rate of change of the right ascension of the ascending node
+    pub raan: RadiansPerSecond,
+    /// Rate of change of the argument of periapsis
+    pub argument_of_periapsis: RadiansPerSecond,
+    /// Rate of change of the mean anomaly, including the mean motion
+    pub mean_anomaly: RadiansPerSecond,
+}
+
+/// A frozen orbit's eccentricity and the argument of periapsis it must be
+/// held at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrozenOrbit {
+    pub eccentricity: Eccentricity,
+    /// 90° or 270°, depending on the sign of J3
+    pub argument_of_periapsis: Radians,
+}
+
+/// Secular rates of `elements` due to the J2 of `body`.
+///
+/// `body` supplies J2 and the equatorial radius; μ is taken from the
+/// elements. Only bound orbits drift periodically, so hyperbolic elements
+/// are rejected.
+///
+/// # Examples
+/// ```rust
+/// use almagest::celestials::celestial_bodies::EARTH;
+/// use almagest::kepler::{KeplerianElements, secular::j2_secular_rates};
+/// use almagest::utils::{Degrees, Eccentricity, Meters, Radians};
+///
+/// // The ISS node regresses about 5° per day
+/// let iss = KeplerianElements::new(
+///     Meters(6_778_000.0),
+///     Eccentricity::new(0.0005).unwrap(),
+///     Degrees(51.64).into(),
+///     Radians::ZERO,
+///     Radians::ZERO,
+///     Radians::ZERO,
+///     EARTH.mu,
+/// )
+/// .unwrap();
+/// let rates = j2_secular_rates(&iss, &EARTH).unwrap();
+/// let per_day = Degrees::from(almagest::utils::Radians(rates.raan.value() * 86_400.0));
+/// assert!((per_day.value() + 5.0).abs() < 0.1);
+/// ```
+pub fn j2_secular_rates(
+    elements: &KeplerianElements,
+    body: &CelestialBody,
+) -> Result<SecularRates, &'static str> {
+    if !elements.is_bound() {
+        return Err("Secular rates are only defined for bound orbits");
+    }
+    let e = elements.eccentricity().value();
+    let (sin_i, cos_i) = libm::sincos(elements.inclination().value());
+    let n = elements.mean_motion().value();
+    let k = oblateness_factor(n, elements.semi_latus_rectum(), body);
+    Ok(SecularRates {
+        raan: RadiansPerSecond(-1.5 * k * cos_i),
+        argument_of_periapsis: RadiansPerSecond(0.75 * k * (4.0 - 5.0 * sin_i * sin_i)),
+        mean_anomaly: RadiansPerSecond(
+            n + 0.75 * k * sqrt(1.0 - e * e) * (2.0 - 3.0 * sin_i * sin_i),
+        ),
+    })
+}
+
+/// Mean elements `dt` after `elements`, advanced by the J2 secular rates.
+///
+/// The semi-major axis, eccentricity and inclination are unchanged; the
+/// node, argument of periapsis and mean anomaly advance linearly. Short
+/// periodic oscillations are not modeled, so treat the inputs and result as
+/// mean rather than osculating elements.
+pub fn secular_drift(
+    elements: &KeplerianElements,
+    body: &CelestialBody,
+    dt: Seconds,
+) -> Result<KeplerianElements, &'static str> {
+    if !dt.value().is_finite() {
+        return Err("Time step must be finite");
+    }
+    let rates = j2_secular_rates(elements, body)?;
+    let dt = dt.value();
+    let e = elements.eccentricity();
+    let m = Radians(elements.mean_anomaly()?.value() + rates.mean_anomaly.value() * dt);
+    KeplerianElements::new(
+        elements.semi_major_axis(),
+        e,
+        elements.inclination(),
+        Radians(elements.raan().value() + rates.raan.value() * dt),
+        Radians(
+            elements.argument_of_periapsis().value() + rates.argument_of_periapsis.value() * dt,
+        ),
+        anomaly::mean_to_true(m, e)?,
+        elements.mu(),
+    )
+}
+
+/// Inclination at which the node precesses once per `orbital_period` of
+/// the body around the Sun, keeping the orbit plane at a fixed angle to the
+/// Sun.
+///
+/// Sun-synchronous orbits are always slightly retrograde. Fails if the orbit
+/// is too high for J2 to turn the node fast enough.
+pub fn sun_synchronous_inclination(
+    a: Meters,
+    e: Eccentricity,
+    body: &CelestialBody,
+    orbital_period: Seconds,
+) -> Result<Radians, &'static str> {
+    let k = rate_factor(a, e, body)?;
+    if orbital_period.value() <= 0.0 || !orbital_period.value().is_finite() {
+        return Err("Orbital period must be positive");
+    }
+    if body.j2() <= 0.0 {
+        return Err("Body has no oblateness to precess the node");
+    }
+    let required = TAU / orbital_period.value();
+    let cos_i = -required / (1.5 * k);
+    if cos_i < -1.0 {
+        return Err("No sun-synchronous inclination exists at this altitude");
+    }
+    Ok(Radians(acos(cos_i)))
+}
+
+/// Eccentricity that freezes the argument of periapsis at inclination `i`.
+///
+/// Balances the J3 drift of the eccentricity vector against the J2
+/// rotation of periapsis, `e ≈ -½ (J₃/J₂)(R/p) sin i`, with `p = a(1 - e²)`
+/// solved iteratively. Periapsis sits at 90° when J3 is negative, as for
+/// Earth, and at 270° when it is positive. Near [`CRITICAL_INCLINATION`]
+/// J2 alone freezes periapsis and any small eccentricity will do.
+pub fn frozen_eccentricity(
+    a: Meters,
+    i: Radians,
+    body: &CelestialBody,
+) -> Result<FrozenOrbit, &'static str> {
+    if a.value() <= 0.0 || !a.value().is_finite() {
+        return Err("Semi-major axis must be positive");
+    }
+    if !i.value().is_finite() || i.value() < 0.0 || i.value() > PI {
+        return Err("Inclination must be between 0 and π");
+    }
+    let j2 = body.j2();
+    if j2 == 0.0 {
+        return Err("Body has no oblateness to balance against");
+    }
+    let ratio = Meters::from(body.equatorial_radius).value() / a.value();
+    let scale = -0.5 * body.zonal_harmonic(3) / j2 * ratio * sin(i.value());
+
+    let mut e: Real = 0.0;
+    for _ in 0..FROZEN_MAX_ITERATIONS {
+        let next = scale / (1.0 - e * e);
+        let converged = libm::fabs(next - e) <= 1e-15;
+        e = next;
+        if converged {
+            if libm::fabs(e) >= 1.0 {
+                return Err("No bound frozen orbit exists at this semi-major axis");
+            }
+            return Ok(FrozenOrbit {
+                eccentricity: Eccentricity::new(libm::fabs(e))?,
+                argument_of_periapsis: Radians(if e >= 0.0 { PI / 2.0 } else { 1.5 * PI }),
+            });
+        }
+    }
+    Err("Frozen eccentricity did not converge")
+}
+
+/// `n J₂ (R/p)²` for an orbit of the given size and shape about `body`
+fn rate_factor(a: Meters, e: Eccentricity, body: &CelestialBody) -> Result<Real, &'static str> {
+    let mu = body.mu;
+    if mu.value() <= 0.0 || !mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    if a.value() <= 0.0 || !a.value().is_finite() {
+        return Err("Semi-major axis must be positive");
+    }
+    let e = e.value();
+    if e >= 1.0 {
+        return Err("Secular rates are only defined for bound orbits");
+    }
+    let a = a.value();
+    let n = sqrt(mu.value() / (a * a * a));
+    Ok(oblateness_factor(n, Meters(a * (1.0 - e * e)), body))
+}
+
+fn oblateness_factor(n: Real, p: Meters, body: &CelestialBody) -> Real {
+    let ratio = Meters::from(body.equatorial_radius).value() / p.value();
+    n * body.j2() * ratio * ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS, MOON};
+    use crate::forces::{ForceModel, PointMass, ZonalHarmonics, propagate};
+    use crate::integrators::{RungeKuttaFehlberg78, Tolerances};
+    use crate::utils::Degrees;
+    use approx::assert_relative_eq;
+
+    const DAY: Real = 86_400.0;
+
+    fn circular(altitude: Real, inclination: Degrees) -> KeplerianElements {
+        KeplerianElements::new(
+            Meters(6_378_137.0 + altitude),
+            Eccentricity::new(0.0).unwrap(),
+            inclination.into(),
+            Radians(0.3),
+            Radians::ZERO,
+            Radians::ZERO,
+            EARTH.mu,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_critical_inclination() {
+        let s = sin(CRITICAL_INCLINATION.value());
+        assert_relative_eq!(4.0 - 5.0 * s * s, 0.0, epsilon = 1e-14);
+        let rates =
+            j2_secular_rates(&circular(1_000_000.0, CRITICAL_INCLINATION.into()), &EARTH).unwrap();
+        assert_relative_eq!(rates.argument_of_periapsis.value(), 0.0, epsilon = 1e-20);
+    }
+
+    #[test]
+    fn test_iss_rates() {
+        // Vallado Example 9-2 style check: node ≈ -5.0°/day, periapsis ≈ +3.7°/day
+        let rates = j2_secular_rates(&circular(400_000.0, Degrees(51.6)), &EARTH).unwrap();
+        let raan = Degrees::from(Radians(rates.raan.value() * DAY)).value();
+        let argp = Degrees::from(Radians(rates.argument_of_periapsis.value() * DAY)).value();
+        assert_relative_eq!(raan, -5.0, epsilon = 0.1);
+        assert_relative_eq!(argp, 3.7, epsilon = 0.1);
+
+        // Polar orbits do not precess; retrograde orbits precess eastward
+        let polar = j2_secular_rates(&circular(400_000.0, Degrees(90.0)), &EARTH).unwrap();
+        assert_relative_eq!(polar.raan.value(), 0.0, epsilon = 1e-20);
+        let retro = j2_secular_rates(&circular(400_000.0, Degrees(120.0)), &EARTH).unwrap();
+        assert!(retro.raan.value() > 0.0);
+    }
+
+    #[test]
+    fn test_rates_match_cowell_propagation() {
+        let elements = circular(700_000.0, Degrees(45.0));
+        let model = PointMass::new(&EARTH).plus(ZonalHarmonics::j2(&EARTH).unwrap());
+        let integrator = RungeKuttaFehlberg78::new(Tolerances::new(1e-6, 1e-12));
+        let state = elements.to_state_vector();
+        let later = propagate(
+            &model,
+            &integrator,
+            &state,
+            Seconds(2.0 * DAY),
+            Seconds(30.0),
+        )
+        .unwrap();
+        let osculating = KeplerianElements::from_state_vector(&later, EARTH.mu).unwrap();
+
+        let mean = secular_drift(&elements, &EARTH, Seconds(2.0 * DAY)).unwrap();
+        let drift = mean.raan().value() - elements.raan().value();
+        let measured = osculating.raan().value() - elements.raan().value();
+        assert_relative_eq!(measured, drift, max_relative = 0.02);
+    }
+
+    #[test]
+    fn test_secular_drift_preserves_shape() {
+        let elements = circular(800_000.0, Degrees(98.6));
+        let later = secular_drift(&elements, &EARTH, Seconds(30.0 * DAY)).unwrap();
+        assert_eq!(later.semi_major_axis(), elements.semi_major_axis());
+        assert_eq!(later.eccentricity(), elements.eccentricity());
+        assert_eq!(later.inclination(), elements.inclination());
+        assert!(later.raan().value() != elements.raan().value());
+    }
+
+    #[test]
+    fn test_sun_synchronous_inclination() {
+        // Classic figures: 800 km → 98.6°, 500 km → 97.4°
+        let zero = Eccentricity::new(0.0).unwrap();
+        let at = |altitude: Real| {
+            let a = Meters(6_378_137.0 + altitude);
+            Degrees::from(
+                sun_synchronous_inclination(a, zero, &EARTH, EARTH_SIDEREAL_YEAR).unwrap(),
+            )
+            .value()
+        };
+        assert_relative_eq!(at(800_000.0), 98.6, epsilon = 0.05);
+        assert_relative_eq!(at(500_000.0), 97.4, epsilon = 0.05);
+
+        // The node then turns 360° per year
+        let a = Meters(6_378_137.0 + 800_000.0);
+        let i = sun_synchronous_inclination(a, zero, &EARTH, EARTH_SIDEREAL_YEAR).unwrap();
+        let elements = KeplerianElements::new(
+            a,
+            zero,
+            i,
+            Radians::ZERO,
+            Radians::ZERO,
+            Radians::ZERO,
+            EARTH.mu,
+        )
+        .unwrap();
+        let rates = j2_secular_rates(&elements, &EARTH).unwrap();
+        assert_relative_eq!(
+            rates.raan.value() * EARTH_SIDEREAL_YEAR.value(),
+            TAU,
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_sun_synchronous_out_of_reach() {
+        let zero = Eccentricity::new(0.0).unwrap();
+        let geo = Meters(42_164_000.0);
+        assert!(sun_synchronous_inclination(geo, zero, &EARTH, EARTH_SIDEREAL_YEAR).is_err());
+        assert!(sun_synchronous_inclination(geo, zero, &EARTH, Seconds(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_frozen_eccentricity() {
+        // Earth's negative J3 freezes periapsis over the north, e ≈ 0.001
+        let a = Meters(6_378_137.0 + 800_000.0);
+        let frozen = frozen_eccentricity(a, Degrees(98.6).into(), &EARTH).unwrap();
+        assert_relative_eq!(frozen.eccentricity.value(), 1.03e-3, max_relative = 0.01);
+        assert_relative_eq!(frozen.argument_of_periapsis.value(), PI / 2.0);
+
+        // Mars' positive J3 freezes it over the south
+        let a = Meters(3_396_190.0 + 400_000.0);
+        let frozen = frozen_eccentricity(a, Degrees(93.0).into(), &MARS).unwrap();
+        assert_relative_eq!(frozen.argument_of_periapsis.value(), 1.5 * PI);
+
+        // Equatorial orbits need no eccentricity
+        let frozen = frozen_eccentricity(a, Radians::ZERO, &MARS).unwrap();
+        assert_relative_eq!(frozen.eccentricity.value(), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(frozen_eccentricity(Meters(-1.0), Radians::ZERO, &EARTH).is_err());
+        assert!(frozen_eccentricity(Meters(7e6), Radians(4.0), &EARTH).is_err());
+
+        let no_harmonics = CelestialBody {
+            zonal_harmonics: &[],
+            ..MOON
+        };
+        assert!(frozen_eccentricity(Meters(2e6), Radians(1.0), &no_harmonics).is_err());
+
+        // With J3 as large as J2, e(1 - e²) = ½ has no solution to settle on
+        let lumpy = CelestialBody {
+            zonal_harmonics: &[1e-3, -1e-3],
+            ..MOON
+        };
+        let surface = Meters::from(MOON.equatorial_radius);
+        assert!(frozen_eccentricity(surface, Radians(PI / 2.0), &lumpy).is_err());
+        let zero = Eccentricity::new(0.0).unwrap();
+        assert!(
+            sun_synchronous_inclination(Meters(2e6), zero, &no_harmonics, Seconds(1e6)).is_err()
+        );
+    }
+}
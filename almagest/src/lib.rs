@@ -44,6 +44,17 @@
 //! - [`materials`] - Database of aerospace materials and their properties
 //! - [`tethers`] - Space tether analysis and characteristic velocity calculations
 //! - [`kepler`] - Orbital mechanics using Keplerian elements
+//! - [`atmosphere`] - Atmospheric density models for Earth and Mars, with drag and heating loads
+//! - [`cr3bp`] - Circular restricted three-body problem: Lagrange points, Jacobi constant and rotating-frame dynamics
+//! - [`decay`] - Orbit-averaged drag decay, orbital lifetime and reboost budgets
+//! - [`events`] - Event detection during propagation: apsides, altitude, nodes, eclipses and tether catch windows
+//! - [`forces`] - Perturbing force models for Cowell propagation: harmonics, third body, drag and SRP
+//! - [`gravity`] - Spherical-harmonic gravity fields and ICGEM/PDS coefficient file readers
+//! - [`integrators`] - Runge-Kutta integrators with adaptive step control and dense output
+//! - [`iod`] - Initial orbit determination: Gibbs, Herrick-Gibbs and Gauss
+//! - [`maneuvers`] - Impulsive transfers: Hohmann, bi-elliptic and plane changes
+//! - [`patched_conics`] - Interplanetary transfers: escape and capture burns and the heliocentric leg
+//! - [`porkchop`] - Launch-window grids of C3, arrival v∞ and time of flight, with CSV output
 //! - [`celestials`] - Properties of celestial bodies (planets, moons, etc.)
 //!
 //! ## Mathematical Foundation
@@ -68,8 +79,25 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod atmosphere;
 pub mod celestials;
+pub mod cr3bp;
+pub mod decay;
+pub mod events;
+pub mod forces;
+pub mod gravity;
+pub mod integrators;
+pub mod iod;
 pub mod kepler;
+pub mod maneuvers;
 pub mod materials;
+pub mod patched_conics;
+pub mod porkchop;
 pub mod tethers;
 pub mod utils;
+
+#[cfg(test)]
+mod test_helpers;
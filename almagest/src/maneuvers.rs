@@ -0,0 +1,455 @@
+//! # Impulsive Maneuvers
+//!
+//! Δv budgets and transfer times for conventional propulsive transfers:
+//! Hohmann, bi-elliptic, and plane changes. Every burn is treated as an
+//! instantaneous change in velocity, and Δv is reported as a positive
+//! magnitude in [`MetersPerSecond`] — the same units returned by
+//! `modeling::Tether::calc_impulse` — so tether throws and rocket burns can be
+//! compared directly.
+//!
+//! Transfer orbits are returned as [`Ellipse`] values sharing the focus of the
+//! departure orbit.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::EARTH;
+//! use almagest::maneuvers::hohmann;
+//! use almagest::utils::Meters;
+//!
+//! // Low Earth orbit to geostationary orbit
+//! let transfer = hohmann(Meters(6_678_000.0), Meters(42_164_000.0), EARTH.mu)
+//!     .expect("Valid orbits");
+//! println!("Total Δv: {} m/s", transfer.total_delta_v().value());
+//! println!("Transfer time: {} s", transfer.time_of_flight.value());
+//! ```
+
+use libm::{cos, fabs, sin, sqrt};
+
+use crate::kepler::{Ellipse, Point};
+use crate::utils::{
+    Eccentricity, Meters, MetersCubedPerSecondSquared, MetersPerSecond, Radians, Real, Seconds,
+    golden_section_minimum,
+};
+
+/// A two-burn transfer along half of a single transfer ellipse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HohmannTransfer {
+    /// The transfer ellipse, tangent to both orbits
+    pub transfer: Ellipse,
+    /// Distance from the focus at which the first burn is made
+    pub departure_radius: Meters,
+    /// Distance from the focus at which the second burn is made
+    pub arrival_radius: Meters,
+    /// Magnitude of the first burn
+    pub departure_delta_v: MetersPerSecond,
+    /// Magnitude of the second burn
+    pub arrival_delta_v: MetersPerSecond,
+    /// Inclination change combined with the first burn
+    pub departure_plane_change: Radians,
+    /// Inclination change combined with the second burn
+    pub arrival_plane_change: Radians,
+    /// Time spent coasting on the transfer ellipse
+    pub time_of_flight: Seconds,
+}
+
+impl HohmannTransfer {
+    /// Sum of both burn magnitudes
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+}
+
+/// A three-burn transfer through an intermediate apoapsis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiEllipticTransfer {
+    /// Ellipse from the initial orbit out to the intermediate radius
+    pub first_transfer: Ellipse,
+    /// Ellipse from the intermediate radius down (or up) to the final orbit
+    pub second_transfer: Ellipse,
+    /// Magnitudes of the departure, intermediate and arrival burns
+    pub delta_v: [MetersPerSecond; 3],
+    /// Time spent coasting on both transfer ellipses
+    pub time_of_flight: Seconds,
+}
+
+impl BiEllipticTransfer {
+    /// Sum of all three burn magnitudes
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.delta_v[0] + self.delta_v[1] + self.delta_v[2]
+    }
+}
+
+/// Hohmann transfer between two coplanar circular orbits.
+///
+/// # Arguments
+/// * `r1` - Radius of the initial circular orbit
+/// * `r2` - Radius of the final circular orbit
+/// * `mu` - Standard gravitational parameter of the central body
+pub fn hohmann(
+    r1: Meters,
+    r2: Meters,
+    mu: MetersCubedPerSecondSquared,
+) -> Result<HohmannTransfer, &'static str> {
+    hohmann_between(&circle(r1)?, &circle(r2)?, mu)
+}
+
+/// Hohmann-style transfer between two coplanar, coaxial elliptic orbits.
+///
+/// Both orbits are assumed to share a line of apsides with their periapses on
+/// the same side. The transfer runs either from the initial periapsis to the
+/// final apoapsis or from the initial apoapsis to the final periapsis;
+/// whichever needs less Δv is returned.
+pub fn hohmann_between(
+    from: &Ellipse,
+    to: &Ellipse,
+    mu: MetersCubedPerSecondSquared,
+) -> Result<HohmannTransfer, &'static str> {
+    validate_mu(mu)?;
+    if from.eccentricity().value() >= 1.0 || to.eccentricity().value() >= 1.0 {
+        return Err("Transfers are only defined between bound orbits");
+    }
+
+    let coplanar = (Radians::ZERO, Radians::ZERO);
+    let via_periapsis = apse_transfer(from, to, (from.periapsis(), to.apoapsis()), coplanar, mu)?;
+    let via_apoapsis = apse_transfer(from, to, (from.apoapsis(), to.periapsis()), coplanar, mu)?;
+
+    if via_periapsis.total_delta_v() <= via_apoapsis.total_delta_v() {
+        Ok(via_periapsis)
+    } else {
+        Ok(via_apoapsis)
+    }
+}
+
+/// Hohmann transfer between circular orbits that also changes inclination.
+///
+/// The plane change is split between the two burns to minimize the total
+/// Δv. Most of it ends up on whichever burn happens at the lower speed,
+/// which for a raising transfer is the arrival burn.
+///
+/// # Arguments
+/// * `r1` - Radius of the initial circular orbit
+/// * `r2` - Radius of the final circular orbit
+/// * `inclination_change` - Angle between the initial and final orbit planes
+/// * `mu` - Standard gravitational parameter of the central body
+pub fn hohmann_with_plane_change(
+    r1: Meters,
+    r2: Meters,
+    inclination_change: Radians,
+    mu: MetersCubedPerSecondSquared,
+) -> Result<HohmannTransfer, &'static str> {
+    validate_mu(mu)?;
+    let (from, to) = (circle(r1)?, circle(r2)?);
+    let total = inclination_change.value();
+    let split = |fraction: Real| (Radians(fraction * total), Radians((1.0 - fraction) * total));
+    let transfer = |fraction: Real| {
+        apse_transfer(&from, &to, (r1, r2), split(fraction), mu)
+            .map(|t| t.total_delta_v().value())
+            .unwrap_or(Real::INFINITY)
+    };
+
+    // The total Δv is convex in the split, so golden-section search suffices
+    let fraction = golden_section_minimum(transfer, 0.0, 1.0, 80);
+    apse_transfer(&from, &to, (r1, r2), split(fraction), mu)
+}
+
+/// Bi-elliptic transfer between two coplanar circular orbits.
+///
+/// For large radius ratios (above roughly 11.94) a bi-elliptic transfer with
+/// a sufficiently distant intermediate apoapsis needs less Δv than a Hohmann
+/// transfer, at the cost of a much longer flight time.
+///
+/// # Arguments
+/// * `r1` - Radius of the initial circular orbit
+/// * `r2` - Radius of the final circular orbit
+/// * `r_b` - Intermediate apoapsis radius; must be at least as large as both
+/// * `mu` - Standard gravitational parameter of the central body
+pub fn bi_elliptic(
+    r1: Meters,
+    r2: Meters,
+    r_b: Meters,
+    mu: MetersCubedPerSecondSquared,
+) -> Result<BiEllipticTransfer, &'static str> {
+    validate_mu(mu)?;
+    circle(r1)?;
+    circle(r2)?;
+    if r_b.value() < r1.value() || r_b.value() < r2.value() {
+        return Err("Intermediate radius must not be inside either orbit");
+    }
+
+    let f = origin();
+    let first = Ellipse::from_periapsis_apoapsis(r1, r_b, f);
+    let second = Ellipse::from_periapsis_apoapsis(r2, r_b, f);
+
+    let v1 = vis_viva(mu, r1, r1);
+    let v2 = vis_viva(mu, r2, r2);
+    let first_a = first.semi_major_axis();
+    let second_a = second.semi_major_axis();
+
+    let delta_v = [
+        MetersPerSecond(fabs(vis_viva(mu, r1, first_a) - v1)),
+        MetersPerSecond(fabs(
+            vis_viva(mu, r_b, second_a) - vis_viva(mu, r_b, first_a),
+        )),
+        MetersPerSecond(fabs(vis_viva(mu, r2, second_a) - v2)),
+    ];
+    let time_of_flight = Seconds((first.period(mu)?.value() + second.period(mu)?.value()) / 2.0);
+
+    Ok(BiEllipticTransfer {
+        first_transfer: first,
+        second_transfer: second,
+        delta_v,
+        time_of_flight,
+    })
+}
+
+/// Δv to rotate a velocity through `angle` without changing its magnitude.
+///
+/// `Δv = 2v·sin(Δi/2)`
+pub fn plane_change(speed: MetersPerSecond, angle: Radians) -> MetersPerSecond {
+    MetersPerSecond(2.0 * speed.value() * fabs(sin(angle.value() / 2.0)))
+}
+
+/// Δv to change speed from `v1` to `v2` while rotating through `angle`.
+///
+/// Law of cosines: `Δv = √(v₁² + v₂² - 2v₁v₂·cos Δi)`
+pub fn combined_plane_change(
+    v1: MetersPerSecond,
+    v2: MetersPerSecond,
+    angle: Radians,
+) -> MetersPerSecond {
+    let (a, b) = (v1.value(), v2.value());
+    MetersPerSecond(sqrt(
+        (a * a + b * b - 2.0 * a * b * cos(angle.value())).max(0.0),
+    ))
+}
+
+/// Transfer tangent to `from` at `r_dep` and to `to` at `r_arr`, rotating
+/// the orbit plane by `dep_angle` at the first burn and `arr_angle` at the second
+fn apse_transfer(
+    from: &Ellipse,
+    to: &Ellipse,
+    (r_dep, r_arr): (Meters, Meters),
+    (dep_angle, arr_angle): (Radians, Radians),
+    mu: MetersCubedPerSecondSquared,
+) -> Result<HohmannTransfer, &'static str> {
+    let (r_p, r_a) = if r_dep.value() <= r_arr.value() {
+        (r_dep, r_arr)
+    } else {
+        (r_arr, r_dep)
+    };
+    let transfer = Ellipse::from_periapsis_apoapsis(r_p, r_a, from.primary_focus());
+    let a_t = transfer.semi_major_axis();
+
+    let v_from = MetersPerSecond(vis_viva(mu, r_dep, from.semi_major_axis()));
+    let v_dep = MetersPerSecond(vis_viva(mu, r_dep, a_t));
+    let v_arr = MetersPerSecond(vis_viva(mu, r_arr, a_t));
+    let v_to = MetersPerSecond(vis_viva(mu, r_arr, to.semi_major_axis()));
+
+    Ok(HohmannTransfer {
+        transfer,
+        departure_radius: r_dep,
+        arrival_radius: r_arr,
+        departure_delta_v: combined_plane_change(v_from, v_dep, dep_angle),
+        arrival_delta_v: combined_plane_change(v_arr, v_to, arr_angle),
+        departure_plane_change: dep_angle,
+        arrival_plane_change: arr_angle,
+        time_of_flight: Seconds(transfer.period(mu)?.value() / 2.0),
+    })
+}
+
+/// Speed at radius `r` on an orbit with semi-major axis `a`
+fn vis_viva(mu: MetersCubedPerSecondSquared, r: Meters, a: Meters) -> Real {
+    sqrt(mu.value() * (2.0 / r.value() - 1.0 / a.value()))
+}
+
+fn circle(r: Meters) -> Result<Ellipse, &'static str> {
+    if r.value() <= 0.0 || !r.value().is_finite() {
+        return Err("Orbit radius must be positive");
+    }
+    Ok(Ellipse::new(Eccentricity::new(0.0)?, origin(), r))
+}
+
+fn origin() -> Point {
+    Point {
+        x: Meters(0.0),
+        y: Meters(0.0),
+    }
+}
+
+fn validate_mu(mu: MetersCubedPerSecondSquared) -> Result<(), &'static str> {
+    if mu.value() <= 0.0 || !mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::EARTH;
+    use crate::utils::{Degrees, PI};
+    use approx::assert_relative_eq;
+
+    const KM: Real = 1_000.0;
+    const EARTH_RADIUS: Real = 6_378.137 * KM;
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 6-1
+    #[test]
+    fn hohmann_matches_vallado() {
+        let r1 = Meters(EARTH_RADIUS + 191.344_11 * KM);
+        let r2 = Meters(EARTH_RADIUS + 35_781.348_57 * KM);
+        let transfer = hohmann(r1, r2, EARTH.mu).unwrap();
+
+        assert_relative_eq!(transfer.departure_delta_v.value(), 2_457.038, epsilon = 1.0);
+        assert_relative_eq!(transfer.arrival_delta_v.value(), 1_478.187, epsilon = 1.0);
+        assert_relative_eq!(transfer.total_delta_v().value(), 3_935.224, epsilon = 1.0);
+        assert_relative_eq!(
+            transfer.time_of_flight.value() / 3_600.0,
+            5.256,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(transfer.transfer.periapsis().value(), r1.value());
+        assert_relative_eq!(
+            transfer.transfer.apoapsis().value(),
+            r2.value(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn hohmann_down_mirrors_hohmann_up() {
+        let (low, high) = (Meters(7_000.0 * KM), Meters(20_000.0 * KM));
+        let up = hohmann(low, high, EARTH.mu).unwrap();
+        let down = hohmann(high, low, EARTH.mu).unwrap();
+
+        assert_relative_eq!(up.departure_delta_v.value(), down.arrival_delta_v.value());
+        assert_relative_eq!(up.arrival_delta_v.value(), down.departure_delta_v.value());
+        assert_relative_eq!(up.time_of_flight.value(), down.time_of_flight.value());
+    }
+
+    #[test]
+    fn elliptic_transfer_picks_cheaper_apse() {
+        let f = Point {
+            x: Meters(0.0),
+            y: Meters(0.0),
+        };
+        let from = Ellipse::from_periapsis_apoapsis(Meters(7_000.0 * KM), Meters(12_000.0 * KM), f);
+        let to = Ellipse::from_periapsis_apoapsis(Meters(30_000.0 * KM), Meters(40_000.0 * KM), f);
+        let transfer = hohmann_between(&from, &to, EARTH.mu).unwrap();
+
+        // Burning at periapsis exploits the Oberth effect
+        assert_eq!(transfer.departure_radius, from.periapsis());
+        assert_eq!(transfer.arrival_radius, to.apoapsis());
+
+        let circular = hohmann(Meters(7_000.0 * KM), Meters(40_000.0 * KM), EARTH.mu).unwrap();
+        assert!(transfer.total_delta_v() < circular.total_delta_v());
+    }
+
+    // Vallado, "Fundamentals of Astrodynamics and Applications", Example 6-2
+    #[test]
+    fn bi_elliptic_matches_vallado() {
+        let r1 = Meters(EARTH_RADIUS + 191.344_11 * KM);
+        let r_b = Meters(EARTH_RADIUS + 503_873.0 * KM);
+        let r2 = Meters(EARTH_RADIUS + 376_310.0 * KM);
+        let transfer = bi_elliptic(r1, r2, r_b, EARTH.mu).unwrap();
+
+        assert_relative_eq!(transfer.delta_v[0].value(), 3_156.233, epsilon = 1.0);
+        assert_relative_eq!(transfer.delta_v[1].value(), 677.813, epsilon = 1.0);
+        assert_relative_eq!(transfer.delta_v[2].value(), 70.946, epsilon = 1.0);
+        assert_relative_eq!(transfer.total_delta_v().value(), 3_904.992, epsilon = 1.0);
+        assert_relative_eq!(
+            transfer.time_of_flight.value() / 3_600.0,
+            593.92,
+            epsilon = 0.1
+        );
+    }
+
+    #[test]
+    fn bi_elliptic_beats_hohmann_for_large_ratios() {
+        let r1 = Meters(7_000.0 * KM);
+        let r2 = Meters(20.0 * 7_000.0 * KM);
+        let r_b = Meters(1_000.0 * 7_000.0 * KM);
+
+        let direct = hohmann(r1, r2, EARTH.mu).unwrap();
+        let indirect = bi_elliptic(r1, r2, r_b, EARTH.mu).unwrap();
+        assert!(indirect.total_delta_v() < direct.total_delta_v());
+        assert!(indirect.time_of_flight > direct.time_of_flight);
+    }
+
+    #[test]
+    fn plane_changes() {
+        let v = MetersPerSecond(7_500.0);
+        // An equilateral velocity triangle
+        assert_relative_eq!(
+            plane_change(v, Radians(PI / 3.0)).value(),
+            v.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            combined_plane_change(v, v, Radians(PI / 3.0)).value(),
+            plane_change(v, Radians(PI / 3.0)).value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            combined_plane_change(v, MetersPerSecond(8_000.0), Radians::ZERO).value(),
+            500.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn split_plane_change_beats_either_extreme() {
+        let r1 = Meters(EARTH_RADIUS + 300.0 * KM);
+        let r2 = Meters(42_164.0 * KM);
+        let di: Radians = Degrees(28.5).into();
+        let transfer = hohmann_with_plane_change(r1, r2, di, EARTH.mu).unwrap();
+
+        let coplanar = hohmann(r1, r2, EARTH.mu).unwrap();
+        let v_parking = sqrt(EARTH.mu.value() / r1.value());
+        let v_periapsis = v_parking + coplanar.departure_delta_v.value();
+        let v_circular = sqrt(EARTH.mu.value() / r2.value());
+        let v_apoapsis = v_circular - coplanar.arrival_delta_v.value();
+        let all_at_departure =
+            combined_plane_change(MetersPerSecond(v_parking), MetersPerSecond(v_periapsis), di)
+                .value()
+                + coplanar.arrival_delta_v.value();
+        let all_at_arrival = coplanar.departure_delta_v.value()
+            + combined_plane_change(MetersPerSecond(v_apoapsis), MetersPerSecond(v_circular), di)
+                .value();
+
+        assert!(transfer.total_delta_v().value() <= all_at_departure);
+        assert!(transfer.total_delta_v().value() <= all_at_arrival);
+        assert!(transfer.arrival_plane_change > transfer.departure_plane_change);
+        assert_relative_eq!(
+            (transfer.departure_plane_change + transfer.arrival_plane_change).value(),
+            di.value(),
+            epsilon = 1e-12
+        );
+        // GTO to GEO with the classic Cape Canaveral inclination
+        assert_relative_eq!(transfer.total_delta_v().value(), 4_230.0, epsilon = 40.0);
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        let mu = EARTH.mu;
+        assert!(hohmann(Meters(0.0), Meters(7_000.0 * KM), mu).is_err());
+        assert!(
+            hohmann(
+                Meters(7_000.0 * KM),
+                Meters(8_000.0 * KM),
+                MetersCubedPerSecondSquared(0.0)
+            )
+            .is_err()
+        );
+        assert!(
+            bi_elliptic(
+                Meters(7_000.0 * KM),
+                Meters(9_000.0 * KM),
+                Meters(8_000.0 * KM),
+                mu
+            )
+            .is_err()
+        );
+    }
+}
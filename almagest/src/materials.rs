@@ -218,6 +218,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_realistic_material_properties() {
         // Test that material properties are within realistic ranges
 
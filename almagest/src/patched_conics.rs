@@ -0,0 +1,464 @@
+//! # Patched Conics
+//!
+//! Interplanetary transfers as a chain of two-body problems. Inside a
+//! body's sphere of influence only that body's gravity counts; outside it
+//! only the central body's (the Sun's, for planets) does. The trajectory
+//! leaves the departure body on a hyperbola, coasts on a heliocentric
+//! conic, and meets the arrival body on another hyperbola, with the pieces
+//! patched together at the sphere-of-influence boundaries. Those boundaries
+//! are small enough next to the heliocentric distances to be treated as
+//! points: the heliocentric leg runs from planet to planet, and its
+//! velocity relative to each planet is the hyperbolic excess velocity `v∞`
+//! of the planetocentric leg.
+//!
+//! The escape and capture burns are made at the periapsis of a parking
+//! orbit, where the hyperbola is tangent to it:
+//!
+//! `Δv = √(v∞² + 2μ/rₚ) - vₚ`
+//!
+//! where `vₚ` is the parking orbit's periapsis speed. A tether at either
+//! end that throws or catches at the same point supplies part of that burn;
+//! see [`PatchedConicTransfer::propulsive_delta_v`].
+//!
+//! - [`hohmann_transfer`] - idealized transfer between circular, coplanar
+//!   orbits using each body's mean distance
+//! - [`lambert_transfer`] - transfer between actual positions at departure
+//!   and arrival, from an ephemeris
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::{EARTH, MARS, SUN};
+//! use almagest::patched_conics::{Endpoint, ParkingOrbit, hohmann_transfer};
+//! use almagest::utils::Kilometers;
+//!
+//! let leo = Endpoint::new(&EARTH, ParkingOrbit::circular(Kilometers(300.0)));
+//! let mars_orbit = Endpoint::new(&MARS, ParkingOrbit::circular(Kilometers(400.0)));
+//!
+//! let transfer = hohmann_transfer(&SUN, &leo, &mars_orbit).expect("Valid transfer");
+//! println!(
+//!     "Escape {:.0} m/s, capture {:.0} m/s, {:.0} days",
+//!     transfer.departure_delta_v.value(),
+//!     transfer.arrival_delta_v.value(),
+//!     transfer.time_of_flight.value() / 86_400.0,
+//! );
+//! ```
+
+use libm::sqrt;
+
+use crate::celestials::CelestialBody;
+use crate::kepler::StateVector;
+use crate::kepler::lambert::{TransferDirection, solve_lambert};
+use crate::maneuvers::hohmann;
+use crate::utils::{
+    JoulesPerKilogram, Kilometers, Meters, MetersPerSecond, Real, Seconds, Vector3,
+};
+
+/// An orbit about the departure or arrival body, from which the escape
+/// burn is made or into which the capture burn places the spacecraft.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParkingOrbit {
+    /// Periapsis altitude above the body's mean radius, where the burn is
+    /// made
+    pub periapsis_altitude: Kilometers,
+    /// Apoapsis altitude above the body's mean radius
+    pub apoapsis_altitude: Kilometers,
+}
+
+impl ParkingOrbit {
+    /// A circular orbit at `altitude`
+    pub fn circular(altitude: Kilometers) -> Self {
+        Self {
+            periapsis_altitude: altitude,
+            apoapsis_altitude: altitude,
+        }
+    }
+}
+
+/// One end of an interplanetary transfer.
+#[derive(Clone, Copy, Debug)]
+pub struct Endpoint<'a> {
+    pub body: &'a CelestialBody<'a>,
+    pub parking_orbit: ParkingOrbit,
+}
+
+impl<'a> Endpoint<'a> {
+    pub fn new(body: &'a CelestialBody<'a>, parking_orbit: ParkingOrbit) -> Self {
+        Self {
+            body,
+            parking_orbit,
+        }
+    }
+}
+
+/// An interplanetary transfer and the burns at each end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PatchedConicTransfer {
+    /// Velocity relative to the central body on leaving the departure body
+    pub departure_velocity: Vector3,
+    /// Velocity relative to the central body on reaching the arrival body
+    pub arrival_velocity: Vector3,
+    /// Hyperbolic excess velocity relative to the departure body
+    pub departure_v_infinity: Vector3,
+    /// Hyperbolic excess velocity relative to the arrival body
+    pub arrival_v_infinity: Vector3,
+    /// Escape burn at the periapsis of the departure parking orbit
+    pub departure_delta_v: MetersPerSecond,
+    /// Capture burn at the periapsis of the arrival parking orbit
+    pub arrival_delta_v: MetersPerSecond,
+    /// Time on the heliocentric leg
+    pub time_of_flight: Seconds,
+}
+
+impl PatchedConicTransfer {
+    /// Launch energy `C3 = v∞²` at departure
+    pub fn c3(&self) -> JoulesPerKilogram {
+        let v = self.departure_v_infinity.norm();
+        JoulesPerKilogram(v * v)
+    }
+
+    /// Sum of the escape and capture burns
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+
+    /// Δv left for propulsion when a tether throw at departure and a tether
+    /// catch at arrival supply part of each burn. Each end saves at most its
+    /// own burn.
+    pub fn propulsive_delta_v(
+        &self,
+        departure_throw: MetersPerSecond,
+        arrival_catch: MetersPerSecond,
+    ) -> MetersPerSecond {
+        let remaining = |burn: MetersPerSecond, assist: MetersPerSecond| {
+            MetersPerSecond((burn.value() - assist.value().max(0.0)).max(0.0))
+        };
+        remaining(self.departure_delta_v, departure_throw)
+            + remaining(self.arrival_delta_v, arrival_catch)
+    }
+}
+
+/// Burn at the periapsis of `parking_orbit` to leave `body` with excess
+/// speed `v_infinity`.
+pub fn escape_delta_v(
+    body: &CelestialBody,
+    parking_orbit: &ParkingOrbit,
+    v_infinity: MetersPerSecond,
+) -> Result<MetersPerSecond, &'static str> {
+    periapsis_burn(body, parking_orbit, v_infinity)
+}
+
+/// Burn at periapsis to capture into `parking_orbit` about `body` when
+/// arriving with excess speed `v_infinity`. By symmetry it is the same as
+/// the escape burn.
+pub fn capture_delta_v(
+    body: &CelestialBody,
+    parking_orbit: &ParkingOrbit,
+    v_infinity: MetersPerSecond,
+) -> Result<MetersPerSecond, &'static str> {
+    periapsis_burn(body, parking_orbit, v_infinity)
+}
+
+/// Hohmann transfer between the mean distances of two bodies orbiting
+/// `central`, treating both orbits as circular and coplanar.
+///
+/// The departure body sits on the +x axis moving along +y, and the
+/// transfer ends half a revolution later on the -x axis.
+pub fn hohmann_transfer(
+    central: &CelestialBody,
+    departure: &Endpoint,
+    arrival: &Endpoint,
+) -> Result<PatchedConicTransfer, &'static str> {
+    let r1 = mean_distance(departure.body)?;
+    let r2 = mean_distance(arrival.body)?;
+    let mu = central.mu;
+    let transfer = hohmann(r1, r2, mu)?;
+
+    // Raising transfers speed up at departure and arrive slower than the
+    // arrival body; lowering transfers do the opposite
+    let sign = if r2.value() >= r1.value() { 1.0 } else { -1.0 };
+    let v1 = sqrt(mu.value() / r1.value());
+    let v2 = sqrt(mu.value() / r2.value());
+    let departure_v_infinity = Vector3::new(0.0, sign * transfer.departure_delta_v.value(), 0.0);
+    let arrival_v_infinity = Vector3::new(0.0, sign * transfer.arrival_delta_v.value(), 0.0);
+    let departure_velocity = Vector3::new(0.0, v1, 0.0) + departure_v_infinity;
+    let arrival_velocity = Vector3::new(0.0, -v2, 0.0) + arrival_v_infinity;
+
+    Ok(PatchedConicTransfer {
+        departure_velocity,
+        arrival_velocity,
+        departure_v_infinity,
+        arrival_v_infinity,
+        departure_delta_v: escape_delta_v(
+            departure.body,
+            &departure.parking_orbit,
+            transfer.departure_delta_v,
+        )?,
+        arrival_delta_v: capture_delta_v(
+            arrival.body,
+            &arrival.parking_orbit,
+            transfer.arrival_delta_v,
+        )?,
+        time_of_flight: transfer.time_of_flight,
+    })
+}
+
+/// Prograde transfer from the departure body's state to the arrival body's
+/// state `time_of_flight` later, both relative to `central`.
+///
+/// The heliocentric leg is the single-revolution solution of Lambert's
+/// problem, so the states usually come from an ephemeris evaluated at the
+/// departure and arrival epochs.
+pub fn lambert_transfer(
+    central: &CelestialBody,
+    departure: &Endpoint,
+    departure_state: &StateVector,
+    arrival: &Endpoint,
+    arrival_state: &StateVector,
+    time_of_flight: Seconds,
+) -> Result<PatchedConicTransfer, &'static str> {
+    let solution = solve_lambert(
+        &departure_state.position,
+        &arrival_state.position,
+        time_of_flight,
+        central.mu,
+        TransferDirection::Prograde,
+    )?;
+    let departure_v_infinity = solution.departure_velocity - departure_state.velocity;
+    let arrival_v_infinity = solution.arrival_velocity - arrival_state.velocity;
+
+    Ok(PatchedConicTransfer {
+        departure_velocity: solution.departure_velocity,
+        arrival_velocity: solution.arrival_velocity,
+        departure_v_infinity,
+        arrival_v_infinity,
+        departure_delta_v: escape_delta_v(
+            departure.body,
+            &departure.parking_orbit,
+            MetersPerSecond(departure_v_infinity.norm()),
+        )?,
+        arrival_delta_v: capture_delta_v(
+            arrival.body,
+            &arrival.parking_orbit,
+            MetersPerSecond(arrival_v_infinity.norm()),
+        )?,
+        time_of_flight,
+    })
+}
+
+/// `√(v∞² + 2μ/rₚ) - vₚ` at the periapsis of the parking orbit
+fn periapsis_burn(
+    body: &CelestialBody,
+    parking_orbit: &ParkingOrbit,
+    v_infinity: MetersPerSecond,
+) -> Result<MetersPerSecond, &'static str> {
+    let mu = body.mu.value();
+    if mu <= 0.0 || !mu.is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    let v = v_infinity.value();
+    if v < 0.0 || !v.is_finite() {
+        return Err("Hyperbolic excess speed must be non-negative");
+    }
+    let (r_p, r_a) = parking_radii(body, parking_orbit)?;
+    let parking_speed = sqrt(mu * (2.0 / r_p - 2.0 / (r_p + r_a)));
+    let hyperbolic_speed = sqrt(v * v + 2.0 * mu / r_p);
+    Ok(MetersPerSecond(hyperbolic_speed - parking_speed))
+}
+
+/// Periapsis and apoapsis radii in meters, checked to lie above the
+/// center of the body and inside its sphere of influence
+fn parking_radii(
+    body: &CelestialBody,
+    parking_orbit: &ParkingOrbit,
+) -> Result<(Real, Real), &'static str> {
+    let radius = |altitude: Kilometers| {
+        Meters::from(Kilometers(body.radius.value() + altitude.value())).value()
+    };
+    let r_p = radius(parking_orbit.periapsis_altitude);
+    let r_a = radius(parking_orbit.apoapsis_altitude);
+    if r_p <= 0.0 || !r_p.is_finite() || !r_a.is_finite() {
+        return Err("Parking orbit periapsis must be above the center of the body");
+    }
+    if r_a < r_p {
+        return Err("Parking orbit apoapsis must not be below its periapsis");
+    }
+    if let Some(soi) = body.sphere_of_influence
+        && r_a >= Meters::from(soi).value()
+    {
+        return Err("Parking orbit must lie inside the sphere of influence");
+    }
+    Ok((r_p, r_a))
+}
+
+fn mean_distance(body: &CelestialBody) -> Result<Meters, &'static str> {
+    body.semi_major_axis
+        .map(Meters::from)
+        .ok_or("Body has no mean distance from a primary")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS, MOON, SUN};
+    use crate::test_helpers::circular_ephemeris;
+    use approx::assert_relative_eq;
+
+    const DAY: Real = 86_400.0;
+
+    fn earth() -> Endpoint<'static> {
+        Endpoint::new(&EARTH, ParkingOrbit::circular(Kilometers(300.0)))
+    }
+
+    fn mars() -> Endpoint<'static> {
+        Endpoint::new(&MARS, ParkingOrbit::circular(Kilometers(300.0)))
+    }
+
+    #[test]
+    fn test_sphere_of_influence_radii() {
+        for (body, primary) in [(&EARTH, &SUN), (&MARS, &SUN), (&MOON, &EARTH)] {
+            let a = body.semi_major_axis.unwrap().value();
+            let laplace = a * libm::pow(body.mass.0 / primary.mass.0, 0.4);
+            assert_relative_eq!(
+                body.sphere_of_influence.unwrap().value(),
+                laplace,
+                max_relative = 1e-3
+            );
+        }
+        assert!(SUN.sphere_of_influence.is_none());
+    }
+
+    #[test]
+    fn test_earth_mars_hohmann() {
+        // Curtis, Orbital Mechanics for Engineering Students, Example 8.4
+        let transfer = hohmann_transfer(&SUN, &earth(), &mars()).unwrap();
+        assert_relative_eq!(
+            transfer.departure_v_infinity.norm(),
+            2_943.0,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            transfer.arrival_v_infinity.norm(),
+            2_648.0,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            transfer.departure_delta_v.value(),
+            3_590.0,
+            max_relative = 2e-3
+        );
+        assert_relative_eq!(
+            transfer.arrival_delta_v.value(),
+            2_090.0,
+            max_relative = 5e-3
+        );
+        assert_relative_eq!(
+            transfer.time_of_flight.value() / DAY,
+            259.0,
+            max_relative = 2e-3
+        );
+        assert_relative_eq!(
+            transfer.c3().value(),
+            2_943.0 * 2_943.0,
+            max_relative = 2e-3
+        );
+
+        // Arrival is slower than Mars, so v∞ points back along its motion
+        assert!(transfer.arrival_velocity.y > -sqrt(SUN.mu.value() / 2.279392e11));
+        assert!(transfer.arrival_v_infinity.y > 0.0);
+    }
+
+    #[test]
+    fn test_inbound_hohmann_mirrors_outbound() {
+        let outbound = hohmann_transfer(&SUN, &earth(), &mars()).unwrap();
+        let inbound = hohmann_transfer(&SUN, &mars(), &earth()).unwrap();
+        assert_relative_eq!(
+            inbound.departure_v_infinity.norm(),
+            outbound.arrival_v_infinity.norm(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            inbound.total_delta_v().value(),
+            outbound.total_delta_v().value(),
+            max_relative = 1e-12
+        );
+        assert!(inbound.departure_v_infinity.y < 0.0);
+    }
+
+    #[test]
+    fn test_lambert_transfer() {
+        let tof = Seconds(200.0 * DAY);
+        let earth_state = circular_ephemeris(&EARTH, 0.0)(Seconds(0.0));
+        let mars_state = circular_ephemeris(&MARS, 2.6)(Seconds(0.0));
+        let transfer =
+            lambert_transfer(&SUN, &earth(), &earth_state, &mars(), &mars_state, tof).unwrap();
+
+        let v_inf = transfer.departure_v_infinity;
+        assert_relative_eq!(
+            (transfer.departure_velocity - earth_state.velocity - v_inf).norm(),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            transfer.departure_delta_v.value(),
+            escape_delta_v(
+                &EARTH,
+                &earth().parking_orbit,
+                MetersPerSecond(v_inf.norm())
+            )
+            .unwrap()
+            .value()
+        );
+        // A faster transfer than Hohmann costs more
+        let hohmann = hohmann_transfer(&SUN, &earth(), &mars()).unwrap();
+        assert!(transfer.total_delta_v().value() > hohmann.total_delta_v().value());
+    }
+
+    #[test]
+    fn test_periapsis_burns() {
+        // With no excess speed, escape from a circular orbit costs (√2 - 1)v
+        let parking = ParkingOrbit::circular(Kilometers(300.0));
+        let r = 6_671_000.0;
+        let v = sqrt(EARTH.mu.value() / r);
+        let escape = escape_delta_v(&EARTH, &parking, MetersPerSecond(0.0)).unwrap();
+        assert_relative_eq!(escape.value(), (sqrt(2.0) - 1.0) * v, max_relative = 1e-12);
+
+        // Capturing into an ellipse is cheaper than into a circle
+        let ellipse = ParkingOrbit {
+            periapsis_altitude: Kilometers(300.0),
+            apoapsis_altitude: Kilometers(30_000.0),
+        };
+        let v_inf = MetersPerSecond(2_648.0);
+        assert!(
+            capture_delta_v(&MARS, &ellipse, v_inf).unwrap().value()
+                < capture_delta_v(&MARS, &parking, v_inf).unwrap().value()
+        );
+    }
+
+    #[test]
+    fn test_tether_assist() {
+        let transfer = hohmann_transfer(&SUN, &earth(), &mars()).unwrap();
+        let full = transfer.total_delta_v().value();
+        let assisted =
+            transfer.propulsive_delta_v(MetersPerSecond(1_000.0), MetersPerSecond(500.0));
+        assert_relative_eq!(assisted.value(), full - 1_500.0, max_relative = 1e-12);
+        // Assists beyond a burn save nothing more
+        let oversized = transfer.propulsive_delta_v(MetersPerSecond(1e5), MetersPerSecond(0.0));
+        assert_relative_eq!(oversized.value(), transfer.arrival_delta_v.value());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let from_sun = Endpoint::new(&SUN, ParkingOrbit::circular(Kilometers(1e6)));
+        assert!(hohmann_transfer(&SUN, &from_sun, &mars()).is_err());
+        let beyond_soi = ParkingOrbit::circular(Kilometers(1_000_000.0));
+        assert!(escape_delta_v(&EARTH, &beyond_soi, MetersPerSecond(3_000.0)).is_err());
+        let inverted = ParkingOrbit {
+            periapsis_altitude: Kilometers(500.0),
+            apoapsis_altitude: Kilometers(300.0),
+        };
+        assert!(capture_delta_v(&MARS, &inverted, MetersPerSecond(3_000.0)).is_err());
+        let parking = ParkingOrbit::circular(Kilometers(300.0));
+        assert!(escape_delta_v(&EARTH, &parking, MetersPerSecond(-1.0)).is_err());
+    }
+}
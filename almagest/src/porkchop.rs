@@ -0,0 +1,363 @@
+//! # Porkchop Plots
+//!
+//! Launch-window survey for transfers between two bodies orbiting the same
+//! central body. For every pair of departure and arrival epochs on a grid,
+//! the bodies' positions come from their ephemerides and Lambert's problem
+//! gives the connecting trajectory. Its cost is summarized by
+//!
+//! - **C3**: launch energy `v∞²` at departure, in the units launch vehicle
+//!   performance is quoted in
+//! - **Arrival v∞**: excess speed to be removed at arrival, by a capture
+//!   burn, a tether catch or aerocapture
+//! - **Time of flight**
+//!
+//! Contours of these over the grid have the shape that gives the plot its
+//! name, with the cheapest transfers in the middle of each lobe. Only
+//! single-revolution prograde transfers are considered, and pairs whose
+//! arrival is not after the departure are skipped.
+//!
+//! Points are delivered to a callback, so no allocation is needed; the
+//! CSV helpers write them to any [`core::fmt::Write`] such as a `String`.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use almagest::celestials::celestial_bodies::SUN;
+//! use almagest::kepler::StateVector;
+//! use almagest::porkchop::{EpochGrid, porkchop};
+//! use almagest::utils::{Seconds, Vector3};
+//!
+//! // Circular, coplanar stand-ins for real ephemerides
+//! let circular = |radius: f64, phase: f64| {
+//!     move |t: Seconds| {
+//!         let v = (SUN.mu.value() / radius).sqrt();
+//!         let angle = phase + v / radius * t.value();
+//!         let (sin, cos) = angle.sin_cos();
+//!         StateVector {
+//!             position: Vector3::new(radius * cos, radius * sin, 0.0),
+//!             velocity: Vector3::new(-v * sin, v * cos, 0.0),
+//!         }
+//!     }
+//! };
+//! let earth = circular(1.496e11, 0.0);
+//! let mars = circular(2.279e11, 0.8);
+//!
+//! let day = 86_400.0;
+//! let departures = EpochGrid::new(Seconds(0.0), Seconds(10.0 * day), 10);
+//! let arrivals = EpochGrid::new(Seconds(200.0 * day), Seconds(10.0 * day), 15);
+//!
+//! let mut best = f64::INFINITY;
+//! porkchop(&SUN, earth, mars, &departures, &arrivals, |point| {
+//!     best = best.min(point.c3.value());
+//! })
+//! .expect("Valid grid");
+//! println!("Lowest C3: {:.1} km²/s²", best / 1e6);
+//! ```
+
+use core::fmt::Write;
+
+use crate::celestials::CelestialBody;
+use crate::kepler::StateVector;
+use crate::kepler::lambert::{TransferDirection, solve_lambert};
+use crate::utils::{JoulesPerKilogram, MetersPerSecond, Real, Seconds};
+
+/// Seconds in a day, the unit of time of flight in CSV output
+const DAY: Real = 86_400.0;
+
+/// Evenly spaced epochs, `start + i·step` for `i` in `0..count`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EpochGrid {
+    pub start: Seconds,
+    pub step: Seconds,
+    pub count: usize,
+}
+
+impl EpochGrid {
+    pub fn new(start: Seconds, step: Seconds, count: usize) -> Self {
+        Self { start, step, count }
+    }
+
+    /// The `index`th epoch
+    pub fn epoch(&self, index: usize) -> Seconds {
+        Seconds(self.start.value() + index as Real * self.step.value())
+    }
+}
+
+/// Cost of the transfer for one pair of epochs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PorkchopPoint {
+    /// Position of the departure epoch in its grid
+    pub departure_index: usize,
+    /// Position of the arrival epoch in its grid
+    pub arrival_index: usize,
+    pub departure: Seconds,
+    pub arrival: Seconds,
+    pub time_of_flight: Seconds,
+    /// Launch energy, `v∞²` at departure
+    pub c3: JoulesPerKilogram,
+    /// Excess speed relative to the departure body
+    pub departure_v_infinity: MetersPerSecond,
+    /// Excess speed relative to the arrival body
+    pub arrival_v_infinity: MetersPerSecond,
+}
+
+impl PorkchopPoint {
+    /// Column names matching [`PorkchopPoint::write_csv_row`]
+    pub const CSV_HEADER: &'static str = "departure_s,arrival_s,time_of_flight_days,c3_km2_s2,departure_v_infinity_km_s,arrival_v_infinity_km_s";
+
+    /// Write the point as one CSV line: epochs in seconds, time of flight in
+    /// days, C3 in km²/s² and excess speeds in km/s
+    pub fn write_csv_row<W: Write>(&self, out: &mut W) -> core::fmt::Result {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            self.departure.value(),
+            self.arrival.value(),
+            self.time_of_flight.value() / DAY,
+            self.c3.value() / 1e6,
+            self.departure_v_infinity.value() / 1e3,
+            self.arrival_v_infinity.value() / 1e3,
+        )
+    }
+}
+
+/// Sweep every departure epoch against every arrival epoch.
+///
+/// `departure_ephemeris` and `arrival_ephemeris` give each body's state
+/// relative to `central` at an epoch. `on_point` is called in order of
+/// departure, then arrival, for each pair with a transfer; pairs whose
+/// arrival is not after the departure, or whose Lambert problem has no
+/// solution, are skipped.
+pub fn porkchop<D, A, P>(
+    central: &CelestialBody,
+    departure_ephemeris: D,
+    arrival_ephemeris: A,
+    departures: &EpochGrid,
+    arrivals: &EpochGrid,
+    mut on_point: P,
+) -> Result<(), &'static str>
+where
+    D: Fn(Seconds) -> StateVector,
+    A: Fn(Seconds) -> StateVector,
+    P: FnMut(PorkchopPoint),
+{
+    if central.mu.value() <= 0.0 || !central.mu.value().is_finite() {
+        return Err("Gravitational parameter must be positive");
+    }
+    for grid in [departures, arrivals] {
+        if !grid.start.value().is_finite() || !grid.step.value().is_finite() {
+            return Err("Epoch grid must be finite");
+        }
+        if grid.count > 1 && grid.step.value() <= 0.0 {
+            return Err("Epoch grid step must be positive");
+        }
+    }
+
+    for i in 0..departures.count {
+        let departure = departures.epoch(i);
+        let from = departure_ephemeris(departure);
+        for j in 0..arrivals.count {
+            let arrival = arrivals.epoch(j);
+            let time_of_flight = Seconds(arrival.value() - departure.value());
+            if time_of_flight.value() <= 0.0 {
+                continue;
+            }
+            let to = arrival_ephemeris(arrival);
+            let Ok(solution) = solve_lambert(
+                &from.position,
+                &to.position,
+                time_of_flight,
+                central.mu,
+                TransferDirection::Prograde,
+            ) else {
+                continue;
+            };
+            let v_departure = (solution.departure_velocity - from.velocity).norm();
+            let v_arrival = (solution.arrival_velocity - to.velocity).norm();
+            on_point(PorkchopPoint {
+                departure_index: i,
+                arrival_index: j,
+                departure,
+                arrival,
+                time_of_flight,
+                c3: JoulesPerKilogram(v_departure * v_departure),
+                departure_v_infinity: MetersPerSecond(v_departure),
+                arrival_v_infinity: MetersPerSecond(v_arrival),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Sweep the grid as [`porkchop`] does and write the header and one row
+/// per transfer as CSV to `out`.
+pub fn porkchop_csv<D, A, W>(
+    central: &CelestialBody,
+    departure_ephemeris: D,
+    arrival_ephemeris: A,
+    departures: &EpochGrid,
+    arrivals: &EpochGrid,
+    out: &mut W,
+) -> Result<(), &'static str>
+where
+    D: Fn(Seconds) -> StateVector,
+    A: Fn(Seconds) -> StateVector,
+    W: Write,
+{
+    const WRITE_FAILED: &str = "Failed to write CSV output";
+    writeln!(out, "{}", PorkchopPoint::CSV_HEADER).map_err(|_| WRITE_FAILED)?;
+    let mut written = Ok(());
+    porkchop(
+        central,
+        departure_ephemeris,
+        arrival_ephemeris,
+        departures,
+        arrivals,
+        |point| {
+            if written.is_ok() {
+                written = point.write_csv_row(out);
+            }
+        },
+    )?;
+    written.map_err(|_| WRITE_FAILED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::celestials::celestial_bodies::{EARTH, MARS, SUN};
+    use crate::patched_conics::{Endpoint, ParkingOrbit, hohmann_transfer};
+    use crate::test_helpers::circular_ephemeris;
+    use crate::utils::{Kilometers, Meters, PI};
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    /// Ephemerides phased for a Hohmann departure at t = 0, and its cost
+    fn hohmann_window() -> (
+        impl Fn(Seconds) -> StateVector,
+        impl Fn(Seconds) -> StateVector,
+        Real,
+        Real,
+    ) {
+        let parking = ParkingOrbit::circular(Kilometers(300.0));
+        let hohmann = hohmann_transfer(
+            &SUN,
+            &Endpoint::new(&EARTH, parking),
+            &Endpoint::new(&MARS, parking),
+        )
+        .unwrap();
+        let tof = hohmann.time_of_flight.value();
+        let r = Meters::from(MARS.semi_major_axis.unwrap()).value();
+        let mars_rate = sqrt(SUN.mu.value() / r) / r;
+        // Mars must arrive at the far side, 180° on, when the transfer does
+        let lead = PI - mars_rate * tof;
+        (
+            circular_ephemeris(&EARTH, 0.0),
+            circular_ephemeris(&MARS, lead),
+            hohmann.c3().value(),
+            tof,
+        )
+    }
+
+    #[test]
+    fn test_minimum_near_hohmann() {
+        let (earth, mars, hohmann_c3, hohmann_tof) = hohmann_window();
+        // Offset by half a step so no pair lies exactly 180° apart
+        let departures = EpochGrid::new(Seconds(-20.5 * DAY), Seconds(4.0 * DAY), 11);
+        let arrivals = EpochGrid::new(Seconds(hohmann_tof - 40.0 * DAY), Seconds(4.0 * DAY), 21);
+
+        let mut count = 0;
+        let mut best: Option<PorkchopPoint> = None;
+        porkchop(&SUN, &earth, &mars, &departures, &arrivals, |point| {
+            count += 1;
+            if best.is_none_or(|b| point.c3.value() < b.c3.value()) {
+                best = Some(point);
+            }
+        })
+        .unwrap();
+        assert_eq!(count, 11 * 21);
+
+        // No transfer between circular orbits beats Hohmann, and the grid's
+        // best lies near it
+        let best = best.unwrap();
+        assert!(best.c3.value() > 0.999 * hohmann_c3);
+        assert!(best.c3.value() < 1.2 * hohmann_c3, "{best:?}");
+        assert!((best.departure.value()).abs() < 10.0 * DAY);
+        assert!((best.time_of_flight.value() - hohmann_tof).abs() < 30.0 * DAY);
+    }
+
+    #[test]
+    fn test_points_match_lambert() {
+        let (earth, mars, _, _) = hohmann_window();
+        let departures = EpochGrid::new(Seconds(0.0), Seconds(DAY), 1);
+        let arrivals = EpochGrid::new(Seconds(200.0 * DAY), Seconds(DAY), 1);
+        let mut points = [None; 1];
+        porkchop(&SUN, &earth, &mars, &departures, &arrivals, |point| {
+            points[point.departure_index] = Some(point);
+        })
+        .unwrap();
+        let point = points[0].unwrap();
+
+        let from = earth(Seconds(0.0));
+        let to = mars(Seconds(200.0 * DAY));
+        let solution = solve_lambert(
+            &from.position,
+            &to.position,
+            Seconds(200.0 * DAY),
+            SUN.mu,
+            TransferDirection::Prograde,
+        )
+        .unwrap();
+        let v_inf = (solution.departure_velocity - from.velocity).norm();
+        assert_relative_eq!(point.departure_v_infinity.value(), v_inf);
+        assert_relative_eq!(point.c3.value(), v_inf * v_inf);
+        assert_relative_eq!(
+            point.arrival_v_infinity.value(),
+            (solution.arrival_velocity - to.velocity).norm()
+        );
+        assert_eq!(point.time_of_flight, Seconds(200.0 * DAY));
+    }
+
+    #[test]
+    fn test_skips_arrivals_before_departure() {
+        let (earth, mars, _, _) = hohmann_window();
+        let epochs = EpochGrid::new(Seconds(0.0), Seconds(30.0 * DAY), 4);
+        let mut pairs = 0;
+        porkchop(&SUN, &earth, &mars, &epochs, &epochs, |point| {
+            assert!(point.arrival_index > point.departure_index);
+            pairs += 1;
+        })
+        .unwrap();
+        assert_eq!(pairs, 6);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_csv() {
+        use std::{string::String, vec::Vec};
+
+        let (earth, mars, _, _) = hohmann_window();
+        let departures = EpochGrid::new(Seconds(0.0), Seconds(10.0 * DAY), 2);
+        let arrivals = EpochGrid::new(Seconds(250.0 * DAY), Seconds(10.0 * DAY), 3);
+        let mut csv = String::new();
+        porkchop_csv(&SUN, &earth, &mars, &departures, &arrivals, &mut csv).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(PorkchopPoint::CSV_HEADER));
+        let rows: Vec<_> = lines.collect();
+        assert_eq!(rows.len(), 6);
+        let fields: Vec<Real> = rows[0].split(',').map(|f| f.parse().unwrap()).collect();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[0], 0.0);
+        assert_relative_eq!(fields[2], 250.0);
+        assert_relative_eq!(fields[3], fields[4] * fields[4], max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let (earth, mars, _, _) = hohmann_window();
+        let backwards = EpochGrid::new(Seconds(0.0), Seconds(-DAY), 3);
+        let arrivals = EpochGrid::new(Seconds(200.0 * DAY), Seconds(DAY), 3);
+        assert!(porkchop(&SUN, &earth, &mars, &backwards, &arrivals, |_| {}).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! Assertions and fixtures shared by the unit tests
+
+use approx::assert_relative_eq;
+use libm::{sincos, sqrt};
+
+use crate::atmosphere::{Atmosphere, AtmosphereKind};
+use crate::celestials::CelestialBody;
+use crate::celestials::celestial_bodies::{EARTH, MOON, SUN};
+use crate::cr3bp::{EARTH_MOON_DISTANCE, ThreeBodySystem};
+use crate::kepler::StateVector;
+use crate::utils::{Meters, Real, Seconds, Vector3};
+
+/// Every component of `a` within `epsilon` of `b`
+pub fn assert_vectors_close(a: Vector3, b: Vector3, epsilon: Real) {
+    assert_relative_eq!(a.x, b.x, epsilon = epsilon);
+    assert_relative_eq!(a.y, b.y, epsilon = epsilon);
+    assert_relative_eq!(a.z, b.z, epsilon = epsilon);
+}
+
+/// Every position component within `position` meters and every velocity
+/// component within `velocity` m/s
+pub fn assert_states_close(a: &StateVector, b: &StateVector, position: Real, velocity: Real) {
+    assert_vectors_close(a.position, b.position, position);
+    assert_vectors_close(a.velocity, b.velocity, velocity);
+}
+
+/// The Earth–Moon circular restricted three-body system
+pub fn earth_moon() -> ThreeBodySystem {
+    ThreeBodySystem::new(&EARTH, &MOON, EARTH_MOON_DISTANCE.into()).unwrap()
+}
+
+/// Earth's atmosphere of the given kind
+pub fn earth_atmosphere(kind: AtmosphereKind) -> Atmosphere<'static> {
+    Atmosphere::for_body(&EARTH, kind).unwrap()
+}
+
+/// Ephemeris of a circular, coplanar heliocentric orbit at the body's mean
+/// distance, `phase` radians from +x at t = 0
+pub fn circular_ephemeris(
+    body: &CelestialBody,
+    phase: Real,
+) -> impl Fn(Seconds) -> StateVector + use<> {
+    let r = Meters::from(body.semi_major_axis.unwrap()).value();
+    let v = sqrt(SUN.mu.value() / r);
+    move |t| {
+        let (sin, cos) = sincos(phase + v / r * t.value());
+        StateVector {
+            position: Vector3::new(r * cos, r * sin, 0.0),
+            velocity: Vector3::new(-v * sin, v * cos, 0.0),
+        }
+    }
+}
@@ -522,7 +522,7 @@ mod tests {
         assert_relative_eq!(pbo_efficiency, 0.387, epsilon = 0.01);
 
         // Efficiency should be between 0 and 1
-        assert!(pbo_efficiency >= 0.0 && pbo_efficiency <= 1.0);
+        assert!((0.0..=1.0).contains(&pbo_efficiency));
 
         // Test efficiency with weaker material (aluminum)
         let aluminum_efficiency =
@@ -531,7 +531,7 @@ mod tests {
 
         // Aluminum should have lower efficiency than PBO
         assert!(aluminum_efficiency < pbo_efficiency);
-        assert!(aluminum_efficiency >= 0.0 && aluminum_efficiency <= 1.0);
+        assert!((0.0..=1.0).contains(&aluminum_efficiency));
     }
 
     #[test]
@@ -585,7 +585,7 @@ mod tests {
         // Test small tether (should have spin rate close to 1x)
         let small_tether = Meters(50e3); // 50 km
         let small_spin = momentum_exchange_spin_rate(small_tether, earth_radius).unwrap();
-        assert!(small_spin >= 1.0 && small_spin < 2.0);
+        assert!((1.0..2.0).contains(&small_spin));
 
         // Test medium tether
         let medium_tether = Meters(200e3); // 200 km
@@ -27,6 +27,13 @@
 //! | [`MetersPerSecondSquared`] | meter per second squared | m/s² | Acceleration, gravity |
 //! | [`MetersCubedByKilogramSecondsSquared`] | m³/(kg·s²) | m³/(kg·s²) | Gravitational constant G |
 //! | [`MetersCubedPerSecondSquared`] | m³/s² | m³/s² | Standard gravitational parameter μ |
+//! | [`Radians`] | radian | rad | Orbital angles, anomalies |
+//! | [`Degrees`] | degree | ° | Human-readable angles |
+//! | [`JoulesPerKilogram`] | joule per kilogram | J/kg | Specific orbital energy |
+//! | [`MetersSquaredPerSecond`] | m²/s | m²/s | Specific angular momentum |
+//! | [`KilogramMetersSquaredPerSecond`] | kg·m²/s | kg·m²/s | Angular momentum |
+//! | [`WattsPerMetersSquared`] | watt per square meter | W/m² | Heat flux |
+//! | [`KilogramsPerMetersSquared`] | kg/m² | kg/m² | Ballistic coefficient |
 //!
 //! ## Mathematical Operations
 //!
@@ -54,7 +61,7 @@
 
 use core::cmp::{PartialEq, PartialOrd};
 use core::fmt::{Debug, Display};
-use core::ops::{Add, Div, Mul, Sub};
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Floating-point type used throughout the library for maximum precision.
 ///
@@ -251,24 +258,203 @@ pub struct Seconds(pub Real);
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 pub struct RadiansPerSecond(pub Real);
 
+/// Angle measurement in radians.
+///
+/// Used for orbital angles such as inclination, right ascension of the
+/// ascending node, argument of periapsis, and the anomalies.
+///
+/// # Examples
+/// ```rust
+/// use almagest::utils::{Degrees, Radians};
+///
+/// let iss_inclination: Radians = Degrees(51.64).into();
+/// let quarter_turn = Radians(core::f64::consts::FRAC_PI_2);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Radians(pub Real);
+
+/// Angle measurement in degrees.
+///
+/// Convenient for human-readable input and output; convert to [`Radians`]
+/// before using in calculations.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Degrees(pub Real);
+
+/// Specific energy in J/kg (equivalent to m²/s²).
+///
+/// Used for the specific orbital energy `ε = v²/2 - μ/r`, which is negative
+/// for bound orbits, zero for parabolic and positive for hyperbolic.
+///
+/// # Examples
+/// ```rust
+/// use almagest::utils::JoulesPerKilogram;
+///
+/// let leo_energy = JoulesPerKilogram(-29.4e6);  // ~400 km circular orbit
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct JoulesPerKilogram(pub Real);
+
+/// Specific angular momentum in m²/s.
+///
+/// Angular momentum per unit mass, `h = |r × v|`. Created by multiplying
+/// [`Meters`] by [`MetersPerSecond`]; multiply by [`Kilograms`] to get a
+/// [`KilogramMetersSquaredPerSecond`].
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct MetersSquaredPerSecond(pub Real);
+
+/// Angular momentum in kg·m²/s.
+///
+/// Used for momentum bookkeeping between a tether and its payload.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct KilogramMetersSquaredPerSecond(pub Real);
+
+/// Heat flux in W/m².
+///
+/// Used for aerodynamic heating of surfaces moving through an atmosphere.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct WattsPerMetersSquared(pub Real);
+
+impl WattsPerMetersSquared {
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+/// Mass per unit area in kg/m².
+///
+/// Used for the ballistic coefficient `m / (C_D A)`, which sets how quickly
+/// drag slows a spacecraft.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct KilogramsPerMetersSquared(pub Real);
+
+impl KilogramsPerMetersSquared {
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
 impl MetersPerSecond {
     pub fn value(&self) -> Real {
         self.0
     }
 }
 
+impl Add for MetersPerSecond {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        MetersPerSecond(self.0 + rhs.0)
+    }
+}
+
+impl JoulesPerKilogram {
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl MetersSquaredPerSecond {
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl Mul<MetersPerSecond> for Meters {
+    type Output = MetersSquaredPerSecond;
+    fn mul(self, rhs: MetersPerSecond) -> Self::Output {
+        MetersSquaredPerSecond(self.0 * rhs.0)
+    }
+}
+
+impl Mul<Kilograms> for MetersSquaredPerSecond {
+    type Output = KilogramMetersSquaredPerSecond;
+    fn mul(self, rhs: Kilograms) -> Self::Output {
+        KilogramMetersSquaredPerSecond(self.0 * rhs.0)
+    }
+}
+
+impl KilogramMetersSquaredPerSecond {
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl Add for KilogramMetersSquaredPerSecond {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        KilogramMetersSquaredPerSecond(self.0 + rhs.0)
+    }
+}
+
 impl Seconds {
     pub fn value(&self) -> Real {
         self.0
     }
 }
 
+impl Add for Seconds {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Seconds(self.0 + rhs.0)
+    }
+}
+
 impl RadiansPerSecond {
     pub fn value(&self) -> Real {
         self.0
     }
 }
 
+impl Radians {
+    pub const ZERO: Self = Radians(0.0);
+
+    pub const fn value(&self) -> Real {
+        self.0
+    }
+
+    /// Wrap the angle into the range [0, 2π)
+    pub fn normalized(&self) -> Self {
+        let wrapped = libm::fmod(self.0, TAU);
+        if wrapped >= 0.0 {
+            return Radians(wrapped);
+        }
+        // Tiny negative angles round up to exactly 2π
+        let shifted = wrapped + TAU;
+        Radians(if shifted >= TAU { 0.0 } else { shifted })
+    }
+}
+
+impl Degrees {
+    pub const fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(deg: Degrees) -> Self {
+        Radians(deg.value() * PI / 180.0)
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(rad: Radians) -> Self {
+        Degrees(rad.value() * 180.0 / PI)
+    }
+}
+
+impl Add for Radians {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Radians(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Radians {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Radians(self.0 - rhs.0)
+    }
+}
+
 pub const G: MetersCubedByKilogramSecondsSquared = MetersCubedByKilogramSecondsSquared(6.6742e-11);
 
 impl Pascals {
@@ -477,6 +663,100 @@ impl Sub for Meters {
     }
 }
 
+/// A vector in 3D Cartesian space.
+///
+/// Components are plain [`Real`] values; the unit is set by context (meters
+/// for positions, meters per second for velocities). Used wherever a
+/// direction matters, such as position and velocity state vectors.
+///
+/// # Examples
+/// ```rust
+/// use almagest::utils::Vector3;
+///
+/// let x = Vector3::new(1.0, 0.0, 0.0);
+/// let y = Vector3::new(0.0, 1.0, 0.0);
+/// assert_eq!(x.cross(&y), Vector3::new(0.0, 0.0, 1.0));
+/// assert_eq!(x.dot(&y), 0.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Vector3 {
+    pub x: Real,
+    pub y: Real,
+    pub z: Real,
+}
+
+impl Vector3 {
+    pub const ZERO: Self = Vector3::new(0.0, 0.0, 0.0);
+
+    pub const fn new(x: Real, y: Real, z: Real) -> Self {
+        Vector3 { x, y, z }
+    }
+
+    pub fn dot(&self, rhs: &Self) -> Real {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(&self, rhs: &Self) -> Self {
+        Vector3 {
+            x: self.y * rhs.z - self.z * rhs.y,
+            y: self.z * rhs.x - self.x * rhs.z,
+            z: self.x * rhs.y - self.y * rhs.x,
+        }
+    }
+
+    /// Euclidean length of the vector
+    pub fn norm(&self) -> Real {
+        libm::sqrt(self.dot(self))
+    }
+
+    /// The vector scaled to unit length
+    pub fn unit(&self) -> Self {
+        *self / self.norm()
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<Real> for Vector3 {
+    type Output = Self;
+    fn mul(self, rhs: Real) -> Self::Output {
+        Vector3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Mul<Vector3> for Real {
+    type Output = Vector3;
+    fn mul(self, rhs: Vector3) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<Real> for Vector3 {
+    type Output = Self;
+    fn div(self, rhs: Real) -> Self::Output {
+        Vector3::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Eccentricity(Real);
 
@@ -494,6 +774,26 @@ impl Eccentricity {
     }
 }
 
+/// Minimizer of a unimodal `f` on `[a, b]` by golden-section search
+pub(crate) fn golden_section_minimum<F: Fn(Real) -> Real>(
+    f: F,
+    mut a: Real,
+    mut b: Real,
+    iterations: usize,
+) -> Real {
+    let ratio = (libm::sqrt(5.0) - 1.0) / 2.0;
+    for _ in 0..iterations {
+        let c = b - ratio * (b - a);
+        let d = a + ratio * (b - a);
+        if f(c) < f(d) {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    (a + b) / 2.0
+}
+
 #[cfg(test)]
 mod test_units {
     use super::*;
@@ -655,8 +955,74 @@ mod test_units {
         assert_eq!(e.value(), 0.5);
     }
 
+    // === Angle Tests ===
+
+    #[test]
+    fn degrees_convert_to_radians() {
+        let r: Radians = Degrees(180.0).into();
+        assert_relative_eq!(r.value(), PI, epsilon = 1e-12);
+
+        let d: Degrees = Radians(PI / 2.0).into();
+        assert_relative_eq!(d.value(), 90.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn radians_normalize_into_one_turn() {
+        assert_relative_eq!(Radians(3.0 * PI).normalized().value(), PI, epsilon = 1e-12);
+        assert_relative_eq!(
+            Radians(-PI / 2.0).normalized().value(),
+            1.5 * PI,
+            epsilon = 1e-12
+        );
+        assert_eq!(Radians::ZERO.normalized(), Radians::ZERO);
+        assert_eq!(Radians(-1e-18).normalized(), Radians::ZERO);
+    }
+
+    // === Vector Tests ===
+
+    #[test]
+    fn vector_products() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, 5.0, 6.0);
+
+        assert_eq!(a.dot(&b), 32.0);
+        assert_eq!(a.cross(&b), Vector3::new(-3.0, 6.0, -3.0));
+        // The cross product is orthogonal to both inputs
+        assert_eq!(a.cross(&b).dot(&a), 0.0);
+        assert_eq!(a.cross(&b).dot(&b), 0.0);
+    }
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, 5.0, 6.0);
+
+        assert_eq!(a + b, Vector3::new(5.0, 7.0, 9.0));
+        assert_eq!(b - a, Vector3::new(3.0, 3.0, 3.0));
+        assert_eq!(-a, Vector3::new(-1.0, -2.0, -3.0));
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(b / 2.0, Vector3::new(2.0, 2.5, 3.0));
+    }
+
+    #[test]
+    fn vector_norm_and_unit() {
+        let v = Vector3::new(3.0, 4.0, 12.0);
+        assert_relative_eq!(v.norm(), 13.0, epsilon = 1e-12);
+        assert_relative_eq!(v.unit().norm(), 1.0, epsilon = 1e-12);
+    }
+
     // === Comparison and Ordering Tests ===
 
+    #[test]
+    fn angular_momentum_units() {
+        let h: MetersSquaredPerSecond = Meters(7_000_000.0) * MetersPerSecond(7_500.0);
+        assert_eq!(h, MetersSquaredPerSecond(5.25e10));
+
+        let tether = h * Kilograms(1_000.0);
+        let payload = h * Kilograms(10.0);
+        assert_eq!((tether + payload).value(), 5.25e10 * 1_010.0);
+    }
+
     #[test]
     fn meters_comparison() {
         let a = Meters(5.0);
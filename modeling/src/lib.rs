@@ -1,10 +1,13 @@
 #![allow(dead_code, unused_imports, unused_variables)]
 use almagest::utils::MetersSquared;
 use almagest::{
+    atmosphere::AtmosphereModel,
+    celestials::CelestialBody,
+    decay::{JULIAN_YEAR, ballistic_coefficient, reboost_delta_v},
     materials::Material,
     utils::{
-        CentimetersSquared, Kilograms, Kilometers, Meters, MetersPerSecond, MetersPerSecondSquared,
-        Real,
+        CentimetersSquared, KilogramMetersSquaredPerSecond, Kilograms, Kilometers, Meters,
+        MetersPerSecond, MetersPerSecondSquared, Real,
     },
 };
 
@@ -67,6 +70,32 @@ impl Tether {
         (thinnest, thickest)
     }
 
+    /// Area the tether presents to the oncoming air, averaged over its rotation.
+    ///
+    /// A cable of mean diameter `d` and length `L` spinning in the orbit plane
+    /// shows `L·d·|sin θ|` to the flow, which averages to `2/π · L·d`.
+    pub fn frontal_area(&self) -> MetersSquared {
+        let (thinnest, thickest) = self.cross_sectional_area();
+        let mean: MetersSquared = CentimetersSquared((thinnest.0 + thickest.0) / 2.0).into();
+        let diameter = 2.0 * libm::sqrt(mean.0 / core::f64::consts::PI);
+        let len: Meters = self.length.into();
+        MetersSquared(2.0 / core::f64::consts::PI * len.0 * diameter)
+    }
+
+    /// Δv per year needed to hold the facility at its design `altitude` against drag.
+    ///
+    /// Density is taken at the center of the tether, so the extra drag on tips that
+    /// dip into thicker air is not included.
+    pub fn reboost_per_year<A: AtmosphereModel>(
+        &self,
+        body: &CelestialBody,
+        atmosphere: &A,
+        drag_coefficient: Real,
+    ) -> Result<MetersPerSecond, &'static str> {
+        let ballistic = ballistic_coefficient(self.mass, drag_coefficient, self.frontal_area())?;
+        reboost_delta_v(body, atmosphere, ballistic, self.altitude, JULIAN_YEAR)
+    }
+
     /// NB: this will later be "reduced by the strain imposed by the takeoff acceleration." [Mora77, p.311]
     pub fn max_load(&self, gravity: MetersPerSecondSquared) -> Kilograms {
         let cable_area: MetersSquared = self.cross_sectional_area().0.into();
@@ -113,10 +142,12 @@ impl Tether {
         // Angular momentum before release (tether + payload system)
         // L = I * ω, where I = m * r² for point mass at tip
         // Simplified: treating payload as point mass at tip, tether as point mass at center
-        let tether_angular_momentum = self.mass.0 * orbit_radius.0 * orbital_velocity;
-        let payload_angular_momentum =
-            payload.mass.0 * tip_radius.0 * (orbital_velocity_at_tip + self.rotational_velocity.0);
-        let total_angular_momentum = tether_angular_momentum + payload_angular_momentum;
+        let tether_angular_momentum = orbit_radius * MetersPerSecond(orbital_velocity) * self.mass;
+        let payload_angular_momentum = tip_radius
+            * MetersPerSecond(orbital_velocity_at_tip + self.rotational_velocity.0)
+            * payload.mass;
+        let total_angular_momentum: KilogramMetersSquaredPerSecond =
+            tether_angular_momentum + payload_angular_momentum;
 
         // After release, tether retains most angular momentum
         // Effective velocity transfer depends on mass ratio
@@ -212,6 +243,52 @@ mod tests {
         assert_eq!(t.rotational_velocity, MetersPerSecond(7_000.0));
     }
 
+    mod reboost_tests {
+        use super::*;
+        use almagest::atmosphere::{Atmosphere, AtmosphereKind, SolarActivity};
+        use almagest::celestials::celestial_bodies::EARTH;
+        use approx::assert_relative_eq;
+        use core::f64::consts::PI;
+
+        fn rotovator(altitude: Kilometers) -> Tether {
+            Tether::new(
+                altitude,
+                Kilometers(100.0),
+                Kilograms(50_000.0),
+                KEVLAR_49,
+                MetersPerSecond(2_000.0),
+            )
+        }
+
+        /// Lower facilities sit in denser air and need more reboost
+        #[test]
+        fn reboost_grows_as_altitude_drops() {
+            let atmosphere =
+                Atmosphere::for_body(&EARTH, AtmosphereKind::Tabulated(SolarActivity::Moderate))
+                    .unwrap();
+            let low = rotovator(Kilometers(350.0))
+                .reboost_per_year(&EARTH, &atmosphere, 2.2)
+                .unwrap();
+            let high = rotovator(Kilometers(600.0))
+                .reboost_per_year(&EARTH, &atmosphere, 2.2)
+                .unwrap();
+            assert!(low.0 > 10.0 * high.0, "{} vs {} m/s", low.0, high.0);
+        }
+
+        #[test]
+        fn frontal_area_of_a_thin_cable() {
+            // 50 t of Kevlar over 100 km averages 3.5 cm²; tapering leaves a
+            // mean section of 1.8 cm², a cable about 15 mm across
+            let length = 100_000.0;
+            let mean = (1.0 + 1.124) / 4.0 * 50_000.0 / (length * 1_440.0);
+            let diameter = 2.0 * libm::sqrt(mean / PI);
+            assert_relative_eq!(diameter, 0.0153, epsilon = 1e-4);
+
+            let area = rotovator(Kilometers(400.0)).frontal_area();
+            assert_relative_eq!(area.0, 2.0 / PI * length * diameter, max_relative = 1e-12);
+        }
+    }
+
     mod calc_impulse_tests {
         use super::*;
 